@@ -63,7 +63,9 @@ mod tests {
     use super::*;
     use anchor_spl::associated_token::get_associated_token_address;
     use bpl_token_metadata::state::{
-        AdminSettings, Campaign, CampaignLocation, Device, Location, Merchant, Promo,
+        AdminSettings, Campaign, CampaignLocation, CampaignMember, CustomerVault, DailyLimits,
+        Device, Gift, GiftPolicy, Location, Membership, MembershipPass, Merchant, MintRequest,
+        Promo, PromoTerms, Referral, ReferralReward, Sponsorship, StampCard, VoucherClaim,
     };
     use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
     use mpl_auction_house::{
@@ -242,6 +244,16 @@ mod tests {
             account.merchant.to_string(),
             "it_upserts_campaign: merchant failed"
         );
+        assert_eq!(
+            row.get::<&str, i32>("burn_count"),
+            account.burn_count as i32,
+            "it_upserts_campaign: burn_count failed"
+        );
+        assert_eq!(
+            row.get::<&str, Option<i32>>("max_mint"),
+            account.max_mint.map(|v| v as i32),
+            "it_upserts_campaign: max_mint failed"
+        );
     }
 
     async fn it_upserts_campaign_location(
@@ -330,6 +342,59 @@ mod tests {
             account.close_authority.map(|k| k.to_string()).into(),
             "it_upserts_token_account: close_authority failed"
         );
+        assert!(
+            queries::spl_token::token_account::select_ids(client)
+                .await
+                .unwrap()
+                .contains(&bs58::encode(key).into_string()),
+            "it_upserts_token_account: select_ids failed"
+        );
+    }
+
+    async fn it_deletes_token_account(
+        client: &Client,
+        key: &[u8],
+        slot: u64,
+        write_version: u64,
+        deleted: bool,
+    ) {
+        queries::spl_token::token_account::delete(client, key, slot, write_version).await;
+        let rows = client
+            .query(
+                "SELECT * FROM token_account WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            rows.is_empty(),
+            deleted,
+            "it_deletes_token_account: delete failed"
+        );
+    }
+
+    async fn it_flags_clawed_back_token_account(client: &Client, key: &Pubkey, slot: u64) {
+        let mut accounts: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        accounts[7] = *key;
+        queries::bpl_token_metadata::clawback_promo_token::upsert(
+            client,
+            &Signature::new_unique(),
+            &accounts,
+            &[0; 8],
+            slot,
+        )
+        .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM token_account WHERE id = $1",
+                &[&key.to_string()],
+            )
+            .await
+            .unwrap();
+        assert!(
+            row.get::<&str, bool>("clawed_back"),
+            "it_flags_clawed_back_token_account: clawed_back failed"
+        );
     }
 
     async fn it_upserts_metadata(
@@ -375,6 +440,110 @@ mod tests {
             promo.mint_count as i32,
             "it_upserts_promo: mints"
         );
+        assert_eq!(
+            row.get::<&str, Option<String>>("referral_reward_mint"),
+            promo.referral.map(|r| r.reward_mint.to_string()),
+            "it_upserts_promo: referral_reward_mint"
+        );
+        assert_eq!(
+            row.get::<&str, Option<i32>>("get_y_percent"),
+            match promo.terms {
+                Some(PromoTerms::BuyXCurrencyGetYPercent { get_y_percent, .. }) => {
+                    Some(get_y_percent as i32)
+                }
+                _ => None,
+            },
+            "it_upserts_promo: get_y_percent"
+        );
+    }
+
+    async fn it_upserts_campaign_member(
+        client: &Client,
+        key: &[u8],
+        account: &CampaignMember,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::campaign_member::upsert(
+            client,
+            key,
+            account,
+            slot,
+            write_version,
+        )
+        .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM campaign_member WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, String>("merchant"),
+            account.merchant.to_string(),
+            "it_upserts_campaign_member: merchant failed"
+        );
+    }
+
+    async fn it_upserts_referral(
+        client: &Client,
+        key: &[u8],
+        account: &Referral,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::referral::upsert(client, key, account, slot, write_version)
+            .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM referral WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, String>("referrer"),
+            account.referrer.to_string(),
+            "it_upserts_referral: referrer failed"
+        );
+        assert_eq!(
+            row.get::<&str, bool>("rewarded"),
+            account.rewarded,
+            "it_upserts_referral: rewarded failed"
+        );
+    }
+
+    async fn it_upserts_sponsorship(
+        client: &Client,
+        key: &[u8],
+        account: &Sponsorship,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::sponsorship::upsert(client, key, account, slot, write_version)
+            .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM sponsorship WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, Vec<String>>("mints"),
+            account
+                .mints
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            "it_upserts_sponsorship: mints failed"
+        );
+        assert_eq!(
+            row.get::<&str, i64>("spent_lamports"),
+            account.spent_lamports as i64,
+            "it_upserts_sponsorship: spent_lamports failed"
+        );
     }
 
     async fn it_upserts_auction_house(
@@ -502,6 +671,215 @@ mod tests {
         );
     }
 
+    async fn it_upserts_stamp_card(
+        client: &Client,
+        key: &[u8],
+        account: &StampCard,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::stamp_card::upsert(client, key, account, slot, write_version)
+            .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM stamp_card WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, i32>("stamps"),
+            account.stamps as i32,
+            "it_upserts_stamp_card: stamps failed"
+        );
+        assert_eq!(
+            row.get::<&str, i32>("rewards"),
+            account.rewards as i32,
+            "it_upserts_stamp_card: rewards failed"
+        );
+    }
+
+    async fn it_upserts_voucher_claim(
+        client: &Client,
+        key: &[u8],
+        account: &VoucherClaim,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::voucher_claim::upsert(
+            client,
+            key,
+            account,
+            slot,
+            write_version,
+        )
+        .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM voucher_claim WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, i64>("nonce"),
+            account.nonce as i64,
+            "it_upserts_voucher_claim: nonce failed"
+        );
+        assert_eq!(
+            row.get::<&str, String>("claimed_by"),
+            account.claimed_by.to_string(),
+            "it_upserts_voucher_claim: claimed_by failed"
+        );
+    }
+
+    async fn it_upserts_mint_request(
+        client: &Client,
+        key: &[u8],
+        account: &MintRequest,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::mint_request::upsert(
+            client,
+            key,
+            account,
+            slot,
+            write_version,
+        )
+        .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM mint_request WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, String>("token_owner"),
+            account.token_owner.to_string(),
+            "it_upserts_mint_request: token_owner failed"
+        );
+        assert_eq!(
+            row.get::<&str, i64>("requested_at"),
+            account.requested_at,
+            "it_upserts_mint_request: requested_at failed"
+        );
+    }
+
+    async fn it_upserts_gift(
+        client: &Client,
+        key: &[u8],
+        account: &Gift,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::gift::upsert(client, key, account, slot, write_version).await;
+        let row = client
+            .query_one(
+                "SELECT * FROM gift WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, String>("recipient"),
+            account.recipient.to_string(),
+            "it_upserts_gift: recipient failed"
+        );
+    }
+
+    async fn it_upserts_customer_vault(
+        client: &Client,
+        key: &[u8],
+        account: &CustomerVault,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::customer_vault::upsert(
+            client,
+            key,
+            account,
+            slot,
+            write_version,
+        )
+        .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM customer_vault WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, String>("identity_hash"),
+            bs58::encode(account.identity_hash).into_string(),
+            "it_upserts_customer_vault: identity_hash failed"
+        );
+        assert_eq!(
+            row.get::<&str, Option<String>>("claimed_by"),
+            account.claimed_by.map(|c| c.to_string()),
+            "it_upserts_customer_vault: claimed_by failed"
+        );
+    }
+
+    async fn it_upserts_membership(
+        client: &Client,
+        key: &[u8],
+        account: &Membership,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::membership::upsert(client, key, account, slot, write_version)
+            .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM membership WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, i64>("pass_count"),
+            account.pass_count as i64,
+            "it_upserts_membership: pass_count failed"
+        );
+    }
+
+    async fn it_upserts_membership_pass(
+        client: &Client,
+        key: &[u8],
+        account: &MembershipPass,
+        slot: u64,
+        write_version: u64,
+    ) {
+        queries::bpl_token_metadata::membership_pass::upsert(
+            client,
+            key,
+            account,
+            slot,
+            write_version,
+        )
+        .await;
+        let row = client
+            .query_one(
+                "SELECT * FROM membership_pass WHERE id = $1",
+                &[&bs58::encode(key).into_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<&str, i64>("expires_at"),
+            account.expires_at,
+            "it_upserts_membership_pass: expires_at failed"
+        );
+        assert_eq!(
+            row.get::<&str, i64>("check_ins"),
+            account.check_ins as i64,
+            "it_upserts_membership_pass: check_ins failed"
+        );
+    }
+
     // =============================
     // Transactions
     // =============================
@@ -525,6 +903,59 @@ mod tests {
                 client, signature, accounts, data, slot,
             )
             .await;
+        } else if table == "stamp_promo_card" {
+            queries::bpl_token_metadata::stamp_promo_card::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "claim_promo_voucher" {
+            queries::bpl_token_metadata::claim_promo_voucher::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "request_promo_mint" {
+            queries::bpl_token_metadata::request_promo_mint::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "approve_mint_request" {
+            queries::bpl_token_metadata::approve_mint_request::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "gift_promo_token" {
+            queries::bpl_token_metadata::gift_promo_token::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "mint_promo_token_to_vault" {
+            queries::bpl_token_metadata::mint_promo_token_to_vault::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "claim_customer_vault" {
+            queries::bpl_token_metadata::claim_customer_vault::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "create_membership" {
+            queries::bpl_token_metadata::create_membership::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "issue_membership_pass" {
+            queries::bpl_token_metadata::issue_membership_pass::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "renew_membership_pass" {
+            queries::bpl_token_metadata::renew_membership_pass::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "check_in" {
+            queries::bpl_token_metadata::check_in::upsert(client, signature, accounts, data, slot)
+                .await;
         } else if table == "delegate_promo_token" {
             queries::bpl_token_metadata::delegate_promo_token::upsert(
                 client, signature, accounts, data, slot,
@@ -535,6 +966,16 @@ mod tests {
                 client, signature, accounts, balances, data, slot,
             )
             .await;
+        } else if table == "redeem_promo_token" {
+            queries::bpl_token_metadata::redeem_promo_token::upsert(
+                client, signature, accounts, balances, data, slot,
+            )
+            .await;
+        } else if table == "clawback_promo_token" {
+            queries::bpl_token_metadata::clawback_promo_token::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
         } else if table == "create_campaign" {
             queries::bpl_token_metadata::create_campaign::upsert(
                 client, signature, accounts, data, slot,
@@ -545,6 +986,26 @@ mod tests {
                 client, signature, accounts, data, slot,
             )
             .await;
+        } else if table == "create_campaign_locations" {
+            queries::bpl_token_metadata::create_campaign_locations::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "create_sponsorship" {
+            queries::bpl_token_metadata::create_sponsorship::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "close_sponsorship" {
+            queries::bpl_token_metadata::close_sponsorship::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
+        } else if table == "join_campaign" {
+            queries::bpl_token_metadata::join_campaign::upsert(
+                client, signature, accounts, data, slot,
+            )
+            .await;
         } else if table == "create_merchant" {
             queries::bpl_token_metadata::create_merchant::upsert(
                 client, signature, accounts, data, slot,
@@ -607,8 +1068,8 @@ mod tests {
 
         // insert transactions
         let data: &[u8] = &[0; 8];
-        let accounts: Vec<Pubkey> = (0..12).map(|_| Pubkey::new_unique()).collect();
-        let balances: Vec<u64> = (0..12).map(|i| i).collect();
+        let accounts: Vec<Pubkey> = (0..16).map(|_| Pubkey::new_unique()).collect();
+        let balances: Vec<u64> = (0..16).map(|i| i).collect();
 
         for table in vec![
             "create_merchant",
@@ -616,10 +1077,27 @@ mod tests {
             "create_device",
             "create_campaign",
             "create_campaign_location",
+            "create_campaign_locations",
+            "join_campaign",
+            "create_sponsorship",
+            "close_sponsorship",
             "create_promo",
             "mint_promo_token",
+            "stamp_promo_card",
+            "claim_promo_voucher",
+            "request_promo_mint",
+            "approve_mint_request",
+            "gift_promo_token",
+            "mint_promo_token_to_vault",
+            "claim_customer_vault",
+            "create_membership",
+            "issue_membership_pass",
+            "renew_membership_pass",
+            "check_in",
             "delegate_promo_token",
             "burn_delegated_promo_token",
+            "redeem_promo_token",
+            "clawback_promo_token",
         ] {
             it_upserts_transaction(
                 &client,
@@ -639,6 +1117,7 @@ mod tests {
             platform: Pubkey::new_unique(),
             create_promo_lamports: 42,
             burn_promo_token_lamports: 69,
+            vault_authority: Pubkey::new_unique(),
         };
         it_upserts_admin_settings(
             &client,
@@ -691,6 +1170,10 @@ mod tests {
             name: "Test Campaign".to_string(),
             uri: "https://campaign.example.com".to_string(),
             active: true,
+            mint_count: 12,
+            burn_count: 3,
+            max_mint: Some(2000),
+            max_burn: None,
         };
 
         it_upserts_campaign(&client, campaign_pubkey.as_ref(), &campaign, 42, 1).await;
@@ -700,6 +1183,11 @@ mod tests {
         let campaign_location = CampaignLocation {
             campaign: Pubkey::new_unique(),
             location: Pubkey::new_unique(),
+            location_limits: DailyLimits {
+                max_mint: Some(100),
+                max_burn: None,
+            },
+            ..Default::default()
         };
         it_upserts_campaign_location(
             &client,
@@ -710,6 +1198,20 @@ mod tests {
         )
         .await;
 
+        // upsert campaign_member
+        let campaign_member = CampaignMember {
+            campaign: campaign_location.campaign,
+            merchant: Pubkey::new_unique(),
+        };
+        it_upserts_campaign_member(
+            &client,
+            Pubkey::new_unique().as_ref(),
+            &campaign_member,
+            42,
+            1,
+        )
+        .await;
+
         // update a mint, null out an optional value
         mint.supply = 2;
         mint.mint_authority = COption::None;
@@ -735,6 +1237,15 @@ mod tests {
         token_account.close_authority = COption::None;
         it_upserts_token_account(&client, token_pubkey.as_ref(), &token_account, 43, 1).await;
 
+        // flag a clawed back token account
+        it_flags_clawed_back_token_account(&client, &token_pubkey, 43).await;
+
+        // close a token account, ignoring closes older than the latest update
+        let closed_pubkey = Pubkey::new_unique();
+        it_upserts_token_account(&client, closed_pubkey.as_ref(), &token_account, 44, 2).await;
+        it_deletes_token_account(&client, closed_pubkey.as_ref(), 44, 1, false).await;
+        it_deletes_token_account(&client, closed_pubkey.as_ref(), 45, 1, true).await;
+
         // insert a metadata account
         let creators = (0..5)
             .map(|_| Creator {
@@ -793,10 +1304,193 @@ mod tests {
             max_mint: Some(88),
             max_burn: Some(42),
             active: true,
+            schedule: None,
+            allowlist: None,
+            referral: Some(ReferralReward {
+                reward_mint: Pubkey::new_unique(),
+                max_rewards: Some(100),
+                reward_count: 0,
+            }),
+            stamp_threshold: Some(10),
+            terms: Some(PromoTerms::BuyXCurrencyGetYPercent {
+                buy_x_currency: 10_000,
+                get_y_percent: 10,
+            }),
+            requires_approval: true,
+            gift_policy: GiftPolicy::AllowedOnce,
         };
 
         it_upserts_promo(&client, key.as_ref(), &promo, 42, 1).await;
 
+        // insert and update a referral
+        let referral_pubkey = Pubkey::new_unique();
+        let mut referral = Referral {
+            mint: mint_pubkey,
+            referrer: Pubkey::new_unique(),
+            referred: owner,
+            rewarded: false,
+        };
+        it_upserts_referral(&client, referral_pubkey.as_ref(), &referral, 42, 1).await;
+        referral.rewarded = true;
+        it_upserts_referral(&client, referral_pubkey.as_ref(), &referral, 43, 1).await;
+
+        // insert and update a stamp_card
+        let stamp_card_pubkey = Pubkey::new_unique();
+        let mut stamp_card = StampCard {
+            mint: mint_pubkey,
+            owner,
+            stamps: 1,
+            rewards: 0,
+            last_stamped_at: get_now() / 1000,
+        };
+        it_upserts_stamp_card(&client, stamp_card_pubkey.as_ref(), &stamp_card, 42, 1).await;
+        stamp_card.stamps = 0;
+        stamp_card.rewards = 1;
+        it_upserts_stamp_card(&client, stamp_card_pubkey.as_ref(), &stamp_card, 43, 1).await;
+
+        // insert a voucher_claim
+        let voucher_claim = VoucherClaim {
+            promo: Pubkey::new_unique(),
+            nonce: 7,
+            device: Pubkey::new_unique(),
+            claimed_by: owner,
+            claimed_at: get_now() / 1000,
+        };
+        it_upserts_voucher_claim(
+            &client,
+            Pubkey::new_unique().as_ref(),
+            &voucher_claim,
+            42,
+            1,
+        )
+        .await;
+
+        // insert a mint_request and reject it
+        let mint_request_pubkey = Pubkey::new_unique();
+        let mint_request = MintRequest {
+            mint: mint_pubkey,
+            token_owner: owner,
+            campaign,
+            payer: Pubkey::new_unique(),
+            requested_at: get_now() / 1000,
+        };
+        it_upserts_mint_request(&client, mint_request_pubkey.as_ref(), &mint_request, 42, 1).await;
+
+        let mut reject_accounts: Vec<Pubkey> = (0..11).map(|_| Pubkey::new_unique()).collect();
+        reject_accounts[8] = mint_request_pubkey;
+        queries::bpl_token_metadata::reject_mint_request::upsert(
+            &client,
+            &Signature::new_unique(),
+            &reject_accounts,
+            data,
+            43,
+        )
+        .await;
+        let row = client
+            .query_one(
+                "SELECT COUNT(*) FROM pending_mint_request WHERE id = $1",
+                &[&mint_request_pubkey.to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<usize, i64>(0),
+            0,
+            "it_upserts_reject_mint_request: pending_mint_request failed"
+        );
+
+        // insert a gift
+        let gift = Gift {
+            mint: mint_pubkey,
+            sender: owner,
+            recipient: Pubkey::new_unique(),
+            gifted_at: get_now() / 1000,
+        };
+        it_upserts_gift(&client, Pubkey::new_unique().as_ref(), &gift, 42, 1).await;
+
+        // insert and claim a customer vault
+        let customer_vault_pubkey = Pubkey::new_unique();
+        let mut customer_vault = CustomerVault {
+            identity_hash: [7; 32],
+            created_at: get_now() / 1000,
+            claimed_by: None,
+        };
+        it_upserts_customer_vault(
+            &client,
+            customer_vault_pubkey.as_ref(),
+            &customer_vault,
+            42,
+            1,
+        )
+        .await;
+        customer_vault.claimed_by = Some(owner);
+        it_upserts_customer_vault(
+            &client,
+            customer_vault_pubkey.as_ref(),
+            &customer_vault,
+            43,
+            1,
+        )
+        .await;
+
+        // insert a membership and issue, renew and check in a pass
+        let membership_pubkey = Pubkey::new_unique();
+        let mut membership = Membership {
+            merchant: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            duration: 30 * 24 * 60 * 60,
+            pass_count: 0,
+        };
+        it_upserts_membership(&client, membership_pubkey.as_ref(), &membership, 42, 1).await;
+        membership.pass_count = 1;
+        it_upserts_membership(&client, membership_pubkey.as_ref(), &membership, 43, 1).await;
+
+        let membership_pass_pubkey = Pubkey::new_unique();
+        let issued_at = get_now() / 1000;
+        let mut membership_pass = MembershipPass {
+            membership: membership_pubkey,
+            mint: Pubkey::new_unique(),
+            edition: 1,
+            issued_to: owner,
+            issued_at,
+            expires_at: issued_at + membership.duration,
+            ..Default::default()
+        };
+        it_upserts_membership_pass(
+            &client,
+            membership_pass_pubkey.as_ref(),
+            &membership_pass,
+            43,
+            1,
+        )
+        .await;
+        membership_pass.expires_at += membership.duration;
+        membership_pass.renewals = 1;
+        membership_pass.check_ins = 1;
+        membership_pass.last_check_in_at = issued_at;
+        it_upserts_membership_pass(
+            &client,
+            membership_pass_pubkey.as_ref(),
+            &membership_pass,
+            44,
+            1,
+        )
+        .await;
+
+        // insert and update a sponsorship
+        let sponsorship_pubkey = Pubkey::new_unique();
+        let mut sponsorship = Sponsorship {
+            sponsor: Pubkey::new_unique(),
+            campaign: campaign_location.campaign,
+            mints: vec![mint_pubkey],
+            reimbursement_lamports: 1_000_000,
+            ..Default::default()
+        };
+        it_upserts_sponsorship(&client, sponsorship_pubkey.as_ref(), &sponsorship, 42, 1).await;
+        sponsorship.redemptions = 1;
+        sponsorship.spent_lamports = 1_000_000 + admin_settings.burn_promo_token_lamports;
+        it_upserts_sponsorship(&client, sponsorship_pubkey.as_ref(), &sponsorship, 43, 1).await;
+
         // insert an auction_house
         let ah_authority = Pubkey::new_unique();
         let (ah_pubkey, _) = find_auction_house_address(&ah_authority, &mint_pubkey);
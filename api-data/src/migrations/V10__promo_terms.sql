@@ -0,0 +1,8 @@
+ALTER TABLE public.promo
+    ADD COLUMN promo_type text,
+    ADD COLUMN product_id text,
+    ADD COLUMN buy_x_product int,
+    ADD COLUMN get_y_product int,
+    ADD COLUMN buy_x_currency bigint,
+    ADD COLUMN get_y_percent int,
+    ADD COLUMN amount_off bigint;
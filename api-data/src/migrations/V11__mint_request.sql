@@ -0,0 +1,101 @@
+ALTER TABLE public.promo
+    ADD COLUMN requires_approval boolean;
+
+CREATE TABLE public.mint_request (
+    id text NOT NULL,
+    mint text NOT NULL,
+    token_owner text NOT NULL,
+    campaign text NOT NULL,
+    payer text NOT NULL,
+    requested_at bigint NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.mint_request
+    ADD CONSTRAINT mint_request_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.request_promo_mint (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    token_owner text NOT NULL,
+    campaign text NOT NULL,
+    mint text NOT NULL,
+    authority text NOT NULL,
+    promo text NOT NULL,
+    mint_request text NOT NULL,
+    token_account text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.request_promo_mint
+    ADD CONSTRAINT request_promo_mint_pkey PRIMARY KEY (signature);
+
+CREATE TABLE public.approve_mint_request (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    device_owner text NOT NULL,
+    device text NOT NULL,
+    campaign text NOT NULL,
+    campaign_location text NOT NULL,
+    token_owner text NOT NULL,
+    mint text NOT NULL,
+    authority text NOT NULL,
+    promo text NOT NULL,
+    mint_request text NOT NULL,
+    request_payer text NOT NULL,
+    token_account text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.approve_mint_request
+    ADD CONSTRAINT approve_mint_request_pkey PRIMARY KEY (signature);
+
+CREATE TABLE public.reject_mint_request (
+    signature text NOT NULL,
+    device_owner text NOT NULL,
+    device text NOT NULL,
+    campaign text NOT NULL,
+    campaign_location text NOT NULL,
+    token_owner text NOT NULL,
+    mint text NOT NULL,
+    authority text NOT NULL,
+    promo text NOT NULL,
+    mint_request text NOT NULL,
+    request_payer text NOT NULL,
+    token_account text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.reject_mint_request
+    ADD CONSTRAINT reject_mint_request_pkey PRIMARY KEY (signature);
+
+-- Closed request accounts stay in mint_request, so a request is pending until an approval
+-- or rejection lands at or after the slot it was last written.
+CREATE VIEW public.pending_mint_request AS
+    SELECT
+        mr.id,
+        mr.mint,
+        mr.token_owner,
+        mr.campaign,
+        c.merchant,
+        mr.payer,
+        mr.requested_at,
+        mr.slot
+    FROM mint_request mr
+    JOIN campaign c ON c.id = mr.campaign
+    WHERE NOT EXISTS (
+        SELECT 1 FROM approve_mint_request a
+        WHERE a.mint_request = mr.id AND a.slot >= mr.slot
+    )
+    AND NOT EXISTS (
+        SELECT 1 FROM reject_mint_request r
+        WHERE r.mint_request = mr.id AND r.slot >= mr.slot
+    );
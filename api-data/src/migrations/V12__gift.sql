@@ -0,0 +1,34 @@
+ALTER TABLE public.promo
+    ADD COLUMN gift_policy text;
+
+CREATE TABLE public.gift (
+    id text NOT NULL,
+    mint text NOT NULL,
+    sender text NOT NULL,
+    recipient text NOT NULL,
+    gifted_at bigint NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.gift
+    ADD CONSTRAINT gift_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.gift_promo_token (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    sender text NOT NULL,
+    recipient text NOT NULL,
+    mint text NOT NULL,
+    promo text NOT NULL,
+    sender_token_account text NOT NULL,
+    recipient_token_account text NOT NULL,
+    gift text,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.gift_promo_token
+    ADD CONSTRAINT gift_promo_token_pkey PRIMARY KEY (signature);
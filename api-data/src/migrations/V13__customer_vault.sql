@@ -0,0 +1,54 @@
+ALTER TABLE public.admin_settings
+    ADD COLUMN vault_authority text;
+
+CREATE TABLE public.customer_vault (
+    id text NOT NULL,
+    identity_hash text NOT NULL,
+    created_at_on_chain bigint NOT NULL,
+    claimed_by text,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.customer_vault
+    ADD CONSTRAINT customer_vault_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.mint_promo_token_to_vault (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    device_owner text NOT NULL,
+    device text NOT NULL,
+    campaign text NOT NULL,
+    campaign_location text NOT NULL,
+    customer_vault text NOT NULL,
+    mint text NOT NULL,
+    authority text NOT NULL,
+    promo text NOT NULL,
+    token_account text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.mint_promo_token_to_vault
+    ADD CONSTRAINT mint_promo_token_to_vault_pkey PRIMARY KEY (signature);
+
+CREATE TABLE public.claim_customer_vault (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    vault_authority text NOT NULL,
+    admin_settings text NOT NULL,
+    customer_vault text NOT NULL,
+    token_owner text NOT NULL,
+    mint text NOT NULL,
+    authority text NOT NULL,
+    vault_token_account text NOT NULL,
+    token_account text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.claim_customer_vault
+    ADD CONSTRAINT claim_customer_vault_pkey PRIMARY KEY (signature);
@@ -0,0 +1,110 @@
+CREATE TABLE public.membership (
+    id text NOT NULL,
+    merchant text NOT NULL,
+    mint text NOT NULL,
+    duration bigint NOT NULL,
+    pass_count bigint NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.membership
+    ADD CONSTRAINT membership_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.membership_pass (
+    id text NOT NULL,
+    membership text NOT NULL,
+    mint text NOT NULL,
+    edition bigint NOT NULL,
+    issued_to text NOT NULL,
+    issued_at bigint NOT NULL,
+    expires_at bigint NOT NULL,
+    renewals bigint NOT NULL,
+    check_ins bigint NOT NULL,
+    last_check_in_at bigint NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.membership_pass
+    ADD CONSTRAINT membership_pass_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.create_membership (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    owner text NOT NULL,
+    merchant text NOT NULL,
+    authority text NOT NULL,
+    mint text NOT NULL,
+    token_account text NOT NULL,
+    metadata text NOT NULL,
+    edition text NOT NULL,
+    membership text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.create_membership
+    ADD CONSTRAINT create_membership_pkey PRIMARY KEY (signature);
+
+CREATE TABLE public.issue_membership_pass (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    owner text NOT NULL,
+    merchant text NOT NULL,
+    membership text NOT NULL,
+    authority text NOT NULL,
+    master_mint text NOT NULL,
+    master_token_account text NOT NULL,
+    master_metadata text NOT NULL,
+    master_edition text NOT NULL,
+    token_owner text NOT NULL,
+    pass_mint text NOT NULL,
+    token_account text NOT NULL,
+    pass_metadata text NOT NULL,
+    pass_edition text NOT NULL,
+    edition_marker text NOT NULL,
+    membership_pass text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.issue_membership_pass
+    ADD CONSTRAINT issue_membership_pass_pkey PRIMARY KEY (signature);
+
+CREATE TABLE public.renew_membership_pass (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    owner text NOT NULL,
+    merchant text NOT NULL,
+    membership text NOT NULL,
+    membership_pass text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.renew_membership_pass
+    ADD CONSTRAINT renew_membership_pass_pkey PRIMARY KEY (signature);
+
+CREATE TABLE public.check_in (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    device_owner text NOT NULL,
+    device text NOT NULL,
+    location text NOT NULL,
+    membership text NOT NULL,
+    membership_pass text NOT NULL,
+    token_owner text NOT NULL,
+    token_account text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.check_in
+    ADD CONSTRAINT check_in_pkey PRIMARY KEY (signature);
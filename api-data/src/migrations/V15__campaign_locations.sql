@@ -0,0 +1,15 @@
+CREATE TABLE public.create_campaign_locations (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    owner text NOT NULL,
+    merchant text NOT NULL,
+    campaign text NOT NULL,
+    locations text[] NOT NULL,
+    campaign_locations text[] NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.create_campaign_locations
+    ADD CONSTRAINT create_campaign_locations_pkey PRIMARY KEY (signature);
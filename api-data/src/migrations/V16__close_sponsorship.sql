@@ -0,0 +1,16 @@
+-- Reimbursements are paid to the owner of the merchant of the redeeming location rather than a
+-- payee recorded in the sponsorship.
+ALTER TABLE public.sponsorship
+    DROP COLUMN payee;
+
+CREATE TABLE public.close_sponsorship (
+    signature text NOT NULL,
+    sponsor text NOT NULL,
+    sponsorship text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.close_sponsorship
+    ADD CONSTRAINT close_sponsorship_pkey PRIMARY KEY (signature);
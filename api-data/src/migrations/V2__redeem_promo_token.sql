@@ -0,0 +1,75 @@
+CREATE TABLE public.redeem_promo_token (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    payer_balance bigint NOT NULL,
+    device_owner text NOT NULL,
+    device text NOT NULL,
+    campaign text NOT NULL,
+    campaign_balance bigint NOT NULL,
+    campaign_location text NOT NULL,
+    token_owner text NOT NULL,
+    mint text NOT NULL,
+    promo text NOT NULL,
+    platform text NOT NULL,
+    platform_balance bigint NOT NULL,
+    admin_settings text NOT NULL,
+    token_account text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.redeem_promo_token
+    ADD CONSTRAINT redeem_promo_token_pkey PRIMARY KEY (signature);
+
+CREATE OR REPLACE VIEW public.promo_transactions AS
+    SELECT
+        mp.signature, 'mint' as transaction_type,
+        mp.payer,
+        mp.device_owner,
+        mp.device,
+        mp.campaign,
+        mp.campaign_location,
+        mp.mint,
+        mp.authority,
+        mp.promo,
+        mp.token_account,
+        mp.memo,
+        mp.slot,
+        mp.created_at,
+        mp.modified_at
+    FROM mint_promo_token mp
+    UNION ALL
+        SELECT
+            bdp.signature, 'burn_delegated' as transaction_type,
+            bdp.payer,
+            bdp.device_owner,
+            bdp.device,
+            bdp.campaign,
+            bdp.campaign_location,
+            bdp.mint,
+            bdp.authority,
+            bdp.promo,
+            bdp.token_account,
+            bdp.memo,
+            bdp.slot,
+            bdp.created_at,
+            bdp.modified_at
+        FROM burn_delegated_promo_token bdp
+    UNION ALL
+        SELECT
+            rp.signature, 'redeem' as transaction_type,
+            rp.payer,
+            rp.device_owner,
+            rp.device,
+            rp.campaign,
+            rp.campaign_location,
+            rp.mint,
+            NULL as authority,
+            rp.promo,
+            rp.token_account,
+            rp.memo,
+            rp.slot,
+            rp.created_at,
+            rp.modified_at
+        FROM redeem_promo_token rp;
@@ -0,0 +1,5 @@
+ALTER TABLE public.campaign
+    ADD COLUMN mint_count int NOT NULL DEFAULT 0,
+    ADD COLUMN burn_count int NOT NULL DEFAULT 0,
+    ADD COLUMN max_mint int,
+    ADD COLUMN max_burn int;
\ No newline at end of file
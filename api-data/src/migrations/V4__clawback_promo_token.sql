@@ -0,0 +1,21 @@
+ALTER TABLE public.token_account
+    ADD COLUMN clawed_back boolean NOT NULL DEFAULT false;
+
+CREATE TABLE public.clawback_promo_token (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    owner text NOT NULL,
+    merchant text NOT NULL,
+    campaign text NOT NULL,
+    mint text NOT NULL,
+    authority text NOT NULL,
+    promo text NOT NULL,
+    token_account text NOT NULL,
+    burn boolean,
+    reason text,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.clawback_promo_token
+    ADD CONSTRAINT clawback_promo_token_pkey PRIMARY KEY (signature);
@@ -0,0 +1,18 @@
+ALTER TABLE public.promo
+    ADD COLUMN referral_reward_mint text,
+    ADD COLUMN max_referral_rewards int,
+    ADD COLUMN referral_reward_count int;
+
+CREATE TABLE public.referral (
+    id text NOT NULL,
+    mint text NOT NULL,
+    referrer text NOT NULL,
+    referred text NOT NULL,
+    rewarded boolean NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.referral
+    ADD CONSTRAINT referral_pkey PRIMARY KEY (id);
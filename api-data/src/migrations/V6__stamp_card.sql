@@ -0,0 +1,38 @@
+ALTER TABLE public.promo
+    ADD COLUMN stamp_threshold int;
+
+CREATE TABLE public.stamp_card (
+    id text NOT NULL,
+    mint text NOT NULL,
+    owner text NOT NULL,
+    stamps int NOT NULL,
+    rewards int NOT NULL,
+    last_stamped_at bigint NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.stamp_card
+    ADD CONSTRAINT stamp_card_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.stamp_promo_card (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    device_owner text NOT NULL,
+    device text NOT NULL,
+    campaign text NOT NULL,
+    campaign_location text NOT NULL,
+    token_owner text NOT NULL,
+    mint text NOT NULL,
+    authority text NOT NULL,
+    promo text NOT NULL,
+    stamp_card text NOT NULL,
+    token_account text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.stamp_promo_card
+    ADD CONSTRAINT stamp_promo_card_pkey PRIMARY KEY (signature);
@@ -0,0 +1,35 @@
+CREATE TABLE public.campaign_member (
+    id text NOT NULL,
+    campaign text NOT NULL,
+    merchant text NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.campaign_member
+    ADD CONSTRAINT campaign_member_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.join_campaign (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    campaign_owner text NOT NULL,
+    campaign_merchant text NOT NULL,
+    campaign text NOT NULL,
+    owner text NOT NULL,
+    merchant text NOT NULL,
+    campaign_member text NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.join_campaign
+    ADD CONSTRAINT join_campaign_pkey PRIMARY KEY (signature);
+
+-- Merchant of the location where the token was redeemed, which for coalition campaigns can be
+-- a member merchant rather than the merchant that created the campaign.
+ALTER TABLE public.burn_delegated_promo_token
+    ADD COLUMN merchant text;
+ALTER TABLE public.redeem_promo_token
+    ADD COLUMN merchant text;
@@ -0,0 +1,47 @@
+CREATE TABLE public.sponsorship (
+    id text NOT NULL,
+    sponsor text NOT NULL,
+    campaign text NOT NULL,
+    payee text NOT NULL,
+    mints text[] NOT NULL,
+    reimbursement_lamports bigint NOT NULL,
+    redemptions int NOT NULL,
+    spent_lamports bigint NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.sponsorship
+    ADD CONSTRAINT sponsorship_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.create_sponsorship (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    sponsor text NOT NULL,
+    merchant text NOT NULL,
+    campaign text NOT NULL,
+    sponsorship text NOT NULL,
+    lamports bigint NOT NULL,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.create_sponsorship
+    ADD CONSTRAINT create_sponsorship_pkey PRIMARY KEY (signature);
+
+-- Sponsorship that paid for the redemption, if any.
+ALTER TABLE public.burn_delegated_promo_token
+    ADD COLUMN sponsorship text;
+ALTER TABLE public.redeem_promo_token
+    ADD COLUMN sponsorship text;
+
+CREATE VIEW public.sponsor_spend AS
+    SELECT
+        sponsor,
+        COUNT(*) sponsorships,
+        SUM(redemptions) redemptions,
+        SUM(spent_lamports) spent_lamports
+    FROM sponsorship
+    GROUP BY sponsor;
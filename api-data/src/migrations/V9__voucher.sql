@@ -0,0 +1,37 @@
+CREATE TABLE public.voucher_claim (
+    id text NOT NULL,
+    promo text NOT NULL,
+    nonce bigint NOT NULL,
+    device text NOT NULL,
+    claimed_by text NOT NULL,
+    claimed_at bigint NOT NULL,
+    slot bigint NOT NULL,
+    write_version bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.voucher_claim
+    ADD CONSTRAINT voucher_claim_pkey PRIMARY KEY (id);
+
+CREATE TABLE public.claim_promo_voucher (
+    signature text NOT NULL,
+    payer text NOT NULL,
+    device_owner text NOT NULL,
+    device text NOT NULL,
+    campaign text NOT NULL,
+    campaign_location text NOT NULL,
+    token_owner text NOT NULL,
+    mint text NOT NULL,
+    authority text NOT NULL,
+    promo text NOT NULL,
+    voucher_claim text NOT NULL,
+    token_account text NOT NULL,
+    nonce bigint,
+    expiry bigint,
+    memo jsonb,
+    slot bigint NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    modified_at timestamp with time zone DEFAULT now() NOT NULL
+);
+ALTER TABLE ONLY public.claim_promo_voucher
+    ADD CONSTRAINT claim_promo_voucher_pkey PRIMARY KEY (signature);
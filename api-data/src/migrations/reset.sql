@@ -6,25 +6,53 @@ SET check_function_bodies = false;
 -- =============================
 
 DROP TABLE IF EXISTS public.admin_settings CASCADE;
+DROP TABLE IF EXISTS public.approve_mint_request CASCADE;
 DROP TABLE IF EXISTS public.burn_delegated_promo_token CASCADE;
 DROP TABLE IF EXISTS public.campaign CASCADE;
 DROP TABLE IF EXISTS public.campaign_location CASCADE;
+DROP TABLE IF EXISTS public.campaign_member CASCADE;
+DROP TABLE IF EXISTS public.check_in CASCADE;
+DROP TABLE IF EXISTS public.claim_customer_vault CASCADE;
+DROP TABLE IF EXISTS public.claim_promo_voucher CASCADE;
+DROP TABLE IF EXISTS public.clawback_promo_token CASCADE;
+DROP TABLE IF EXISTS public.close_sponsorship CASCADE;
 DROP TABLE IF EXISTS public.create_admin_settings CASCADE;
 DROP TABLE IF EXISTS public.create_campaign CASCADE;
 DROP TABLE IF EXISTS public.create_campaign_location CASCADE;
+DROP TABLE IF EXISTS public.create_campaign_locations CASCADE;
 DROP TABLE IF EXISTS public.create_device CASCADE;
 DROP TABLE IF EXISTS public.create_location CASCADE;
+DROP TABLE IF EXISTS public.create_membership CASCADE;
 DROP TABLE IF EXISTS public.create_merchant CASCADE;
 DROP TABLE IF EXISTS public.create_promo CASCADE;
 DROP TABLE IF EXISTS public.create_promo_group CASCADE;
+DROP TABLE IF EXISTS public.create_sponsorship CASCADE;
+DROP TABLE IF EXISTS public.customer_vault CASCADE;
 DROP TABLE IF EXISTS public.delegate_promo_token CASCADE;
 DROP TABLE IF EXISTS public.device CASCADE;
+DROP TABLE IF EXISTS public.gift CASCADE;
+DROP TABLE IF EXISTS public.gift_promo_token CASCADE;
+DROP TABLE IF EXISTS public.issue_membership_pass CASCADE;
+DROP TABLE IF EXISTS public.join_campaign CASCADE;
 DROP TABLE IF EXISTS public.location CASCADE;
+DROP TABLE IF EXISTS public.membership CASCADE;
+DROP TABLE IF EXISTS public.membership_pass CASCADE;
 DROP TABLE IF EXISTS public.merchant CASCADE;
 DROP TABLE IF EXISTS public.mint_promo_token CASCADE;
+DROP TABLE IF EXISTS public.mint_promo_token_to_vault CASCADE;
+DROP TABLE IF EXISTS public.mint_request CASCADE;
 DROP TABLE IF EXISTS public.promo CASCADE;
 DROP TABLE IF EXISTS public.promo_group CASCADE;
+DROP TABLE IF EXISTS public.redeem_promo_token CASCADE;
+DROP TABLE IF EXISTS public.referral CASCADE;
+DROP TABLE IF EXISTS public.reject_mint_request CASCADE;
+DROP TABLE IF EXISTS public.renew_membership_pass CASCADE;
+DROP TABLE IF EXISTS public.request_promo_mint CASCADE;
 DROP TABLE IF EXISTS public.sign_memo CASCADE;
+DROP TABLE IF EXISTS public.sponsorship CASCADE;
+DROP TABLE IF EXISTS public.stamp_card CASCADE;
+DROP TABLE IF EXISTS public.stamp_promo_card CASCADE;
+DROP TABLE IF EXISTS public.voucher_claim CASCADE;
 
 -- =============================
 -- mpl_auction_house
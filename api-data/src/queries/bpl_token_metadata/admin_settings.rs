@@ -16,6 +16,7 @@ pub async fn upsert(
     let platform = account.platform.to_string();
     let create_promo_lamports = account.create_promo_lamports as i64;
     let burn_promo_token_lamports = account.burn_promo_token_lamports as i64;
+    let vault_authority = account.vault_authority.to_string();
     let slot = slot as i64;
     let write_version = write_version as i64;
 
@@ -27,6 +28,7 @@ pub async fn upsert(
                 &platform,
                 &create_promo_lamports,
                 &burn_promo_token_lamports,
+                &vault_authority,
                 &slot,
                 &write_version,
             ],
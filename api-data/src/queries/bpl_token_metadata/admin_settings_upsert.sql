@@ -3,15 +3,17 @@ INSERT INTO admin_settings (
     platform,
     create_promo_lamports,
     burn_promo_token_lamports,
+    vault_authority,
     slot,
     write_version
 )
-    VALUES($1, $2, $3, $4, $5, $6)
+    VALUES($1, $2, $3, $4, $5, $6, $7)
 ON CONFLICT ON CONSTRAINT admin_settings_pkey DO UPDATE 
     SET
         platform = EXCLUDED.platform,
         create_promo_lamports = EXCLUDED.create_promo_lamports,
         burn_promo_token_lamports = EXCLUDED.burn_promo_token_lamports,
+        vault_authority = EXCLUDED.vault_authority,
         slot = EXCLUDED.slot,
         write_version = EXCLUDED.write_version,
         modified_at = NOW()
@@ -16,7 +16,7 @@ pub async fn upsert(
 ) {
     let accounts: Vec<String> = accounts.iter().map(ToString::to_string).collect();
     let memo = if let Ok(args) =
-        bpl_token_metadata::instruction::MintPromoToken::try_from_slice(&data[8..])
+        bpl_token_metadata::instruction::BurnDelegatedPromoToken::try_from_slice(&data[8..])
     {
         args.memo.map(|m| {
             if let Ok(result) = serde_json::from_str::<serde_json::Value>(&m) {
@@ -40,6 +40,10 @@ pub async fn upsert(
     let promo = &accounts[7];
     let platform = &accounts[8];
     let admin_settings = &accounts[9];
+    // Optional accounts that aren't included are replaced by the program id.
+    let sponsorship = accounts
+        .get(23)
+        .filter(|key| *key != &bpl_token_metadata::id().to_string());
     let token_account = &accounts[10];
     let slot = slot as i64;
 
@@ -68,6 +72,7 @@ pub async fn upsert(
                 token_account,
                 &Json::<Option<serde_json::Value>>(memo),
                 &slot,
+                &sponsorship,
             ],
         )
         .await;
@@ -15,9 +15,22 @@ INSERT INTO burn_delegated_promo_token (
     admin_settings,
     token_account,
     memo,
-    slot
+    slot,
+    merchant,
+    sponsorship
 )
-    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+    VALUES(
+        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17,
+        -- Attribute the redemption to the merchant of the redeeming location, which for coalition
+        -- campaigns can be a member merchant rather than the one that created the campaign.
+        (
+            SELECT location.merchant
+            FROM campaign_location
+            JOIN location ON location.id = campaign_location.location
+            WHERE campaign_location.id = $8
+        ),
+        $18
+    )
 ON CONFLICT ON CONSTRAINT burn_delegated_promo_token_pkey DO UPDATE 
     SET
         payer = EXCLUDED.payer,
@@ -36,6 +49,8 @@ ON CONFLICT ON CONSTRAINT burn_delegated_promo_token_pkey DO UPDATE
         token_account = EXCLUDED.token_account,
         memo = EXCLUDED.memo,
         slot = EXCLUDED.slot,
+        merchant = COALESCE(EXCLUDED.merchant, burn_delegated_promo_token.merchant),
+        sponsorship = EXCLUDED.sponsorship,
         modified_at = NOW()
     WHERE
         EXCLUDED.slot > burn_delegated_promo_token.slot
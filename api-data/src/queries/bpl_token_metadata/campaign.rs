@@ -16,6 +16,10 @@ pub async fn upsert(
 ) {
     let id = bs58::encode(key).into_string();
     let merchant = account.merchant.to_string();
+    let mint_count = account.mint_count as i32;
+    let burn_count = account.burn_count as i32;
+    let max_mint = account.max_mint.map(|v| v as i32);
+    let max_burn = account.max_burn.map(|v| v as i32);
     let slot = slot as i64;
     let write_version = write_version as i64;
 
@@ -45,6 +49,10 @@ pub async fn upsert(
                 &account.uri,
                 &Json::<Option<serde_json::Value>>(metadata_json),
                 &account.active,
+                &mint_count,
+                &burn_count,
+                &max_mint,
+                &max_burn,
                 &slot,
                 &write_version,
             ],
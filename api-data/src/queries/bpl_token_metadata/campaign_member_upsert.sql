@@ -0,0 +1,22 @@
+INSERT INTO campaign_member (
+    id,
+    campaign,
+    merchant,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5)
+ON CONFLICT ON CONSTRAINT campaign_member_pkey DO UPDATE 
+    SET
+        campaign = EXCLUDED.campaign,
+        merchant = EXCLUDED.merchant,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > campaign_member.slot
+        OR (
+            EXCLUDED.slot = campaign_member.slot
+            AND EXCLUDED.write_version > campaign_member.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -5,10 +5,14 @@ INSERT INTO campaign (
     uri,
     metadata_json,
     active,
+    mint_count,
+    burn_count,
+    max_mint,
+    max_burn,
     slot,
     write_version
 )
-    VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
 ON CONFLICT ON CONSTRAINT campaign_pkey DO UPDATE 
     SET
         merchant = EXCLUDED.merchant,
@@ -16,6 +20,10 @@ ON CONFLICT ON CONSTRAINT campaign_pkey DO UPDATE
         uri = EXCLUDED.uri,
         metadata_json = EXCLUDED.metadata_json,
         active = EXCLUDED.active,
+        mint_count = EXCLUDED.mint_count,
+        burn_count = EXCLUDED.burn_count,
+        max_mint = EXCLUDED.max_mint,
+        max_burn = EXCLUDED.max_burn,
         slot = EXCLUDED.slot,
         write_version = EXCLUDED.write_version,
         modified_at = NOW()
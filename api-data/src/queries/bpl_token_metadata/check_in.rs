@@ -0,0 +1,68 @@
+use borsh::de::BorshDeserialize;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio_postgres::{types::Json, Client};
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("check_in_upsert.sql");
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert(
+    client: &Client,
+    signature: &Signature,
+    accounts: &Vec<Pubkey>,
+    data: &[u8],
+    slot: u64,
+) {
+    let accounts: Vec<String> = accounts.iter().map(ToString::to_string).collect();
+    let memo =
+        if let Ok(args) = bpl_token_metadata::instruction::CheckIn::try_from_slice(&data[8..]) {
+            args.memo.map(|m| {
+                if let Ok(result) = serde_json::from_str::<serde_json::Value>(&m) {
+                    result
+                } else {
+                    serde_json::json!({ "memo": m })
+                }
+            })
+        } else {
+            None
+        };
+
+    let signature = signature.to_string();
+    let payer = &accounts[0];
+    let device_owner = &accounts[1];
+    let device = &accounts[2];
+    let location = &accounts[3];
+    let membership = &accounts[4];
+    let membership_pass = &accounts[5];
+    let token_owner = &accounts[6];
+    let token_account = &accounts[7];
+    let slot = slot as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &signature,
+                payer,
+                device_owner,
+                device,
+                location,
+                membership,
+                membership_pass,
+                token_owner,
+                token_account,
+                &Json::<Option<serde_json::Value>>(memo),
+                &slot,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(signature = signature.as_str(), insert);
+        }
+        Err(error) => {
+            error!(signature = signature.as_str(), ?error);
+        }
+    }
+}
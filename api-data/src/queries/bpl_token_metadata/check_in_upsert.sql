@@ -0,0 +1,30 @@
+INSERT INTO check_in (
+    signature,
+    payer,
+    device_owner,
+    device,
+    location,
+    membership,
+    membership_pass,
+    token_owner,
+    token_account,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+ON CONFLICT ON CONSTRAINT check_in_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        device_owner = EXCLUDED.device_owner,
+        device = EXCLUDED.device,
+        location = EXCLUDED.location,
+        membership = EXCLUDED.membership,
+        membership_pass = EXCLUDED.membership_pass,
+        token_owner = EXCLUDED.token_owner,
+        token_account = EXCLUDED.token_account,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > check_in.slot
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,71 @@
+use borsh::de::BorshDeserialize;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio_postgres::{types::Json, Client};
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("claim_customer_vault_upsert.sql");
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert(
+    client: &Client,
+    signature: &Signature,
+    accounts: &Vec<Pubkey>,
+    data: &[u8],
+    slot: u64,
+) {
+    let accounts: Vec<String> = accounts.iter().map(ToString::to_string).collect();
+    let memo = if let Ok(args) =
+        bpl_token_metadata::instruction::ClaimCustomerVault::try_from_slice(&data[8..])
+    {
+        args.memo.map(|m| {
+            if let Ok(result) = serde_json::from_str::<serde_json::Value>(&m) {
+                result
+            } else {
+                serde_json::json!({ "memo": m })
+            }
+        })
+    } else {
+        None
+    };
+
+    let signature = signature.to_string();
+    let payer = &accounts[0];
+    let vault_authority = &accounts[1];
+    let admin_settings = &accounts[2];
+    let customer_vault = &accounts[3];
+    let token_owner = &accounts[4];
+    let mint = &accounts[5];
+    let authority = &accounts[6];
+    let vault_token_account = &accounts[7];
+    let token_account = &accounts[8];
+    let slot = slot as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &signature,
+                payer,
+                vault_authority,
+                admin_settings,
+                customer_vault,
+                token_owner,
+                mint,
+                authority,
+                vault_token_account,
+                token_account,
+                &Json::<Option<serde_json::Value>>(memo),
+                &slot,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(signature = signature.as_str(), insert);
+        }
+        Err(error) => {
+            error!(signature = signature.as_str(), ?error);
+        }
+    }
+}
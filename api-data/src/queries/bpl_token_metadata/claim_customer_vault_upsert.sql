@@ -0,0 +1,32 @@
+INSERT INTO claim_customer_vault (
+    signature,
+    payer,
+    vault_authority,
+    admin_settings,
+    customer_vault,
+    token_owner,
+    mint,
+    authority,
+    vault_token_account,
+    token_account,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+ON CONFLICT ON CONSTRAINT claim_customer_vault_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        vault_authority = EXCLUDED.vault_authority,
+        admin_settings = EXCLUDED.admin_settings,
+        customer_vault = EXCLUDED.customer_vault,
+        token_owner = EXCLUDED.token_owner,
+        mint = EXCLUDED.mint,
+        authority = EXCLUDED.authority,
+        vault_token_account = EXCLUDED.vault_token_account,
+        token_account = EXCLUDED.token_account,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > claim_customer_vault.slot
+RETURNING created_at = modified_at
\ No newline at end of file
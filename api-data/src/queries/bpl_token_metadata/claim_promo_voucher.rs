@@ -0,0 +1,82 @@
+use borsh::de::BorshDeserialize;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio_postgres::{types::Json, Client};
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("claim_promo_voucher_upsert.sql");
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert(
+    client: &Client,
+    signature: &Signature,
+    accounts: &Vec<Pubkey>,
+    data: &[u8],
+    slot: u64,
+) {
+    let accounts: Vec<String> = accounts.iter().map(ToString::to_string).collect();
+    let (nonce, expiry, memo) = if let Ok(args) =
+        bpl_token_metadata::instruction::ClaimPromoVoucher::try_from_slice(&data[8..])
+    {
+        let memo = args.memo.map(|m| {
+            if let Ok(result) = serde_json::from_str::<serde_json::Value>(&m) {
+                result
+            } else {
+                serde_json::json!({ "memo": m })
+            }
+        });
+        (
+            Some(args.voucher.nonce as i64),
+            Some(args.voucher.expiry),
+            memo,
+        )
+    } else {
+        (None, None, None)
+    };
+
+    let signature = signature.to_string();
+    let payer = &accounts[0];
+    let device_owner = &accounts[1];
+    let device = &accounts[2];
+    let campaign = &accounts[3];
+    let campaign_location = &accounts[4];
+    let token_owner = &accounts[5];
+    let mint = &accounts[6];
+    let authority = &accounts[7];
+    let promo = &accounts[8];
+    let voucher_claim = &accounts[9];
+    let token_account = &accounts[10];
+    let slot = slot as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &signature,
+                payer,
+                device_owner,
+                device,
+                campaign,
+                campaign_location,
+                token_owner,
+                mint,
+                authority,
+                promo,
+                voucher_claim,
+                token_account,
+                &nonce,
+                &expiry,
+                &Json::<Option<serde_json::Value>>(memo),
+                &slot,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(signature = signature.as_str(), insert);
+        }
+        Err(error) => {
+            error!(signature = signature.as_str(), ?error);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+INSERT INTO claim_promo_voucher (
+    signature,
+    payer,
+    device_owner,
+    device,
+    campaign,
+    campaign_location,
+    token_owner,
+    mint,
+    authority,
+    promo,
+    voucher_claim,
+    token_account,
+    nonce,
+    expiry,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+ON CONFLICT ON CONSTRAINT claim_promo_voucher_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        device_owner = EXCLUDED.device_owner,
+        device = EXCLUDED.device,
+        campaign = EXCLUDED.campaign,
+        campaign_location = EXCLUDED.campaign_location,
+        token_owner = EXCLUDED.token_owner,
+        mint = EXCLUDED.mint,
+        authority = EXCLUDED.authority,
+        promo = EXCLUDED.promo,
+        voucher_claim = EXCLUDED.voucher_claim,
+        token_account = EXCLUDED.token_account,
+        nonce = EXCLUDED.nonce,
+        expiry = EXCLUDED.expiry,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > claim_promo_voucher.slot
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,65 @@
+use borsh::de::BorshDeserialize;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio_postgres::Client;
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("clawback_promo_token_upsert.sql");
+
+/// Records a clawback and flags the clawed back token account.
+#[tracing::instrument(skip_all)]
+pub async fn upsert(
+    client: &Client,
+    signature: &Signature,
+    accounts: &Vec<Pubkey>,
+    data: &[u8],
+    slot: u64,
+) {
+    let accounts: Vec<String> = accounts.iter().map(ToString::to_string).collect();
+    let (burn, reason) = if let Ok(args) =
+        bpl_token_metadata::instruction::ClawbackPromoToken::try_from_slice(&data[8..])
+    {
+        (Some(args.burn), Some(args.reason))
+    } else {
+        (None, None)
+    };
+
+    let signature = signature.to_string();
+    let payer = &accounts[0];
+    let owner = &accounts[1];
+    let merchant = &accounts[2];
+    let campaign = &accounts[3];
+    let mint = &accounts[4];
+    let authority = &accounts[5];
+    let promo = &accounts[6];
+    let token_account = &accounts[7];
+    let slot = slot as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &signature,
+                payer,
+                owner,
+                merchant,
+                campaign,
+                mint,
+                authority,
+                promo,
+                token_account,
+                &burn,
+                &reason,
+                &slot,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(signature = signature.as_str(), insert);
+        }
+        Err(error) => {
+            error!(signature = signature.as_str(), ?error);
+        }
+    }
+}
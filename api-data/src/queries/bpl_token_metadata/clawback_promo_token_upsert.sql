@@ -0,0 +1,39 @@
+WITH flagged AS (
+    UPDATE token_account
+        SET
+            clawed_back = true,
+            modified_at = NOW()
+        WHERE id = $9
+)
+INSERT INTO clawback_promo_token (
+    signature,
+    payer,
+    owner,
+    merchant,
+    campaign,
+    mint,
+    authority,
+    promo,
+    token_account,
+    burn,
+    reason,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+ON CONFLICT ON CONSTRAINT clawback_promo_token_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        owner = EXCLUDED.owner,
+        merchant = EXCLUDED.merchant,
+        campaign = EXCLUDED.campaign,
+        mint = EXCLUDED.mint,
+        authority = EXCLUDED.authority,
+        promo = EXCLUDED.promo,
+        token_account = EXCLUDED.token_account,
+        burn = EXCLUDED.burn,
+        reason = EXCLUDED.reason,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > clawback_promo_token.slot
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,18 @@
+INSERT INTO close_sponsorship (
+    signature,
+    sponsor,
+    sponsorship,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5)
+ON CONFLICT ON CONSTRAINT close_sponsorship_pkey DO UPDATE 
+    SET
+        sponsor = EXCLUDED.sponsor,
+        sponsorship = EXCLUDED.sponsorship,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > close_sponsorship.slot
+RETURNING created_at = modified_at
\ No newline at end of file
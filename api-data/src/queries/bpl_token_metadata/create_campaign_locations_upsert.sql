@@ -0,0 +1,26 @@
+INSERT INTO create_campaign_locations (
+    signature,
+    payer,
+    owner,
+    merchant,
+    campaign,
+    locations,
+    campaign_locations,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9)
+ON CONFLICT ON CONSTRAINT create_campaign_locations_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        owner = EXCLUDED.owner,
+        merchant = EXCLUDED.merchant,
+        campaign = EXCLUDED.campaign,
+        locations = EXCLUDED.locations,
+        campaign_locations = EXCLUDED.campaign_locations,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > create_campaign_locations.slot
+RETURNING created_at = modified_at
\ No newline at end of file
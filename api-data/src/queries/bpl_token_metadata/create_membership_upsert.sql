@@ -0,0 +1,32 @@
+INSERT INTO create_membership (
+    signature,
+    payer,
+    owner,
+    merchant,
+    authority,
+    mint,
+    token_account,
+    metadata,
+    edition,
+    membership,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+ON CONFLICT ON CONSTRAINT create_membership_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        owner = EXCLUDED.owner,
+        merchant = EXCLUDED.merchant,
+        authority = EXCLUDED.authority,
+        mint = EXCLUDED.mint,
+        token_account = EXCLUDED.token_account,
+        metadata = EXCLUDED.metadata,
+        edition = EXCLUDED.edition,
+        membership = EXCLUDED.membership,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > create_membership.slot
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -15,7 +15,7 @@ pub async fn upsert(
 ) {
     let accounts: Vec<String> = accounts.iter().map(ToString::to_string).collect();
     let memo = if let Ok(args) =
-        bpl_token_metadata::instruction::CreatePromo::try_from_slice(&data[8..])
+        bpl_token_metadata::instruction::CreateMerchant::try_from_slice(&data[8..])
     {
         args.memo.map(|m| {
             if let Ok(result) = serde_json::from_str::<serde_json::Value>(&m) {
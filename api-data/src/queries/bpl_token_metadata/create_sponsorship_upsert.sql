@@ -0,0 +1,26 @@
+INSERT INTO create_sponsorship (
+    signature,
+    payer,
+    sponsor,
+    merchant,
+    campaign,
+    sponsorship,
+    lamports,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9)
+ON CONFLICT ON CONSTRAINT create_sponsorship_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        sponsor = EXCLUDED.sponsor,
+        merchant = EXCLUDED.merchant,
+        campaign = EXCLUDED.campaign,
+        sponsorship = EXCLUDED.sponsorship,
+        lamports = EXCLUDED.lamports,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > create_sponsorship.slot
+RETURNING created_at = modified_at
\ No newline at end of file
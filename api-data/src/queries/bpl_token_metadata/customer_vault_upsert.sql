@@ -0,0 +1,24 @@
+INSERT INTO customer_vault (
+    id,
+    identity_hash,
+    created_at_on_chain,
+    claimed_by,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6)
+ON CONFLICT ON CONSTRAINT customer_vault_pkey DO UPDATE 
+    SET
+        identity_hash = EXCLUDED.identity_hash,
+        created_at_on_chain = EXCLUDED.created_at_on_chain,
+        claimed_by = EXCLUDED.claimed_by,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > customer_vault.slot
+        OR (
+            EXCLUDED.slot = customer_vault.slot
+            AND EXCLUDED.write_version > customer_vault.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
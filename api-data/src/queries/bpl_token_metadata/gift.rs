@@ -0,0 +1,40 @@
+use bpl_token_metadata::state::Gift;
+use tokio_postgres::Client;
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("gift_upsert.sql");
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert(client: &Client, key: &[u8], account: &Gift, slot: u64, write_version: u64) {
+    let id = bs58::encode(key).into_string();
+    let mint = account.mint.to_string();
+    let sender = account.sender.to_string();
+    let recipient = account.recipient.to_string();
+    let gifted_at = account.gifted_at;
+    let slot = slot as i64;
+    let write_version = write_version as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &id,
+                &mint,
+                &sender,
+                &recipient,
+                &gifted_at,
+                &slot,
+                &write_version,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(id = id.as_str(), insert);
+        }
+        Err(error) => {
+            error!(id = id.as_str(), ?error);
+        }
+    }
+}
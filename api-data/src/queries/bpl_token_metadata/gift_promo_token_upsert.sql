@@ -0,0 +1,30 @@
+INSERT INTO gift_promo_token (
+    signature,
+    payer,
+    sender,
+    recipient,
+    mint,
+    promo,
+    sender_token_account,
+    recipient_token_account,
+    gift,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+ON CONFLICT ON CONSTRAINT gift_promo_token_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        sender = EXCLUDED.sender,
+        recipient = EXCLUDED.recipient,
+        mint = EXCLUDED.mint,
+        promo = EXCLUDED.promo,
+        sender_token_account = EXCLUDED.sender_token_account,
+        recipient_token_account = EXCLUDED.recipient_token_account,
+        gift = EXCLUDED.gift,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > gift_promo_token.slot
+RETURNING created_at = modified_at
\ No newline at end of file
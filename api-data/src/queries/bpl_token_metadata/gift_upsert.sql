@@ -0,0 +1,26 @@
+INSERT INTO gift (
+    id,
+    mint,
+    sender,
+    recipient,
+    gifted_at,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT ON CONSTRAINT gift_pkey DO UPDATE 
+    SET
+        mint = EXCLUDED.mint,
+        sender = EXCLUDED.sender,
+        recipient = EXCLUDED.recipient,
+        gifted_at = EXCLUDED.gifted_at,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > gift.slot
+        OR (
+            EXCLUDED.slot = gift.slot
+            AND EXCLUDED.write_version > gift.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,85 @@
+use borsh::de::BorshDeserialize;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio_postgres::{types::Json, Client};
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("issue_membership_pass_upsert.sql");
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert(
+    client: &Client,
+    signature: &Signature,
+    accounts: &Vec<Pubkey>,
+    data: &[u8],
+    slot: u64,
+) {
+    let accounts: Vec<String> = accounts.iter().map(ToString::to_string).collect();
+    let memo = if let Ok(args) =
+        bpl_token_metadata::instruction::IssueMembershipPass::try_from_slice(&data[8..])
+    {
+        args.memo.map(|m| {
+            if let Ok(result) = serde_json::from_str::<serde_json::Value>(&m) {
+                result
+            } else {
+                serde_json::json!({ "memo": m })
+            }
+        })
+    } else {
+        None
+    };
+
+    let signature = signature.to_string();
+    let payer = &accounts[0];
+    let owner = &accounts[1];
+    let merchant = &accounts[2];
+    let membership = &accounts[3];
+    let authority = &accounts[4];
+    let master_mint = &accounts[5];
+    let master_token_account = &accounts[6];
+    let master_metadata = &accounts[7];
+    let master_edition = &accounts[8];
+    let token_owner = &accounts[9];
+    let pass_mint = &accounts[10];
+    let token_account = &accounts[11];
+    let pass_metadata = &accounts[12];
+    let pass_edition = &accounts[13];
+    let edition_marker = &accounts[14];
+    let membership_pass = &accounts[15];
+    let slot = slot as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &signature,
+                payer,
+                owner,
+                merchant,
+                membership,
+                authority,
+                master_mint,
+                master_token_account,
+                master_metadata,
+                master_edition,
+                token_owner,
+                pass_mint,
+                token_account,
+                pass_metadata,
+                pass_edition,
+                edition_marker,
+                membership_pass,
+                &Json::<Option<serde_json::Value>>(memo),
+                &slot,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(signature = signature.as_str(), insert);
+        }
+        Err(error) => {
+            error!(signature = signature.as_str(), ?error);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+INSERT INTO issue_membership_pass (
+    signature,
+    payer,
+    owner,
+    merchant,
+    membership,
+    authority,
+    master_mint,
+    master_token_account,
+    master_metadata,
+    master_edition,
+    token_owner,
+    pass_mint,
+    token_account,
+    pass_metadata,
+    pass_edition,
+    edition_marker,
+    membership_pass,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+ON CONFLICT ON CONSTRAINT issue_membership_pass_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        owner = EXCLUDED.owner,
+        merchant = EXCLUDED.merchant,
+        membership = EXCLUDED.membership,
+        authority = EXCLUDED.authority,
+        master_mint = EXCLUDED.master_mint,
+        master_token_account = EXCLUDED.master_token_account,
+        master_metadata = EXCLUDED.master_metadata,
+        master_edition = EXCLUDED.master_edition,
+        token_owner = EXCLUDED.token_owner,
+        pass_mint = EXCLUDED.pass_mint,
+        token_account = EXCLUDED.token_account,
+        pass_metadata = EXCLUDED.pass_metadata,
+        pass_edition = EXCLUDED.pass_edition,
+        edition_marker = EXCLUDED.edition_marker,
+        membership_pass = EXCLUDED.membership_pass,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > issue_membership_pass.slot
+RETURNING created_at = modified_at
\ No newline at end of file
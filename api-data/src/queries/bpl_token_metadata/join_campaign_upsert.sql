@@ -0,0 +1,28 @@
+INSERT INTO join_campaign (
+    signature,
+    payer,
+    campaign_owner,
+    campaign_merchant,
+    campaign,
+    owner,
+    merchant,
+    campaign_member,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+ON CONFLICT ON CONSTRAINT join_campaign_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        campaign_owner = EXCLUDED.campaign_owner,
+        campaign_merchant = EXCLUDED.campaign_merchant,
+        campaign = EXCLUDED.campaign,
+        owner = EXCLUDED.owner,
+        merchant = EXCLUDED.merchant,
+        campaign_member = EXCLUDED.campaign_member,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > join_campaign.slot
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,56 @@
+use bpl_token_metadata::state::MembershipPass;
+use tokio_postgres::Client;
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("membership_pass_upsert.sql");
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert(
+    client: &Client,
+    key: &[u8],
+    account: &MembershipPass,
+    slot: u64,
+    write_version: u64,
+) {
+    let id = bs58::encode(key).into_string();
+    let membership = account.membership.to_string();
+    let mint = account.mint.to_string();
+    let edition = account.edition as i64;
+    let issued_to = account.issued_to.to_string();
+    let issued_at = account.issued_at;
+    let expires_at = account.expires_at;
+    let renewals = account.renewals as i64;
+    let check_ins = account.check_ins as i64;
+    let last_check_in_at = account.last_check_in_at;
+    let slot = slot as i64;
+    let write_version = write_version as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &id,
+                &membership,
+                &mint,
+                &edition,
+                &issued_to,
+                &issued_at,
+                &expires_at,
+                &renewals,
+                &check_ins,
+                &last_check_in_at,
+                &slot,
+                &write_version,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(id = id.as_str(), insert);
+        }
+        Err(error) => {
+            error!(id = id.as_str(), ?error);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+INSERT INTO membership_pass (
+    id,
+    membership,
+    mint,
+    edition,
+    issued_to,
+    issued_at,
+    expires_at,
+    renewals,
+    check_ins,
+    last_check_in_at,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+ON CONFLICT ON CONSTRAINT membership_pass_pkey DO UPDATE 
+    SET
+        membership = EXCLUDED.membership,
+        mint = EXCLUDED.mint,
+        edition = EXCLUDED.edition,
+        issued_to = EXCLUDED.issued_to,
+        issued_at = EXCLUDED.issued_at,
+        expires_at = EXCLUDED.expires_at,
+        renewals = EXCLUDED.renewals,
+        check_ins = EXCLUDED.check_ins,
+        last_check_in_at = EXCLUDED.last_check_in_at,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > membership_pass.slot
+        OR (
+            EXCLUDED.slot = membership_pass.slot
+            AND EXCLUDED.write_version > membership_pass.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
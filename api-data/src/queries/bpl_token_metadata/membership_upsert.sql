@@ -0,0 +1,26 @@
+INSERT INTO membership (
+    id,
+    merchant,
+    mint,
+    duration,
+    pass_count,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT ON CONSTRAINT membership_pkey DO UPDATE 
+    SET
+        merchant = EXCLUDED.merchant,
+        mint = EXCLUDED.mint,
+        duration = EXCLUDED.duration,
+        pass_count = EXCLUDED.pass_count,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > membership.slot
+        OR (
+            EXCLUDED.slot = membership.slot
+            AND EXCLUDED.write_version > membership.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,28 @@
+INSERT INTO mint_request (
+    id,
+    mint,
+    token_owner,
+    campaign,
+    payer,
+    requested_at,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+ON CONFLICT ON CONSTRAINT mint_request_pkey DO UPDATE 
+    SET
+        mint = EXCLUDED.mint,
+        token_owner = EXCLUDED.token_owner,
+        campaign = EXCLUDED.campaign,
+        payer = EXCLUDED.payer,
+        requested_at = EXCLUDED.requested_at,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > mint_request.slot
+        OR (
+            EXCLUDED.slot = mint_request.slot
+            AND EXCLUDED.write_version > mint_request.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -1,18 +1,46 @@
 pub mod admin_settings;
+pub mod approve_mint_request;
 pub mod burn_delegated_promo_token;
 pub mod campaign;
 pub mod campaign_location;
+pub mod campaign_member;
+pub mod check_in;
+pub mod claim_customer_vault;
+pub mod claim_promo_voucher;
+pub mod clawback_promo_token;
+pub mod close_sponsorship;
 pub mod create_admin_settings;
 pub mod create_campaign;
 pub mod create_campaign_location;
+pub mod create_campaign_locations;
 pub mod create_device;
 pub mod create_location;
+pub mod create_membership;
 pub mod create_merchant;
 pub mod create_promo;
+pub mod create_sponsorship;
+pub mod customer_vault;
 pub mod delegate_promo_token;
 pub mod device;
+pub mod gift;
+pub mod gift_promo_token;
+pub mod issue_membership_pass;
+pub mod join_campaign;
 pub mod location;
+pub mod membership;
+pub mod membership_pass;
 pub mod merchant;
 pub mod mint_promo_token;
+pub mod mint_promo_token_to_vault;
+pub mod mint_request;
 pub mod promo;
+pub mod redeem_promo_token;
+pub mod referral;
+pub mod reject_mint_request;
+pub mod renew_membership_pass;
+pub mod request_promo_mint;
 pub mod sign_memo;
+pub mod sponsorship;
+pub mod stamp_card;
+pub mod stamp_promo_card;
+pub mod voucher_claim;
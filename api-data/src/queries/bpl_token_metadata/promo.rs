@@ -1,4 +1,4 @@
-use bpl_token_metadata::state::Promo;
+use bpl_token_metadata::state::{GiftPolicy, Promo, PromoTerms};
 use tokio_postgres::Client;
 use tracing::{error, info};
 
@@ -15,6 +15,76 @@ pub async fn upsert(client: &Client, key: &[u8], account: &Promo, slot: u64, wri
     let max_mint = account.max_mint.map(|v| v as i32);
     let max_burn = account.max_burn.map(|v| v as i32);
     let active = account.active;
+    let referral_reward_mint = account.referral.map(|r| r.reward_mint.to_string());
+    let max_referral_rewards = account
+        .referral
+        .and_then(|r| r.max_rewards)
+        .map(|v| v as i32);
+    let referral_reward_count = account.referral.map(|r| r.reward_count as i32);
+    let stamp_threshold = account.stamp_threshold.map(|v| v as i32);
+    let (
+        promo_type,
+        product_id,
+        buy_x_product,
+        get_y_product,
+        buy_x_currency,
+        get_y_percent,
+        amount_off,
+    ) = match &account.terms {
+        Some(PromoTerms::BuyXProductGetYFree {
+            product_id,
+            buy_x_product,
+            get_y_product,
+        }) => (
+            Some("buyXProductGetYFree"),
+            Some(product_id.clone()),
+            Some(*buy_x_product as i32),
+            Some(*get_y_product as i32),
+            None,
+            None,
+            None,
+        ),
+        Some(PromoTerms::BuyXCurrencyGetYPercent {
+            buy_x_currency,
+            get_y_percent,
+        }) => (
+            Some("buyXCurrencyGetYPercent"),
+            None,
+            None,
+            None,
+            Some(*buy_x_currency as i64),
+            Some(*get_y_percent as i32),
+            None,
+        ),
+        Some(PromoTerms::AmountOff {
+            buy_x_currency,
+            amount_off,
+        }) => (
+            Some("amountOff"),
+            None,
+            None,
+            None,
+            Some(*buy_x_currency as i64),
+            None,
+            Some(*amount_off as i64),
+        ),
+        Some(PromoTerms::FreeItem { product_id }) => (
+            Some("freeItem"),
+            Some(product_id.clone()),
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+        None => (None, None, None, None, None, None, None),
+    };
+    let requires_approval = account.requires_approval;
+    let gift_policy = match account.gift_policy {
+        GiftPolicy::Disallowed => "disallowed",
+        GiftPolicy::Allowed => "allowed",
+        GiftPolicy::AllowedOnce => "allowedOnce",
+    };
     let slot = slot as i64;
     let write_version = write_version as i64;
 
@@ -31,6 +101,19 @@ pub async fn upsert(client: &Client, key: &[u8], account: &Promo, slot: u64, wri
                 &max_mint,
                 &max_burn,
                 &active,
+                &referral_reward_mint,
+                &max_referral_rewards,
+                &referral_reward_count,
+                &stamp_threshold,
+                &promo_type,
+                &product_id,
+                &buy_x_product,
+                &get_y_product,
+                &buy_x_currency,
+                &get_y_percent,
+                &amount_off,
+                &requires_approval,
+                &gift_policy,
                 &slot,
                 &write_version,
             ],
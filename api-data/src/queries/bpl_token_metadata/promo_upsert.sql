@@ -8,10 +8,23 @@ INSERT INTO promo (
     max_mint,
     max_burn,
     active,
+    referral_reward_mint,
+    max_referral_rewards,
+    referral_reward_count,
+    stamp_threshold,
+    promo_type,
+    product_id,
+    buy_x_product,
+    get_y_product,
+    buy_x_currency,
+    get_y_percent,
+    amount_off,
+    requires_approval,
+    gift_policy,
     slot,
     write_version
 )
-    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24)
 ON CONFLICT ON CONSTRAINT promo_pkey DO UPDATE 
     SET
         campaign = EXCLUDED.campaign,
@@ -22,6 +35,19 @@ ON CONFLICT ON CONSTRAINT promo_pkey DO UPDATE
         max_mint = EXCLUDED.max_mint,
         max_burn = EXCLUDED.max_burn,
         active = EXCLUDED.active,
+        referral_reward_mint = EXCLUDED.referral_reward_mint,
+        max_referral_rewards = EXCLUDED.max_referral_rewards,
+        referral_reward_count = EXCLUDED.referral_reward_count,
+        stamp_threshold = EXCLUDED.stamp_threshold,
+        promo_type = EXCLUDED.promo_type,
+        product_id = EXCLUDED.product_id,
+        buy_x_product = EXCLUDED.buy_x_product,
+        get_y_product = EXCLUDED.get_y_product,
+        buy_x_currency = EXCLUDED.buy_x_currency,
+        get_y_percent = EXCLUDED.get_y_percent,
+        amount_off = EXCLUDED.amount_off,
+        requires_approval = EXCLUDED.requires_approval,
+        gift_policy = EXCLUDED.gift_policy,
         slot = EXCLUDED.slot,
         write_version = EXCLUDED.write_version,
         modified_at = NOW()
@@ -0,0 +1,57 @@
+INSERT INTO redeem_promo_token (
+    signature,
+    payer,
+    payer_balance,
+    device_owner,
+    device,
+    campaign,
+    campaign_balance,
+    campaign_location,
+    token_owner,
+    mint,
+    promo,
+    platform,
+    platform_balance,
+    admin_settings,
+    token_account,
+    memo,
+    slot,
+    merchant,
+    sponsorship
+)
+    VALUES(
+        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17,
+        -- Attribute the redemption to the merchant of the redeeming location, which for coalition
+        -- campaigns can be a member merchant rather than the one that created the campaign.
+        (
+            SELECT location.merchant
+            FROM campaign_location
+            JOIN location ON location.id = campaign_location.location
+            WHERE campaign_location.id = $8
+        ),
+        $18
+    )
+ON CONFLICT ON CONSTRAINT redeem_promo_token_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        payer_balance = EXCLUDED.payer_balance,
+        device_owner = EXCLUDED.device_owner,
+        device = EXCLUDED.device,
+        campaign = EXCLUDED.campaign,
+        campaign_balance = EXCLUDED.campaign_balance,
+        campaign_location = EXCLUDED.campaign_location,
+        token_owner = EXCLUDED.token_owner,
+        mint = EXCLUDED.mint,
+        promo = EXCLUDED.promo,
+        platform = EXCLUDED.platform,
+        platform_balance = EXCLUDED.platform_balance,
+        admin_settings = EXCLUDED.admin_settings,
+        token_account = EXCLUDED.token_account,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        merchant = COALESCE(EXCLUDED.merchant, redeem_promo_token.merchant),
+        sponsorship = EXCLUDED.sponsorship,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > redeem_promo_token.slot
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,26 @@
+INSERT INTO referral (
+    id,
+    mint,
+    referrer,
+    referred,
+    rewarded,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT ON CONSTRAINT referral_pkey DO UPDATE 
+    SET
+        mint = EXCLUDED.mint,
+        referrer = EXCLUDED.referrer,
+        referred = EXCLUDED.referred,
+        rewarded = EXCLUDED.rewarded,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > referral.slot
+        OR (
+            EXCLUDED.slot = referral.slot
+            AND EXCLUDED.write_version > referral.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,36 @@
+INSERT INTO reject_mint_request (
+    signature,
+    device_owner,
+    device,
+    campaign,
+    campaign_location,
+    token_owner,
+    mint,
+    authority,
+    promo,
+    mint_request,
+    request_payer,
+    token_account,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+ON CONFLICT ON CONSTRAINT reject_mint_request_pkey DO UPDATE 
+    SET
+        device_owner = EXCLUDED.device_owner,
+        device = EXCLUDED.device,
+        campaign = EXCLUDED.campaign,
+        campaign_location = EXCLUDED.campaign_location,
+        token_owner = EXCLUDED.token_owner,
+        mint = EXCLUDED.mint,
+        authority = EXCLUDED.authority,
+        promo = EXCLUDED.promo,
+        mint_request = EXCLUDED.mint_request,
+        request_payer = EXCLUDED.request_payer,
+        token_account = EXCLUDED.token_account,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > reject_mint_request.slot
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,24 @@
+INSERT INTO renew_membership_pass (
+    signature,
+    payer,
+    owner,
+    merchant,
+    membership,
+    membership_pass,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+ON CONFLICT ON CONSTRAINT renew_membership_pass_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        owner = EXCLUDED.owner,
+        merchant = EXCLUDED.merchant,
+        membership = EXCLUDED.membership,
+        membership_pass = EXCLUDED.membership_pass,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > renew_membership_pass.slot
+RETURNING created_at = modified_at
\ No newline at end of file
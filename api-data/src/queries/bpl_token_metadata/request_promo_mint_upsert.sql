@@ -0,0 +1,30 @@
+INSERT INTO request_promo_mint (
+    signature,
+    payer,
+    token_owner,
+    campaign,
+    mint,
+    authority,
+    promo,
+    mint_request,
+    token_account,
+    memo,
+    slot
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+ON CONFLICT ON CONSTRAINT request_promo_mint_pkey DO UPDATE 
+    SET
+        payer = EXCLUDED.payer,
+        token_owner = EXCLUDED.token_owner,
+        campaign = EXCLUDED.campaign,
+        mint = EXCLUDED.mint,
+        authority = EXCLUDED.authority,
+        promo = EXCLUDED.promo,
+        mint_request = EXCLUDED.mint_request,
+        token_account = EXCLUDED.token_account,
+        memo = EXCLUDED.memo,
+        slot = EXCLUDED.slot,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > request_promo_mint.slot
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,50 @@
+use bpl_token_metadata::state::Sponsorship;
+use tokio_postgres::Client;
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("sponsorship_upsert.sql");
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert(
+    client: &Client,
+    key: &[u8],
+    account: &Sponsorship,
+    slot: u64,
+    write_version: u64,
+) {
+    let id = bs58::encode(key).into_string();
+    let sponsor = account.sponsor.to_string();
+    let campaign = account.campaign.to_string();
+    let mints: Vec<String> = account.mints.iter().map(ToString::to_string).collect();
+    let reimbursement_lamports = account.reimbursement_lamports as i64;
+    let redemptions = account.redemptions as i32;
+    let spent_lamports = account.spent_lamports as i64;
+    let slot = slot as i64;
+    let write_version = write_version as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &id,
+                &sponsor,
+                &campaign,
+                &mints,
+                &reimbursement_lamports,
+                &redemptions,
+                &spent_lamports,
+                &slot,
+                &write_version,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(id = id.as_str(), insert);
+        }
+        Err(error) => {
+            error!(id = id.as_str(), ?error);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+INSERT INTO sponsorship (
+    id,
+    sponsor,
+    campaign,
+    mints,
+    reimbursement_lamports,
+    redemptions,
+    spent_lamports,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9)
+ON CONFLICT ON CONSTRAINT sponsorship_pkey DO UPDATE 
+    SET
+        sponsor = EXCLUDED.sponsor,
+        campaign = EXCLUDED.campaign,
+        mints = EXCLUDED.mints,
+        reimbursement_lamports = EXCLUDED.reimbursement_lamports,
+        redemptions = EXCLUDED.redemptions,
+        spent_lamports = EXCLUDED.spent_lamports,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > sponsorship.slot
+        OR (
+            EXCLUDED.slot = sponsorship.slot
+            AND EXCLUDED.write_version > sponsorship.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
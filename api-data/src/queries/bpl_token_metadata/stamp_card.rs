@@ -0,0 +1,48 @@
+use bpl_token_metadata::state::StampCard;
+use tokio_postgres::Client;
+use tracing::{error, info};
+
+const UPSERT_QUERY: &str = include_str!("stamp_card_upsert.sql");
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert(
+    client: &Client,
+    key: &[u8],
+    account: &StampCard,
+    slot: u64,
+    write_version: u64,
+) {
+    let id = bs58::encode(key).into_string();
+    let mint = account.mint.to_string();
+    let owner = account.owner.to_string();
+    let stamps = account.stamps as i32;
+    let rewards = account.rewards as i32;
+    let last_stamped_at = account.last_stamped_at;
+    let slot = slot as i64;
+    let write_version = write_version as i64;
+
+    let result = client
+        .query_one(
+            UPSERT_QUERY,
+            &[
+                &id,
+                &mint,
+                &owner,
+                &stamps,
+                &rewards,
+                &last_stamped_at,
+                &slot,
+                &write_version,
+            ],
+        )
+        .await;
+    match result {
+        Ok(row) => {
+            let insert = row.get::<usize, Option<bool>>(0).unwrap();
+            info!(id = id.as_str(), insert);
+        }
+        Err(error) => {
+            error!(id = id.as_str(), ?error);
+        }
+    }
+}
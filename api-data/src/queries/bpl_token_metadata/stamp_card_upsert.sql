@@ -0,0 +1,28 @@
+INSERT INTO stamp_card (
+    id,
+    mint,
+    owner,
+    stamps,
+    rewards,
+    last_stamped_at,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+ON CONFLICT ON CONSTRAINT stamp_card_pkey DO UPDATE 
+    SET
+        mint = EXCLUDED.mint,
+        owner = EXCLUDED.owner,
+        stamps = EXCLUDED.stamps,
+        rewards = EXCLUDED.rewards,
+        last_stamped_at = EXCLUDED.last_stamped_at,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > stamp_card.slot
+        OR (
+            EXCLUDED.slot = stamp_card.slot
+            AND EXCLUDED.write_version > stamp_card.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -0,0 +1,28 @@
+INSERT INTO voucher_claim (
+    id,
+    promo,
+    nonce,
+    device,
+    claimed_by,
+    claimed_at,
+    slot,
+    write_version
+)
+    VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+ON CONFLICT ON CONSTRAINT voucher_claim_pkey DO UPDATE 
+    SET
+        promo = EXCLUDED.promo,
+        nonce = EXCLUDED.nonce,
+        device = EXCLUDED.device,
+        claimed_by = EXCLUDED.claimed_by,
+        claimed_at = EXCLUDED.claimed_at,
+        slot = EXCLUDED.slot,
+        write_version = EXCLUDED.write_version,
+        modified_at = NOW()
+    WHERE
+        EXCLUDED.slot > voucher_claim.slot
+        OR (
+            EXCLUDED.slot = voucher_claim.slot
+            AND EXCLUDED.write_version > voucher_claim.write_version
+        )
+RETURNING created_at = modified_at
\ No newline at end of file
@@ -3,6 +3,8 @@ use tokio_postgres::Client;
 use tracing::{error, info};
 
 const UPSERT_QUERY: &str = include_str!("token_account_upsert.sql");
+const DELETE_QUERY: &str = include_str!("token_account_delete.sql");
+const SELECT_IDS_QUERY: &str = include_str!("token_account_select_ids.sql");
 
 #[tracing::instrument(skip_all)]
 pub async fn upsert(client: &Client, key: &[u8], account: &Account, slot: u64, write_version: u64) {
@@ -51,3 +53,30 @@ pub async fn upsert(client: &Client, key: &[u8], account: &Account, slot: u64, w
         }
     }
 }
+
+/// Deletes a closed token account unless it has since been updated, i.e. recreated.
+#[tracing::instrument(skip_all)]
+pub async fn delete(client: &Client, key: &[u8], slot: u64, write_version: u64) {
+    let id = bs58::encode(key).into_string();
+    let slot = slot as i64;
+    let write_version = write_version as i64;
+
+    let result = client
+        .query(DELETE_QUERY, &[&id, &slot, &write_version])
+        .await;
+    match result {
+        Ok(rows) => {
+            info!(id = id.as_str(), delete = rows.len());
+        }
+        Err(ref error) => {
+            error!(id = id.as_str(), ?error);
+        }
+    }
+}
+
+/// Returns the ids of all token accounts, so the indexer knows which closed accounts to delete.
+#[tracing::instrument(skip_all)]
+pub async fn select_ids(client: &Client) -> Result<Vec<String>, tokio_postgres::Error> {
+    let rows = client.query(SELECT_IDS_QUERY, &[]).await?;
+    Ok(rows.iter().map(|row| row.get::<usize, String>(0)).collect())
+}
@@ -0,0 +1,6 @@
+DELETE FROM token_account WHERE
+    id = $1 AND (
+        slot < $2
+        OR (slot = $2 AND write_version < $3)
+    )
+RETURNING id
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT id FROM token_account
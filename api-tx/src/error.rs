@@ -1,7 +1,8 @@
 use anchor_lang::solana_program::pubkey::ParsePubkeyError;
-use serde_json::json;
+use serde::{Deserialize, Serialize};
 use solana_sdk::signature::SignerError;
 use thiserror::Error;
+use utoipa::ToSchema;
 // ed25519_dalek::ed25519::Error
 use axum::{
     body::BoxBody,
@@ -15,10 +16,14 @@ use axum::{
 pub enum AppError {
     #[error("generic error: {0}")]
     GenericError(String),
+    #[error("allowlist error: {0}")]
+    AllowlistError(String),
     #[error("bincode: {0}")]
     BincodeError(#[from] Box<bincode::ErrorKind>),
     #[error("bundlr: {0}")]
     BundlrError(#[from] bundlr_sdk::error::BundlrError),
+    #[error("client: {0}")]
+    ClientError(#[from] bpl_token_metadata_client::ClientError),
     #[error("bundlr response error: {0}")]
     BundlrResponseError(String),
     #[error("create promo request: {0}")]
@@ -35,6 +40,8 @@ pub enum AppError {
     PubkeyError(#[from] ParsePubkeyError),
     #[error("payer not in members")]
     PayerNotInMembers,
+    #[error("token owner not on promo allowlist")]
+    NotAllowlisted,
     #[error("reqwest: {0}")]
     Reqwest(#[from] reqwest::Error),
     #[error("serde json: {0}")]
@@ -55,6 +62,20 @@ pub enum AppError {
     UrlDecodeError(std::string::FromUtf8Error),
     #[error("clover status not OK: {0}")]
     StatusNotOK(reqwest::StatusCode),
+    #[error("voucher error: {0}")]
+    VoucherError(String),
+    #[error("customer vault error: {0}")]
+    CustomerVaultError(String),
+    #[error("metadata render error: {0}")]
+    MetadataRenderError(String),
+    #[error("invalid parameters: {0}")]
+    ParamsError(String),
+}
+
+/// Body of error responses.
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
 }
 
 impl IntoResponse for AppError {
@@ -65,9 +86,9 @@ impl IntoResponse for AppError {
         };
 
         tracing::debug!(error = self.to_string());
-        let body = Json(json!({
-            "error": self.to_string(),
-        }));
+        let body = Json(ErrorResponse {
+            error: self.to_string(),
+        });
         (status, body).into_response()
     }
 }
@@ -0,0 +1,49 @@
+use crate::{
+    error::AppError,
+    utils::{allowlist::AllowlistTree, bundlr::upload_metadata_json},
+    State,
+};
+use axum::{Extension, Json};
+use base58::ToBase58;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Builds an allowlist from a csv of wallets and uploads the wallet list to Arweave. The
+/// returned uri is added to promo metadata as the `allowlist` attribute.
+#[utoipa::path(
+    post,
+    path = "/v2/allowlist/create",
+    request_body(
+        content = String,
+        content_type = "text/csv",
+        description = "Wallets, one per line"
+    ),
+    responses(
+        (status = 200, body = AllowlistResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    csv: String,
+) -> Result<Json<AllowlistResponse>, AppError> {
+    let tree = AllowlistTree::from_csv(&csv)?;
+    tracing::debug!(wallets = tree.len());
+
+    let (uri, _) = upload_metadata_json(&mut tree.to_json(), state).await?;
+
+    Ok(Json(AllowlistResponse {
+        uri,
+        root: tree.root().to_base58(),
+        wallets: tree.len(),
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct AllowlistResponse {
+    pub uri: String,
+    pub root: String,
+    pub wallets: usize,
+}
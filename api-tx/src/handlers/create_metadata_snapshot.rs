@@ -0,0 +1,99 @@
+use crate::{
+    error::AppError,
+    utils::{
+        bundlr::upload_metadata_json,
+        metadata::{
+            check_template, get_dynamic_metadata_uri, get_promo_state, get_template,
+            render_metadata,
+        },
+    },
+    State,
+};
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use solana_sdk::signer::Signer;
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use utoipa::ToSchema;
+
+use super::{Address, MetadataParams, Params};
+
+/// Renders dynamic promo metadata and pins it to Arweave, signed by the platform signer so
+/// the snapshot can be verified against the rendering endpoint. Meant to be called on a
+/// schedule for promos whose metadata should also be available from permanent storage.
+///
+/// The signature is over the json without `snapshot.signature`.
+#[utoipa::path(
+    post,
+    path = "/v2/metadata/snapshot",
+    params(MetadataParams),
+    responses(
+        (status = 200, body = SnapshotResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "metadata"
+)]
+pub async fn handler(
+    Params(MetadataParams {
+        template,
+        mint: Address(mint),
+        owner,
+    }): Params<MetadataParams>,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<SnapshotResponse>, AppError> {
+    let owner = owner.map(|o| o.0);
+    tracing::debug!(template = template, %mint, owner = ?owner);
+
+    let promo_state = get_promo_state(&mint, owner.as_ref(), &state).await?;
+    check_template(&template, &mint, &promo_state.metadata_uri)?;
+    let mut metadata = render_metadata(&*get_template(&template, &state).await?, &promo_state)?;
+
+    let source = match owner {
+        Some(owner) => format!(
+            "{}/{}",
+            get_dynamic_metadata_uri(&state.metadata_url, &template, &mint),
+            owner
+        ),
+        None => get_dynamic_metadata_uri(&state.metadata_url, &template, &mint),
+    };
+    let rendered_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let signer = state.platform_signer.pubkey().to_string();
+    metadata.insert(
+        "snapshot".to_string(),
+        json!({
+            "source": source,
+            "renderedAt": rendered_at,
+            "signer": signer,
+        }),
+    );
+
+    let signature = state
+        .platform_signer
+        .sign_message(&serde_json::to_vec(&metadata)?)
+        .to_string();
+    metadata["snapshot"]
+        .as_object_mut()
+        .unwrap()
+        .insert("signature".to_string(), signature.clone().into());
+
+    let (uri, _) = upload_metadata_json(&mut metadata, state).await?;
+
+    Ok(Json(SnapshotResponse {
+        uri,
+        signer,
+        signature,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct SnapshotResponse {
+    pub uri: String,
+    pub signer: String,
+    pub signature: String,
+}
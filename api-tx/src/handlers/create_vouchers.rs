@@ -0,0 +1,208 @@
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, VoucherParams},
+    State,
+};
+use anchor_lang::{prelude::Pubkey, AnchorSerialize};
+use axum::{Extension, Json};
+use base58::{FromBase58, ToBase58};
+use bpl_token_metadata::{
+    state::{Device, Location, Merchant, Voucher},
+    utils::find_promo_address,
+};
+use bpl_token_metadata_client::accounts::fetch_existing_account;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signature::Signature, signer::Signer};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use utoipa::ToSchema;
+
+/// Max number of vouchers generated in a single batch.
+pub const MAX_VOUCHER_BATCH: u64 = 1000;
+
+/// Number of seconds an authorization to create vouchers is valid for after its timestamp.
+pub const AUTHORIZATION_TTL: i64 = 300;
+
+/// Generates a batch of claim vouchers for a promo. Nonces start from the current time in
+/// microseconds times [MAX_VOUCHER_BATCH] so batches don't overlap.
+///
+/// The request has to be authorized by the owner of the merchant of the device's location, who
+/// signs [authorization_message] for the batch. Vouchers are signed and returned with a Solana
+/// Pay claim url if the device owner is the platform signer. Otherwise the serialized vouchers are
+/// returned for the device owner to sign, after which the claim url can be built the same way.
+#[utoipa::path(
+    post,
+    path = "/v2/voucher/create",
+    params(VoucherParams),
+    request_body = CreateVouchersData,
+    responses(
+        (status = 200, body = VoucherBatchResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        VoucherParams {
+            mint: Address(mint),
+            device: Address(device),
+            device_owner: Address(device_owner),
+            location: Address(location),
+            campaign: Address(campaign),
+        },
+        data,
+    ): Args<VoucherParams, Data>,
+) -> Result<Json<VoucherBatchResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %device,
+        %device_owner,
+        %location,
+        %campaign,
+        count = data.count,
+        authority = %data.authority,
+    );
+
+    if data.count == 0 || data.count > MAX_VOUCHER_BATCH {
+        return Err(AppError::VoucherError(format!(
+            "count must be between 1 and {MAX_VOUCHER_BATCH}"
+        )));
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| AppError::VoucherError(e.to_string()))?;
+    if (now.as_secs() as i64 - data.timestamp).abs() > AUTHORIZATION_TTL {
+        return Err(AppError::VoucherError("authorization expired".to_string()));
+    }
+
+    let authority = data.authority.0;
+    let recipient = data.recipient.map(|recipient| recipient.0);
+
+    let authorization: [u8; 64] = data
+        .authorization
+        .from_base58()
+        .ok()
+        .and_then(|signature| signature.try_into().ok())
+        .ok_or_else(|| AppError::VoucherError("invalid authorization".to_string()))?;
+    let message = authorization_message(&mint, &device, &data);
+    if !Signature::new(&authorization).verify(authority.as_ref(), message.as_bytes()) {
+        return Err(AppError::VoucherError("invalid authorization".to_string()));
+    }
+
+    let device_account = fetch_existing_account::<Device, _>(&state.solana, &device).await?;
+    if device_account.owner != device_owner || device_account.location != location {
+        return Err(AppError::VoucherError(
+            "device doesn't match device owner and location".to_string(),
+        ));
+    }
+    let merchant = fetch_existing_account::<Location, _>(&state.solana, &location)
+        .await?
+        .merchant;
+    if fetch_existing_account::<Merchant, _>(&state.solana, &merchant)
+        .await?
+        .owner
+        != authority
+    {
+        return Err(AppError::VoucherError(
+            "authority isn't the merchant owner".to_string(),
+        ));
+    }
+
+    let promo = find_promo_address(&mint).0;
+    let signs = device_owner == state.platform_signer.pubkey();
+    let app_url = state.app_url.trim_end_matches('/');
+
+    let start = now.as_micros() as u64 * MAX_VOUCHER_BATCH;
+
+    let vouchers = (0..data.count)
+        .map(|i| {
+            let voucher = Voucher {
+                promo,
+                nonce: start + i,
+                expiry: data.expiry,
+                recipient,
+            };
+            let payload = voucher.try_to_vec()?;
+
+            let (signature, url) = if signs {
+                let signature = state.platform_signer.sign_message(&payload);
+                let url = format!(
+                    "{app_url}/promo/claim/{mint}/{device}/{device_owner}/{location}/{campaign}/{}/{}/{}",
+                    payload.to_base58(),
+                    signature.as_ref().to_base58(),
+                    urlencoding::encode(&data.message),
+                );
+                (
+                    Some(signature.as_ref().to_base58()),
+                    Some(format!("solana:{}", urlencoding::encode(&url))),
+                )
+            } else {
+                (None, None)
+            };
+
+            Ok(VoucherResponse {
+                nonce: voucher.nonce,
+                voucher: payload.to_base58(),
+                signature,
+                url,
+            })
+        })
+        .collect::<Result<Vec<_>, std::io::Error>>()
+        .map_err(|e| AppError::VoucherError(e.to_string()))?;
+
+    Ok(Json(VoucherBatchResponse { vouchers }))
+}
+
+/// Message signed by the merchant owner to authorize a batch of vouchers.
+pub fn authorization_message(mint: &Pubkey, device: &Pubkey, data: &Data) -> String {
+    format!(
+        "Create {} vouchers of promo {mint} for device {device} expiring at {}{} at {}",
+        data.count,
+        data.expiry,
+        data.recipient
+            .as_ref()
+            .map(|recipient| format!(" for {recipient}"))
+            .unwrap_or_default(),
+        data.timestamp,
+    )
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = CreateVouchersData)]
+pub struct Data {
+    pub count: u64,
+    /// Unix timestamp after which the vouchers can no longer be claimed
+    pub expiry: i64,
+    /// Only this wallet can claim the vouchers, if set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<Address>,
+    /// Message shown in the wallet when claiming
+    pub message: String,
+    /// Owner of the merchant of the device's location
+    pub authority: Address,
+    /// Unix timestamp the authorization was signed at
+    pub timestamp: i64,
+    /// Base58 encoded signature of the authority over [authorization_message]
+    pub authorization: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct VoucherBatchResponse {
+    pub vouchers: Vec<VoucherResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct VoucherResponse {
+    pub nonce: u64,
+    /// Base58 encoded serialized voucher, which is the message signed by the device owner
+    pub voucher: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Solana Pay transaction request url for claiming the voucher
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
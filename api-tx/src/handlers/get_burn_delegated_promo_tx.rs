@@ -1,37 +1,66 @@
-use anchor_lang::prelude::Pubkey;
-use axum::{
-    extract::{Extension, Path},
-    Json,
+use anchor_spl::token_interface::TokenAccount;
+use axum::{extract::Extension, Json};
+use bpl_token_metadata_client::{
+    accounts::{
+        fetch_existing_account, fetch_referral_reward_accounts, fetch_sponsorship_accounts,
+    },
+    instructions::burn_delegated_promo_instruction,
 };
 use serde::{Deserialize, Serialize};
 use solana_sdk::{signer::Signer, transaction::Transaction};
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
+use utoipa::ToSchema;
 
-use crate::{error::AppError, utils::solana::burn_delegated_promo_instruction, State};
+use crate::{error::AppError, State};
 
-use super::{BurnDelegatedParams, PayResponse};
+use super::{Address, Args, BurnDelegatedParams, PayResponse};
 
+/// Returns a transaction burning a promo token delegated to the device owner.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/burn-delegated",
+    params(BurnDelegatedParams),
+    request_body = BurnDelegatedPromoData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
 pub async fn handler(
-    Path(BurnDelegatedParams {
-        mint,
-        token_account,
-        device,
-        location,
-        campaign,
-        message,
-        memo,
-    }): Path<BurnDelegatedParams>,
     Extension(state): Extension<Arc<State>>,
-    Json(data): Json<Data>,
+    Args(
+        BurnDelegatedParams {
+            mint: Address(mint),
+            token_account: Address(token_account),
+            device: Address(device),
+            location: Address(location),
+            campaign: Address(campaign),
+            message,
+            memo,
+        },
+        data,
+    ): Args<BurnDelegatedParams, Data>,
 ) -> Result<Json<PayResponse>, AppError> {
     let payer = state.platform_signer.pubkey();
-    let device_owner = Pubkey::from_str(&data.account)?;
-    let mint = Pubkey::from_str(&mint)?;
-    let token_account = Pubkey::from_str(&token_account)?;
-    let device = Pubkey::from_str(&device)?;
-    let location = Pubkey::from_str(&location)?;
-    let campaign = Pubkey::from_str(&campaign)?;
+    let device_owner = data.account.0;
     let platform = state.platform;
+    let token_program = state.solana.get_token_program(&mint).await?;
+
+    // Include the referral accounts if the token owner was referred so that their referrer is
+    // rewarded when the token is burned.
+    let token_owner = fetch_existing_account::<TokenAccount, _>(&state.solana, &token_account)
+        .await?
+        .owner;
+    let referral = fetch_referral_reward_accounts(&state.solana, &mint, &token_owner).await?;
+
+    // Include the sponsorship accounts so that the sponsor pays for the redemption.
+    let sponsorship = match data.sponsor.map(|address| address.0) {
+        Some(sponsor) => {
+            fetch_sponsorship_accounts(&state.solana, &campaign, &sponsor, &mint, &location).await?
+        }
+        None => None,
+    };
 
     let instruction = burn_delegated_promo_instruction(
         payer,
@@ -42,8 +71,11 @@ pub async fn handler(
         token_account,
         mint,
         platform,
+        token_program,
+        referral,
+        sponsorship,
         memo,
-    )?;
+    );
 
     let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
     let recent_blockhash = state.solana.get_latest_blockhash().await?;
@@ -58,9 +90,13 @@ pub async fn handler(
     }))
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = BurnDelegatedPromoData)]
 pub struct Data {
-    pub account: String,
+    pub account: Address,
+    /// Sponsor paying the burn fee and merchant reimbursement, if the promo is sponsored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<Address>,
 }
 
 // https://sandbox.dev.clover.com/v3/apps/MAC8DQKWCCB1R/merchants/XKDCJNW9JXGM1/notifications
@@ -0,0 +1,85 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, CheckInParams},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata_client::instructions::check_in_instruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction checking a pass holder in at a location.
+#[utoipa::path(
+    post,
+    path = "/v2/membership/checkin",
+    params(CheckInParams),
+    request_body = CheckInData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "membership"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        CheckInParams {
+            mint: Address(mint),
+            pass_mint: Address(pass_mint),
+            device: Address(device),
+            device_owner: Address(device_owner),
+            location: Address(location),
+            message,
+            memo,
+        },
+        data,
+    ): Args<CheckInParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %pass_mint,
+        %device,
+        %device_owner,
+        %location,
+        message = message,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let token_owner = data.account.0;
+
+    let instruction = check_in_instruction(
+        payer,
+        device_owner,
+        device,
+        location,
+        mint,
+        pass_mint,
+        token_owner,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+
+    // platform_signer signs as payer and also as device_owner if device_owner is platform_signer.
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+/// `account` is the holder of the membership pass.
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = CheckInData)]
+pub struct Data {
+    pub account: Address,
+}
@@ -0,0 +1,86 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, AddressList, Args, ClaimVaultParams},
+    utils::customer_vault::identity_hash,
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata_client::instructions::claim_customer_vault_instruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction claiming the tokens of each of the promos in the path from a customer
+/// vault into the customer's wallet, once the one time password sent by the vault lookup has
+/// been verified. The platform signer signs as vault authority, leaving the wallet to sign.
+#[utoipa::path(
+    post,
+    path = "/v2/vault/claim",
+    params(ClaimVaultParams),
+    request_body = ClaimCustomerVaultData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "vault"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        ClaimVaultParams {
+            message,
+            mints: AddressList(mints),
+        },
+        data,
+    ): Args<ClaimVaultParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(message = message, mints = ?mints);
+
+    if !state.otp.verify_code(&data.identity, &data.code).await? {
+        return Err(AppError::CustomerVaultError(
+            "invalid one time password".to_string(),
+        ));
+    }
+
+    let payer = state.platform_signer.pubkey();
+    let token_owner = data.account.0;
+    let identity_hash = identity_hash(&state.vault_salt, &data.identity)?;
+
+    let mut instructions = Vec::with_capacity(mints.len());
+    for mint in mints {
+        let token_program = state.solana.get_token_program(&mint).await?;
+        instructions.push(claim_customer_vault_instruction(
+            payer,
+            payer,
+            identity_hash,
+            token_owner,
+            mint,
+            token_program,
+            None,
+        ));
+    }
+
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+/// `account` is the wallet the tokens are claimed into, `identity` the customer's phone number
+/// or email and `code` the one time password sent to it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = ClaimCustomerVaultData)]
+pub struct Data {
+    pub account: Address,
+    pub identity: String,
+    pub code: String,
+}
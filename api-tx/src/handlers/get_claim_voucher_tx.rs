@@ -0,0 +1,108 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, ClaimVoucherParams},
+    utils::allowlist::get_allowlist_proof,
+    State,
+};
+use anchor_lang::AnchorDeserialize;
+use axum::{Extension, Json};
+use base58::FromBase58;
+use bpl_token_metadata::state::Voucher;
+use bpl_token_metadata_client::instructions::claim_promo_voucher_instruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction claiming a voucher signed by the device owner.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/claim",
+    params(ClaimVoucherParams),
+    request_body = ClaimVoucherData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        ClaimVoucherParams {
+            mint: Address(mint),
+            device: Address(device),
+            device_owner: Address(device_owner),
+            location: Address(location),
+            campaign: Address(campaign),
+            voucher,
+            signature,
+            message,
+            memo,
+        },
+        data,
+    ): Args<ClaimVoucherParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %device,
+        %device_owner,
+        %location,
+        %campaign,
+        %voucher,
+        message = message,
+        memo = memo
+    );
+
+    let voucher = voucher
+        .from_base58()
+        .ok()
+        .and_then(|payload| Voucher::try_from_slice(&payload).ok())
+        .ok_or_else(|| AppError::VoucherError("invalid voucher".to_string()))?;
+    let signature: [u8; 64] = signature
+        .from_base58()
+        .ok()
+        .and_then(|signature| signature.try_into().ok())
+        .ok_or_else(|| AppError::VoucherError("invalid voucher signature".to_string()))?;
+
+    let payer = state.platform_signer.pubkey();
+    let token_owner = data.account.0;
+    let token_program = state.solana.get_token_program(&mint).await?;
+    let proof = get_allowlist_proof(&mint, &token_owner, &state).await?;
+
+    let instructions = claim_promo_voucher_instruction(
+        payer,
+        device_owner,
+        device,
+        location,
+        campaign,
+        token_owner,
+        mint,
+        token_program,
+        voucher,
+        signature,
+        proof,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+
+    // The device owner signed the voucher, so platform_signer only signs as payer.
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = ClaimVoucherData)]
+pub struct Data {
+    pub account: Address,
+}
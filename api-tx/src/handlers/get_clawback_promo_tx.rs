@@ -0,0 +1,80 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, ClawbackParams},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata_client::instructions::clawback_promo_instruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction clawing back a promo token, for the campaign owner to sign.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/clawback",
+    params(ClawbackParams),
+    request_body = ClawbackPromoData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        ClawbackParams {
+            owner: Address(owner),
+            campaign: Address(campaign),
+            mint: Address(mint),
+            token_account: Address(token_account),
+        },
+        data,
+    ): Args<ClawbackParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %owner,
+        %campaign,
+        %mint,
+        %token_account,
+        data = format!("{:?}", data)
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let token_program = state.solana.get_token_program(&mint).await?;
+
+    let instruction = clawback_promo_instruction(
+        payer,
+        owner,
+        campaign,
+        mint,
+        token_account,
+        token_program,
+        data.burn,
+        data.reason,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message: "Claw back promo tokens".to_string(),
+    }))
+}
+
+/// Whether to burn the tokens rather than freeze the token account, and the reason for the
+/// clawback, which is recorded in a memo.
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = ClawbackPromoData)]
+pub struct Data {
+    pub burn: bool,
+    pub reason: String,
+}
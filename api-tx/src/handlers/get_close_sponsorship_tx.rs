@@ -0,0 +1,50 @@
+use axum::{extract::Extension, Json};
+use bpl_token_metadata_client::instructions::close_sponsorship_instruction;
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+
+use crate::{
+    error::AppError,
+    handlers::{Address, Params, SponsorshipParams},
+    State,
+};
+
+use super::PayResponse;
+
+/// Returns a transaction closing a sponsorship and returning its remaining funds to the sponsor.
+#[utoipa::path(
+    post,
+    path = "/v2/campaign/sponsor/close",
+    params(SponsorshipParams),
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "campaign"
+)]
+pub async fn handler(
+    Params(SponsorshipParams {
+        sponsor: Address(sponsor),
+        campaign: Address(campaign),
+        memo,
+    }): Params<SponsorshipParams>,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(%sponsor, %campaign, memo = memo);
+
+    let payer = state.platform_signer.pubkey();
+
+    let instruction = close_sponsorship_instruction(sponsor, campaign, memo);
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message: "Close sponsorship".to_string(),
+    }))
+}
@@ -2,47 +2,52 @@ use crate::{
     error::AppError,
     utils::{
         bundlr::{upload_image, upload_metadata_json},
-        multipart::{get_args, get_metadata},
-        solana::create_campaign_instruction,
+        multipart::{get_args, get_campaign_args, get_memo_args, get_metadata},
+        transactions::{get_campaign_location_transactions, sign_step_transactions},
     },
     State,
 };
-use anchor_lang::prelude::Pubkey;
-use axum::{
-    extract::{Multipart, Path},
-    Extension, Json,
-};
-use solana_sdk::{pubkey::ParsePubkeyError, signer::Signer, transaction::Transaction};
-use std::{str::FromStr, sync::Arc};
+use axum::{extract::Multipart, Extension, Json};
+use bpl_token_metadata::utils::{find_campaign_address, find_merchant_address};
+use bpl_token_metadata_client::instructions::create_campaign_instruction;
+use solana_sdk::signer::Signer;
+use std::sync::Arc;
 
-use super::{CampaignParams, PayResponse};
+use super::{Address, AddressList, CampaignParams, Params, PayStepsResponse};
 
+/// Uploads campaign metadata to Arweave and returns transactions creating the campaign.
+#[utoipa::path(
+    post,
+    path = "/v2/campaign/create",
+    params(CampaignParams),
+    request_body(
+        content = String,
+        content_type = "multipart/form-data",
+        description = "Metadata json and image"
+    ),
+    responses(
+        (status = 200, body = PayStepsResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "campaign"
+)]
 pub async fn handler(
-    Path(CampaignParams {
-        owner,
+    Params(CampaignParams {
+        owner: Address(owner),
         lamports,
         memo,
-        locations,
-    }): Path<CampaignParams>,
+        locations: AddressList(locations),
+    }): Params<CampaignParams>,
     Extension(state): Extension<Arc<State>>,
     multipart: Multipart,
-) -> Result<Json<PayResponse>, AppError> {
+) -> Result<Json<PayStepsResponse>, AppError> {
     tracing::debug!(
-        owner = owner,
+        %owner,
         lamports = lamports,
         memo = memo,
-        locations = locations
+        locations = ?locations
     );
     let payer = state.platform_signer.pubkey();
-    let owner = Pubkey::from_str(&owner)?;
-
-    let locations: Vec<Pubkey> = locations
-        .split("/")
-        .filter(|s| s != &"")
-        .map(Pubkey::from_str)
-        .collect::<Vec<Result<Pubkey, ParsePubkeyError>>>()
-        .into_iter()
-        .collect::<Result<Vec<Pubkey>, ParsePubkeyError>>()?;
 
     // Parse metadata - leaving option of image in the future.
     let (mut metadata_data, image_data) = get_metadata(multipart).await?;
@@ -53,9 +58,11 @@ pub async fn handler(
             .ok_or(AppError::MultipartMetadataError(
                 "metadata data part should be an object".to_string(),
             ))?;
+    let memo = get_memo_args(metadata_data_obj, memo)?;
 
     // // Parse args.
     let (name, active) = get_args(metadata_data_obj)?;
+    let (max_mint, max_burn) = get_campaign_args(metadata_data_obj)?;
     metadata_data_obj.remove("active");
 
     // If image exists, upload to arweave and add uri to metadata.
@@ -70,19 +77,32 @@ pub async fn handler(
     // Upload metadata json to Arweave.
     let (uri, state) = upload_metadata_json(metadata_data_obj, state).await?;
 
-    // Create campaign instruction.
-    let instructions =
-        create_campaign_instruction(payer, owner, name, uri, lamports, locations, active, memo)?;
-
-    let mut tx = Transaction::new_with_payer(&instructions, Some(&payer));
-    let latest_blockhash = &state.solana.get_latest_blockhash().await?;
-    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash.clone())?;
+    let campaign = find_campaign_address(&find_merchant_address(&owner).0, &name).0;
 
-    let serialized = bincode::serialize(&tx)?;
-    let transaction = base64::encode(serialized);
+    // Create campaign instruction, with the locations linked in as many transactions as needed.
+    let instructions = create_campaign_instruction(
+        payer,
+        owner,
+        name,
+        uri,
+        lamports,
+        vec![],
+        max_mint,
+        max_burn,
+        active,
+        memo,
+    );
+    let transactions = get_campaign_location_transactions(
+        &payer,
+        &owner,
+        &campaign,
+        instructions,
+        &locations,
+        false,
+    )?;
 
-    Ok(Json(PayResponse {
-        transaction,
+    Ok(Json(PayStepsResponse {
+        transactions: sign_step_transactions(transactions, &state).await?,
         message: "Create campaign".to_string(),
     }))
 }
@@ -2,28 +2,40 @@ use crate::{
     error::AppError,
     utils::{
         bundlr::{upload_image, upload_metadata_json},
-        multipart::{get_args, get_metadata},
-        solana::create_device_instruction,
+        multipart::{get_args, get_memo_args, get_metadata},
     },
     State,
 };
-use anchor_lang::prelude::Pubkey;
-use axum::{
-    extract::{Multipart, Path},
-    Extension, Json,
-};
+use axum::{extract::Multipart, Extension, Json};
+use bpl_token_metadata_client::instructions::create_device_instruction;
 use solana_sdk::{signer::Signer, transaction::Transaction};
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
 
-use super::{DeviceParams, PayResponse};
+use super::{Address, DeviceParams, Params, PayResponse};
 
+/// Uploads device metadata to Arweave and returns a transaction creating the device.
+#[utoipa::path(
+    post,
+    path = "/v2/device/create",
+    params(DeviceParams),
+    request_body(
+        content = String,
+        content_type = "multipart/form-data",
+        description = "Metadata json and image"
+    ),
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "merchant"
+)]
 pub async fn handler(
-    Path(DeviceParams {
-        merchant_owner,
-        location,
-        owner,
+    Params(DeviceParams {
+        merchant_owner: Address(merchant_owner),
+        location: Address(location),
+        owner: Address(owner),
         memo,
-    }): Path<DeviceParams>,
+    }): Params<DeviceParams>,
     Extension(state): Extension<Arc<State>>,
     multipart: Multipart,
 ) -> Result<Json<PayResponse>, AppError> {
@@ -37,6 +49,7 @@ pub async fn handler(
             .ok_or(AppError::MultipartMetadataError(
                 "metadata data part should be an object".to_string(),
             ))?;
+    let memo = get_memo_args(metadata_data_obj, memo)?;
 
     // Parse args.
     let (name, active) = get_args(metadata_data_obj)?;
@@ -54,10 +67,6 @@ pub async fn handler(
     // Upload metadata json to Arweave.
     let (uri, state) = upload_metadata_json(metadata_data_obj, state).await?;
 
-    let merchant_owner = Pubkey::from_str(&merchant_owner)?;
-    let location = Pubkey::from_str(&location)?;
-    let owner = Pubkey::from_str(&owner)?;
-
     // Create location instruction.
     let ix = create_device_instruction(
         payer,
@@ -68,7 +77,7 @@ pub async fn handler(
         uri,
         active,
         memo,
-    )?;
+    );
 
     let mut tx = Transaction::new_with_payer(&[ix], Some(&payer));
     let latest_blockhash = &state.solana.get_latest_blockhash().await?;
@@ -0,0 +1,102 @@
+use crate::{
+    error::AppError,
+    utils::{
+        bundlr::{upload_image, upload_metadata_json},
+        multipart::{get_membership_args, get_memo_args, get_metadata},
+    },
+    State,
+};
+use axum::{extract::Multipart, Extension, Json};
+use bpl_token_metadata::state::DataV2;
+use bpl_token_metadata_client::instructions::create_membership_instruction;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+
+use super::{Address, MembershipParams, Params, PayResponse};
+
+/// Uploads membership metadata to Arweave and returns a transaction creating the membership.
+#[utoipa::path(
+    post,
+    path = "/v2/membership/create",
+    params(MembershipParams),
+    request_body(
+        content = String,
+        content_type = "multipart/form-data",
+        description = "Metadata json and image"
+    ),
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "membership"
+)]
+pub async fn handler(
+    Params(MembershipParams {
+        owner: Address(owner),
+        memo,
+    }): Params<MembershipParams>,
+    Extension(state): Extension<Arc<State>>,
+    multipart: Multipart,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(%owner, memo = memo);
+
+    // Parse data - json data plus optional image.
+    let (mut metadata_data, image_data) = get_metadata(multipart).await?;
+
+    let metadata_data_obj =
+        metadata_data
+            .as_object_mut()
+            .ok_or(AppError::MultipartMetadataError(
+                "metadata data part should be an object".to_string(),
+            ))?;
+    let memo = get_memo_args(metadata_data_obj, memo)?;
+
+    let (name, symbol, duration, max_supply) = get_membership_args(metadata_data_obj)?;
+
+    // If image exists, upload to arweave and add uri to metadata.
+    let state = if let Some(image_data) = image_data {
+        let (image_url, _, state) = upload_image(image_data, state).await?;
+        metadata_data_obj.insert("image".to_string(), image_url.into());
+        state
+    } else {
+        state
+    };
+
+    // Upload metadata json to Arweave.
+    let (uri, state) = upload_metadata_json(metadata_data_obj, state).await?;
+
+    let mint_keypair = Keypair::new();
+    let payer = state.platform_signer.pubkey();
+
+    let data = DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    let ix = create_membership_instruction(
+        payer,
+        owner,
+        mint_keypair.pubkey(),
+        data,
+        duration,
+        max_supply,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[ix], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer, &mint_keypair], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message: "Create membership".to_string(),
+    }))
+}
@@ -2,23 +2,38 @@ use crate::{
     error::AppError,
     utils::{
         bundlr::{upload_image, upload_metadata_json},
-        multipart::{get_args, get_metadata},
-        solana::create_merchant_instruction,
+        multipart::{get_args, get_memo_args, get_metadata},
     },
     State,
 };
-use anchor_lang::prelude::Pubkey;
-use axum::{
-    extract::{Multipart, Path},
-    Extension, Json,
-};
+use axum::{extract::Multipart, Extension, Json};
+use bpl_token_metadata_client::instructions::create_merchant_instruction;
 use solana_sdk::{signature::Signer, transaction::Transaction};
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
 
-use super::{MerchantParams, PayResponse};
+use super::{Address, MerchantParams, Params, PayResponse};
 
+/// Uploads merchant metadata to Arweave and returns a transaction creating the merchant.
+#[utoipa::path(
+    post,
+    path = "/v2/merchant/create",
+    params(MerchantParams),
+    request_body(
+        content = String,
+        content_type = "multipart/form-data",
+        description = "Metadata json and image"
+    ),
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "merchant"
+)]
 pub async fn handler(
-    Path(MerchantParams { owner, memo }): Path<MerchantParams>,
+    Params(MerchantParams {
+        owner: Address(owner),
+        memo,
+    }): Params<MerchantParams>,
     Extension(state): Extension<Arc<State>>,
     multipart: Multipart,
 ) -> Result<Json<PayResponse>, AppError> {
@@ -34,6 +49,7 @@ pub async fn handler(
             .ok_or(AppError::MultipartMetadataError(
                 "metadata data part should be an object".to_string(),
             ))?;
+    let memo = get_memo_args(metadata_data_obj, memo)?;
 
     // Parse args.
     let (name, active) = get_args(metadata_data_obj)?;
@@ -52,10 +68,8 @@ pub async fn handler(
     let (uri, state) = upload_metadata_json(metadata_data_obj, state).await?;
     // let uri = "https://merchant.example.com".to_string();
 
-    let owner = Pubkey::from_str(&owner)?;
-
     // Create merchant instruction.
-    let ix = create_merchant_instruction(payer, owner, name, uri, active, memo)?;
+    let ix = create_merchant_instruction(payer, owner, name, uri, active, memo);
 
     let mut tx = Transaction::new_with_payer(&[ix], Some(&payer));
     let latest_blockhash = &state.solana.get_latest_blockhash().await?;
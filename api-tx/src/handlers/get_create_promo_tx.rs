@@ -1,33 +1,51 @@
 use crate::{
     error::AppError,
     utils::{
+        allowlist::{get_allowlist, get_allowlist_uri},
         bundlr::{upload_image, upload_metadata_json},
-        multipart::{get_metadata, get_promo_args},
-        solana::create_promo_instruction,
+        metadata::{get_arweave_id, get_dynamic_metadata_uri},
+        multipart::{
+            get_memo_args, get_metadata, get_promo_approval_args, get_promo_args,
+            get_promo_dynamic_args, get_promo_gift_policy_args, get_promo_referral_args,
+            get_promo_stamp_card_args, get_promo_terms_args, get_promo_token_args,
+        },
     },
     State,
 };
-use anchor_lang::prelude::Pubkey;
-use axum::{
-    extract::{Multipart, Path},
-    Extension, Json,
-};
+use axum::{extract::Multipart, Extension, Json};
+use bpl_token_metadata_client::instructions::create_promo_instruction;
 use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
 
-use super::{PayResponse, PromoParams};
+use super::{Address, Params, PayResponse, PromoParams};
 
+/// Uploads promo metadata to Arweave and returns a transaction creating the promo.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/create",
+    params(PromoParams),
+    request_body(
+        content = String,
+        content_type = "multipart/form-data",
+        description = "Metadata json and image"
+    ),
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
 #[axum_macros::debug_handler]
 pub async fn handler(
-    Path(PromoParams {
-        owner,
-        campaign,
+    Params(PromoParams {
+        owner: Address(owner),
+        campaign: Address(campaign),
         memo,
-    }): Path<PromoParams>,
+    }): Params<PromoParams>,
     Extension(state): Extension<Arc<State>>,
     multipart: Multipart,
 ) -> Result<Json<PayResponse>, AppError> {
-    tracing::debug!(owner = owner, campaign = campaign, memo = memo,);
+    tracing::debug!(%owner, %campaign, memo = memo);
 
     // Parse data - json data plus optional image. If image data exists it gets
     // uploaded to arweave and an image property added to the json metadata.
@@ -39,13 +57,33 @@ pub async fn handler(
             .ok_or(AppError::MultipartMetadataError(
                 "metadata data part should be an object".to_string(),
             ))?;
+    let memo = get_memo_args(metadata_data_obj, memo)?;
 
     // Parse args.
-    let (name, symbol, max_mint, max_burn, active) = get_promo_args(metadata_data_obj)?;
+    let (name, symbol, max_mint, max_burn, active, schedule) = get_promo_args(metadata_data_obj)?;
+    let (token_program, extensions) = get_promo_token_args(metadata_data_obj)?;
+    let referral = get_promo_referral_args(metadata_data_obj)?;
+    let stamp_threshold = get_promo_stamp_card_args(metadata_data_obj)?;
+    let terms = get_promo_terms_args(metadata_data_obj)?;
+    let requires_approval = get_promo_approval_args(metadata_data_obj)?;
+    let gift_policy = get_promo_gift_policy_args(metadata_data_obj)?;
+    let dynamic = get_promo_dynamic_args(metadata_data_obj)?;
     metadata_data_obj.remove("active");
+    metadata_data_obj.remove("dynamic");
+    metadata_data_obj.remove("requiresApproval");
+    metadata_data_obj.remove("giftPolicy");
+    metadata_data_obj.remove("tokenProgram");
+    metadata_data_obj.remove("extensions");
     metadata_data_obj.remove("max_mint");
     metadata_data_obj.remove("max_burn");
 
+    // If the promo is allowlisted, rebuild the tree from the uploaded wallet list to get its
+    // root. The allowlist attribute is kept so proofs can be served when minting.
+    let allowlist = match get_allowlist_uri(metadata_data_obj) {
+        Some(uri) => Some(get_allowlist(&uri, &state).await?.root()),
+        None => None,
+    };
+
     // If image exists, upload to arweave and add uri to metadata.
     let state = if let Some(image_data) = image_data {
         let (image_url, _, state) = upload_image(image_data, state).await?;
@@ -59,9 +97,18 @@ pub async fn handler(
     let (uri, state) = upload_metadata_json(metadata_data_obj, state).await?;
 
     let mint_keypair = Keypair::new();
+
+    // Dynamic promos point at the rendering endpoint, with the uploaded json as the template.
+    let uri = if dynamic {
+        get_dynamic_metadata_uri(
+            &state.metadata_url,
+            get_arweave_id(&uri)?,
+            &mint_keypair.pubkey(),
+        )
+    } else {
+        uri
+    };
     let payer = state.platform_signer.pubkey();
-    let owner = Pubkey::from_str(&owner)?;
-    let campaign = Pubkey::from_str(&campaign)?;
 
     // Create promo instruction.
     let ix = create_promo_instruction(
@@ -76,9 +123,18 @@ pub async fn handler(
         max_mint,
         max_burn,
         active,
+        schedule,
+        allowlist,
+        referral,
+        stamp_threshold,
+        terms,
+        requires_approval,
+        gift_policy,
         true,
+        token_program,
+        extensions,
         memo,
-    )?;
+    );
 
     let mut tx = Transaction::new_with_payer(&[ix], Some(&payer));
     let latest_blockhash = state.solana.get_latest_blockhash().await?;
@@ -0,0 +1,92 @@
+use axum::{extract::Extension, Json};
+use bpl_token_metadata::state::Campaign;
+use bpl_token_metadata_client::{
+    accounts::fetch_existing_account, instructions::create_sponsorship_instruction,
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, SponsorshipParams},
+    State,
+};
+
+use super::PayResponse;
+
+/// Returns a transaction funding a sponsorship of a campaign's promos.
+#[utoipa::path(
+    post,
+    path = "/v2/campaign/sponsor",
+    params(SponsorshipParams),
+    request_body = CreateSponsorshipData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "campaign"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        SponsorshipParams {
+            sponsor: Address(sponsor),
+            campaign: Address(campaign),
+            memo,
+        },
+        data,
+    ): Args<SponsorshipParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %sponsor,
+        %campaign,
+        memo = memo,
+        data = format!("{:?}", data)
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let merchant = fetch_existing_account::<Campaign, _>(&state.solana, &campaign)
+        .await?
+        .merchant;
+
+    let mints = data.mints.iter().map(|mint| mint.0).collect();
+
+    let instruction = create_sponsorship_instruction(
+        payer,
+        sponsor,
+        merchant,
+        campaign,
+        mints,
+        data.reimbursement_lamports,
+        data.lamports,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message: "Sponsor campaign".to_string(),
+    }))
+}
+
+/// Terms of the sponsorship. Sponsors all of the campaign's promos if `mints` is empty.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = CreateSponsorshipData)]
+pub struct Data {
+    #[serde(default)]
+    pub mints: Vec<Address>,
+    /// Paid to the owner of the redeeming location's merchant for each redemption
+    #[serde(default)]
+    pub reimbursement_lamports: u64,
+    /// Funds transferred from the sponsor to the sponsorship
+    pub lamports: u64,
+}
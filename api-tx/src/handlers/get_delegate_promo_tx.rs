@@ -1,39 +1,49 @@
-use anchor_lang::prelude::Pubkey;
-use axum::{
-    extract::{Extension, Path},
-    Json,
-};
+use axum::{extract::Extension, Json};
+use bpl_token_metadata_client::instructions::delegate_promo_instruction;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{signer::Signer, transaction::Transaction};
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
+use utoipa::ToSchema;
 
 use crate::{
-    error::AppError, handlers::DelegateParams, utils::solana::delegate_promo_instruction, State,
+    error::AppError,
+    handlers::{Address, Args, DelegateParams},
+    State,
 };
 
 use super::PayResponse;
 
 // Payer is distinct from
+/// Returns a transaction delegating a promo token to the device owner to burn at checkout.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/delegate",
+    params(DelegateParams),
+    request_body = DelegatePromoData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
 pub async fn handler(
-    Path(DelegateParams {
-        mint,
-        device_owner,
-        device,
-        location,
-        campaign,
-        message,
-        memo,
-    }): Path<DelegateParams>,
     Extension(state): Extension<Arc<State>>,
-    Json(data): Json<Data>,
+    Args(
+        DelegateParams {
+            mint: Address(mint),
+            device_owner: Address(device_owner),
+            device: Address(device),
+            location: Address(location),
+            campaign: Address(campaign),
+            message,
+            memo,
+        },
+        data,
+    ): Args<DelegateParams, Data>,
 ) -> Result<Json<PayResponse>, AppError> {
-    let token_owner = Pubkey::from_str(&data.account)?;
+    let token_owner = data.account.0;
     let payer = state.platform_signer.pubkey();
-    let mint = Pubkey::from_str(&mint)?;
-    let device_owner = Pubkey::from_str(&device_owner)?;
-    let device = Pubkey::from_str(&device)?;
-    let location = Pubkey::from_str(&location)?;
-    let campaign = Pubkey::from_str(&campaign)?;
+    let token_program = state.solana.get_token_program(&mint).await?;
 
     let instruction = delegate_promo_instruction(
         payer,
@@ -43,8 +53,9 @@ pub async fn handler(
         location,
         token_owner,
         mint,
+        token_program,
         memo,
-    )?;
+    );
 
     let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
     let recent_blockhash = state.solana.get_latest_blockhash().await?;
@@ -59,9 +70,10 @@ pub async fn handler(
     }))
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = DelegatePromoData)]
 pub struct Data {
-    pub account: String,
+    pub account: Address,
 }
 
 // https://sandbox.dev.clover.com/v3/apps/MAC8DQKWCCB1R/merchants/XKDCJNW9JXGM1/notifications
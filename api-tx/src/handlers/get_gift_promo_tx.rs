@@ -0,0 +1,83 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, GiftParams},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata::{state::Promo, utils::find_promo_address};
+use bpl_token_metadata_client::{
+    accounts::fetch_existing_account, instructions::gift_promo_token_instruction,
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction gifting a promo token from the account to the recipient.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/gift",
+    params(GiftParams),
+    request_body = GiftPromoData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        GiftParams {
+            mint: Address(mint),
+            recipient: Address(recipient),
+            message,
+            memo,
+        },
+        data,
+    ): Args<GiftParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %recipient,
+        message = message,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let sender = data.account.0;
+    let token_program = state.solana.get_token_program(&mint).await?;
+
+    // The gift policy decides whether the gift accounts are included.
+    let promo: Promo = fetch_existing_account(&state.solana, &find_promo_address(&mint).0).await?;
+
+    let instruction = gift_promo_token_instruction(
+        payer,
+        sender,
+        recipient,
+        mint,
+        token_program,
+        promo.gift_policy,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+/// `account` is the token owner sending the gift.
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = GiftPromoData)]
+pub struct Data {
+    pub account: Address,
+}
@@ -0,0 +1,73 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, IssueMembershipParams, Params},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata::{state::Membership, utils::find_membership_address};
+use bpl_token_metadata_client::{
+    accounts::fetch_existing_account, instructions::issue_membership_pass_instruction,
+};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+
+/// Returns a transaction issuing a membership pass to the token owner.
+#[utoipa::path(
+    post,
+    path = "/v2/membership/issue",
+    params(IssueMembershipParams),
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "membership"
+)]
+pub async fn handler(
+    Params(IssueMembershipParams {
+        owner: Address(owner),
+        mint: Address(mint),
+        token_owner: Address(token_owner),
+        memo,
+    }): Params<IssueMembershipParams>,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %owner,
+        %mint,
+        %token_owner,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let pass_mint_keypair = Keypair::new();
+
+    // The edition number of the new pass determines its edition marker account.
+    let membership: Membership =
+        fetch_existing_account(&state.solana, &find_membership_address(&mint).0).await?;
+
+    let instruction = issue_membership_pass_instruction(
+        payer,
+        owner,
+        mint,
+        membership.pass_count + 1,
+        token_owner,
+        pass_mint_keypair.pubkey(),
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(
+        &[&state.platform_signer, &pass_mint_keypair],
+        latest_blockhash,
+    )?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message: "Issue membership pass".to_string(),
+    }))
+}
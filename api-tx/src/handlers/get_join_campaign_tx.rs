@@ -0,0 +1,61 @@
+use axum::{extract::Extension, Json};
+use bpl_token_metadata_client::instructions::join_campaign_instruction;
+use solana_sdk::signer::Signer;
+use std::sync::Arc;
+
+use crate::{
+    error::AppError,
+    handlers::{Address, AddressList, JoinCampaignParams, Params},
+    utils::transactions::{get_campaign_location_transactions, sign_step_transactions},
+    State,
+};
+
+use super::PayStepsResponse;
+
+/// Returns transactions adding a merchant and its locations to a coalition campaign.
+#[utoipa::path(
+    post,
+    path = "/v2/campaign/join",
+    params(JoinCampaignParams),
+    responses(
+        (status = 200, body = PayStepsResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "campaign"
+)]
+pub async fn handler(
+    Params(JoinCampaignParams {
+        campaign_owner: Address(campaign_owner),
+        campaign: Address(campaign),
+        owner: Address(owner),
+        locations: AddressList(locations),
+    }): Params<JoinCampaignParams>,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<PayStepsResponse>, AppError> {
+    tracing::debug!(
+        %campaign_owner,
+        %campaign,
+        %owner,
+        locations = ?locations
+    );
+    let payer = state.platform_signer.pubkey();
+
+    let instructions =
+        join_campaign_instruction(payer, campaign_owner, campaign, owner, vec![], None);
+
+    // Both the campaign owner and the joining merchant owner still need to sign the first
+    // transaction, and the joining merchant owner the rest.
+    let transactions = get_campaign_location_transactions(
+        &payer,
+        &owner,
+        &campaign,
+        instructions,
+        &locations,
+        true,
+    )?;
+
+    Ok(Json(PayStepsResponse {
+        transactions: sign_step_transactions(transactions, &state).await?,
+        message: "Join campaign".to_string(),
+    }))
+}
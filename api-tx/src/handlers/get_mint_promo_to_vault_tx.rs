@@ -0,0 +1,95 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, MintParams},
+    utils::allowlist::get_allowlist_proof,
+    utils::customer_vault::identity_hash,
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata::utils::find_customer_vault_address;
+use bpl_token_metadata_client::instructions::mint_promo_token_to_vault_instruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Mints a promo token into the vault of a customer without a wallet. Called by the point of
+/// sale with the customer's phone number or email rather than by a wallet, so the identity
+/// isn't included in the url.
+#[utoipa::path(
+    post,
+    path = "/v2/vault/mint",
+    params(MintParams),
+    request_body = MintPromoToVaultData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "vault"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        MintParams {
+            mint: Address(mint),
+            device: Address(device),
+            device_owner: Address(device_owner),
+            location: Address(location),
+            campaign: Address(campaign),
+            message,
+            memo,
+        },
+        data,
+    ): Args<MintParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %device,
+        %device_owner,
+        %location,
+        %campaign,
+        message = message,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let identity_hash = identity_hash(&state.vault_salt, &data.identity)?;
+    let customer_vault = find_customer_vault_address(&identity_hash).0;
+    let token_program = state.solana.get_token_program(&mint).await?;
+    let proof = get_allowlist_proof(&mint, &customer_vault, &state).await?;
+
+    let instruction = mint_promo_token_to_vault_instruction(
+        payer,
+        device_owner,
+        device,
+        location,
+        campaign,
+        identity_hash,
+        mint,
+        token_program,
+        proof,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+
+    // platform_signer signs as payer and also as device_owner if device_owner is platform_signer.
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+/// `identity` is the customer's phone number or email.
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = MintPromoToVaultData)]
+pub struct Data {
+    pub identity: String,
+}
@@ -1,41 +1,59 @@
 use super::PayResponse;
-use crate::{error::AppError, handlers::MintParams, utils::solana::mint_promo_instruction, State};
-use anchor_lang::prelude::Pubkey;
-use axum::{extract::Path, Extension, Json};
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, MintParams},
+    utils::allowlist::get_allowlist_proof,
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata_client::instructions::mint_promo_instruction;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{signer::Signer, transaction::Transaction};
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
+use utoipa::ToSchema;
 
+/// Returns a transaction minting a promo token to the account, paid for by the platform.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/mint",
+    params(MintParams),
+    request_body = MintPromoData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
 pub async fn handler(
-    Path(MintParams {
-        mint,
-        device,
-        device_owner,
-        location,
-        campaign,
-        message,
-        memo,
-    }): Path<MintParams>,
     Extension(state): Extension<Arc<State>>,
-    Json(data): Json<Data>,
+    Args(
+        MintParams {
+            mint: Address(mint),
+            device: Address(device),
+            device_owner: Address(device_owner),
+            location: Address(location),
+            campaign: Address(campaign),
+            message,
+            memo,
+        },
+        data,
+    ): Args<MintParams, Data>,
 ) -> Result<Json<PayResponse>, AppError> {
     tracing::debug!(
-        mint = mint,
-        device = device,
-        device_owner = device_owner,
-        location = location,
-        campaign = campaign,
+        %mint,
+        %device,
+        %device_owner,
+        %location,
+        %campaign,
         message = message,
         memo = memo
     );
 
     let payer = state.platform_signer.pubkey();
-    let device_owner = Pubkey::from_str(&device_owner)?;
-    let mint = Pubkey::from_str(&mint)?;
-    let device = Pubkey::from_str(&device)?;
-    let campaign = Pubkey::from_str(&campaign)?;
-    let location = Pubkey::from_str(&location)?;
-    let token_owner = Pubkey::from_str(&data.account)?;
+    let token_owner = data.account.0;
+    let token_program = state.solana.get_token_program(&mint).await?;
+    let proof = get_allowlist_proof(&mint, &token_owner, &state).await?;
+    let referrer = data.referrer.map(|address| address.0);
 
     let instruction = mint_promo_instruction(
         payer,
@@ -45,8 +63,11 @@ pub async fn handler(
         campaign,
         token_owner,
         mint,
+        token_program,
+        proof,
+        referrer,
         memo,
-    )?;
+    );
 
     let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
     let latest_blockhash = state.solana.get_latest_blockhash().await?;
@@ -63,7 +84,11 @@ pub async fn handler(
     }))
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = MintPromoData)]
 pub struct Data {
-    pub account: String,
+    pub account: Address,
+    /// Customer who referred the token owner, if the promo has referral rewards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<Address>,
 }
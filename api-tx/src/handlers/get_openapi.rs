@@ -0,0 +1,137 @@
+use crate::{
+    error::{AppError, ErrorResponse},
+    handlers::{
+        create_allowlist, create_allowlist::AllowlistResponse, create_metadata_snapshot,
+        create_metadata_snapshot::SnapshotResponse, create_vouchers,
+        create_vouchers::VoucherBatchResponse, create_vouchers::VoucherResponse,
+        get_approve_mint_request_tx, get_burn_delegated_promo_tx, get_check_in_tx,
+        get_claim_customer_vault_tx, get_claim_voucher_tx, get_clawback_promo_tx,
+        get_close_sponsorship_tx, get_create_campaign_tx, get_create_device_tx,
+        get_create_location_tx, get_create_membership_tx, get_create_merchant_tx,
+        get_create_promo_tx, get_create_sponsorship_tx, get_delegate_promo_tx, get_gift_promo_tx,
+        get_issue_membership_pass_tx, get_join_campaign_tx, get_mint_promo_to_vault_tx,
+        get_mint_promo_tx, get_promo_metadata, get_redeem_promo_tx, get_reject_mint_request_tx,
+        get_renew_membership_pass_tx, get_request_promo_mint_tx,
+        get_set_campaign_location_limits_tx, get_sign_memo_tx, get_stamp_promo_tx,
+        lookup_customer_vault, lookup_customer_vault::VaultResponse, Address, AddressList,
+        PayResponse, PayStepsResponse, StepTransaction,
+    },
+};
+use axum::{response::Html, Json};
+use utoipa::OpenApi;
+
+/// OpenAPI document of the v2 routes, generated from the handlers and the types they take and
+/// return.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "bokoup transaction api",
+        description = "Solana Pay transaction requests for bokoup promos. A `GET` to any route \
+        answering `POST` with a transaction returns the label and icon shown by wallets. The \
+        original routes take the same parameters as path segments."
+    ),
+    paths(
+        get_mint_promo_tx::handler,
+        get_stamp_promo_tx::handler,
+        get_claim_voucher_tx::handler,
+        get_request_promo_mint_tx::handler,
+        get_approve_mint_request_tx::handler,
+        get_reject_mint_request_tx::handler,
+        get_gift_promo_tx::handler,
+        get_delegate_promo_tx::handler,
+        get_burn_delegated_promo_tx::handler,
+        get_redeem_promo_tx::handler,
+        get_clawback_promo_tx::handler,
+        get_create_promo_tx::handler,
+        create_allowlist::handler,
+        get_promo_metadata::handler,
+        create_metadata_snapshot::handler,
+        create_vouchers::handler,
+        get_mint_promo_to_vault_tx::handler,
+        lookup_customer_vault::handler,
+        get_claim_customer_vault_tx::handler,
+        get_sign_memo_tx::handler,
+        get_create_merchant_tx::handler,
+        get_create_location_tx::handler,
+        get_create_device_tx::handler,
+        get_create_campaign_tx::handler,
+        get_join_campaign_tx::handler,
+        get_create_sponsorship_tx::handler,
+        get_close_sponsorship_tx::handler,
+        get_set_campaign_location_limits_tx::handler,
+        get_create_membership_tx::handler,
+        get_issue_membership_pass_tx::handler,
+        get_renew_membership_pass_tx::handler,
+        get_check_in_tx::handler,
+    ),
+    components(schemas(
+        Address,
+        AddressList,
+        PayResponse,
+        PayStepsResponse,
+        StepTransaction,
+        ErrorResponse,
+        AllowlistResponse,
+        SnapshotResponse,
+        VoucherBatchResponse,
+        VoucherResponse,
+        VaultResponse,
+        get_mint_promo_tx::Data,
+        get_stamp_promo_tx::Data,
+        get_claim_voucher_tx::Data,
+        get_request_promo_mint_tx::Data,
+        get_approve_mint_request_tx::Data,
+        get_reject_mint_request_tx::Data,
+        get_gift_promo_tx::Data,
+        get_delegate_promo_tx::Data,
+        get_burn_delegated_promo_tx::Data,
+        get_redeem_promo_tx::Data,
+        get_clawback_promo_tx::Data,
+        create_vouchers::Data,
+        get_mint_promo_to_vault_tx::Data,
+        lookup_customer_vault::Data,
+        get_claim_customer_vault_tx::Data,
+        get_sign_memo_tx::Data,
+        get_create_sponsorship_tx::Data,
+        get_set_campaign_location_limits_tx::Data,
+        get_check_in_tx::Data,
+    )),
+    tags(
+        (name = "promo", description = "Minting, redeeming and managing promo tokens"),
+        (name = "metadata", description = "Dynamic promo metadata"),
+        (name = "vault", description = "Customer vaults for customers without wallets"),
+        (name = "memo", description = "Signed memos"),
+        (name = "merchant", description = "Merchants, locations and devices"),
+        (name = "campaign", description = "Campaigns, coalitions and sponsorships"),
+        (name = "membership", description = "Memberships and passes"),
+    )
+)]
+pub struct ApiDoc;
+
+pub async fn handler() -> Result<Json<utoipa::openapi::OpenApi>, AppError> {
+    Ok(Json(ApiDoc::openapi()))
+}
+
+/// Serves a Swagger UI page for the OpenAPI document.
+pub async fn docs_handler() -> Html<&'static str> {
+    Html(DOCS)
+}
+
+const DOCS: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>bokoup transaction api</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>
+"##;
@@ -0,0 +1,41 @@
+use crate::{
+    error::AppError,
+    utils::metadata::{check_template, get_promo_state, get_template, render_metadata},
+    State,
+};
+use axum::{Extension, Json};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+use super::{Address, MetadataParams, Params};
+
+/// Serves metadata json for dynamic promos, rendered from the template uploaded when the promo
+/// was created and its indexed state. Holder values are included when an owner is given. Only
+/// the template in the promo's metadata uri can be rendered.
+#[utoipa::path(
+    get,
+    path = "/v2/metadata",
+    params(MetadataParams),
+    responses(
+        (status = 200, body = Object),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "metadata"
+)]
+pub async fn handler(
+    Params(MetadataParams {
+        template,
+        mint: Address(mint),
+        owner,
+    }): Params<MetadataParams>,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<Map<String, Value>>, AppError> {
+    let owner = owner.map(|o| o.0);
+    tracing::debug!(template = template, %mint, owner = ?owner);
+
+    let promo_state = get_promo_state(&mint, owner.as_ref(), &state).await?;
+    check_template(&template, &mint, &promo_state.metadata_uri)?;
+    let template = get_template(&template, &state).await?;
+
+    Ok(Json(render_metadata(&template, &promo_state)?))
+}
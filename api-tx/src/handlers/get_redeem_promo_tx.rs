@@ -0,0 +1,101 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, RedeemParams},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata_client::{
+    accounts::fetch_sponsorship_accounts, instructions::redeem_promo_instruction,
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction redeeming a promo token at a location.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/redeem",
+    params(RedeemParams),
+    request_body = RedeemPromoData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        RedeemParams {
+            mint: Address(mint),
+            device: Address(device),
+            device_owner: Address(device_owner),
+            location: Address(location),
+            campaign: Address(campaign),
+            message,
+            memo,
+        },
+        data,
+    ): Args<RedeemParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %device,
+        %device_owner,
+        %location,
+        %campaign,
+        message = message,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let token_owner = data.account.0;
+    let token_program = state.solana.get_token_program(&mint).await?;
+
+    // Include the sponsorship accounts so that the sponsor pays for the redemption.
+    let sponsorship = match data.sponsor.map(|address| address.0) {
+        Some(sponsor) => {
+            fetch_sponsorship_accounts(&state.solana, &campaign, &sponsor, &mint, &location).await?
+        }
+        None => None,
+    };
+
+    let instruction = redeem_promo_instruction(
+        payer,
+        device_owner,
+        device,
+        location,
+        campaign,
+        token_owner,
+        mint,
+        state.platform,
+        token_program,
+        sponsorship,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+
+    // platform_signer signs as payer and also as device_owner if device_owner is platform_signer.
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = RedeemPromoData)]
+pub struct Data {
+    pub account: Address,
+    /// Sponsor paying the burn fee and merchant reimbursement, if the promo is sponsored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<Address>,
+}
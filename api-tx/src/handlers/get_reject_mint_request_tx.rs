@@ -0,0 +1,97 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, MintRequestParams},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata::{state::MintRequest, utils::find_mint_request_address};
+use bpl_token_metadata_client::{
+    accounts::fetch_existing_account, instructions::reject_mint_request_instruction,
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction rejecting a mint request.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/reject",
+    params(MintRequestParams),
+    request_body = RejectMintRequestData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        MintRequestParams {
+            mint: Address(mint),
+            device: Address(device),
+            location: Address(location),
+            campaign: Address(campaign),
+            token_owner: Address(token_owner),
+            message,
+            memo,
+        },
+        data,
+    ): Args<MintRequestParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %device,
+        %location,
+        %campaign,
+        %token_owner,
+        message = message,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let device_owner = data.account.0;
+    let token_program = state.solana.get_token_program(&mint).await?;
+
+    // Rent for the request goes back to whoever paid for it.
+    let mint_request: MintRequest = fetch_existing_account(
+        &state.solana,
+        &find_mint_request_address(&mint, &token_owner).0,
+    )
+    .await?;
+
+    let instruction = reject_mint_request_instruction(
+        device_owner,
+        device,
+        location,
+        campaign,
+        token_owner,
+        mint,
+        mint_request.payer,
+        token_program,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+
+    // platform_signer signs as payer and also as device_owner if device_owner is platform_signer.
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+/// `account` is the device owner rejecting the request.
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = RejectMintRequestData)]
+pub struct Data {
+    pub account: Address,
+}
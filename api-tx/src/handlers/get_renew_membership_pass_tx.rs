@@ -0,0 +1,57 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Params, RenewMembershipParams},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata_client::instructions::renew_membership_pass_instruction;
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+
+/// Returns a transaction extending a membership pass by a number of periods.
+#[utoipa::path(
+    post,
+    path = "/v2/membership/renew",
+    params(RenewMembershipParams),
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "membership"
+)]
+pub async fn handler(
+    Params(RenewMembershipParams {
+        owner: Address(owner),
+        mint: Address(mint),
+        pass_mint: Address(pass_mint),
+        periods,
+        memo,
+    }): Params<RenewMembershipParams>,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %owner,
+        %mint,
+        %pass_mint,
+        periods = periods,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+
+    let instruction =
+        renew_membership_pass_instruction(payer, owner, mint, pass_mint, periods, memo);
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message: "Renew membership pass".to_string(),
+    }))
+}
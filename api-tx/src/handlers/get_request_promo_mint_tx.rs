@@ -0,0 +1,69 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, RequestMintParams},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata_client::instructions::request_promo_mint_instruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction requesting a promo token that needs merchant approval to be minted.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/request",
+    params(RequestMintParams),
+    request_body = RequestPromoMintData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        RequestMintParams {
+            mint: Address(mint),
+            campaign: Address(campaign),
+            message,
+            memo,
+        },
+        data,
+    ): Args<RequestMintParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %campaign,
+        message = message,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let token_owner = data.account.0;
+    let token_program = state.solana.get_token_program(&mint).await?;
+
+    let instruction =
+        request_promo_mint_instruction(payer, token_owner, campaign, mint, token_program, memo);
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = RequestPromoMintData)]
+pub struct Data {
+    pub account: Address,
+}
@@ -0,0 +1,89 @@
+use axum::{extract::Extension, Json};
+use bpl_token_metadata::state::DailyLimits;
+use bpl_token_metadata_client::instructions::set_campaign_location_limits_instruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, CampaignLocationLimitsParams},
+    State,
+};
+
+use super::PayResponse;
+
+/// Returns a transaction setting the daily limits of a campaign location.
+#[utoipa::path(
+    post,
+    path = "/v2/campaign/limits",
+    params(CampaignLocationLimitsParams),
+    request_body = CampaignLocationLimitsData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "campaign"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        CampaignLocationLimitsParams {
+            owner: Address(owner),
+            campaign: Address(campaign),
+            location: Address(location),
+            memo,
+        },
+        data,
+    ): Args<CampaignLocationLimitsParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %owner,
+        %campaign,
+        %location,
+        memo = memo,
+        data = format!("{:?}", data)
+    );
+
+    let payer = state.platform_signer.pubkey();
+
+    let instruction = set_campaign_location_limits_instruction(
+        payer,
+        owner,
+        campaign,
+        location,
+        DailyLimits {
+            max_mint: data.location_max_mint,
+            max_burn: data.location_max_burn,
+        },
+        DailyLimits {
+            max_mint: data.device_max_mint,
+            max_burn: data.device_max_burn,
+        },
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message: "Set campaign location limits".to_string(),
+    }))
+}
+
+/// Daily limits, any of which can be omitted for no limit.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = CampaignLocationLimitsData)]
+pub struct Data {
+    pub location_max_mint: Option<u32>,
+    pub location_max_burn: Option<u32>,
+    pub device_max_mint: Option<u32>,
+    pub device_max_burn: Option<u32>,
+}
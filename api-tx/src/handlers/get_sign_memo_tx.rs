@@ -1,24 +1,35 @@
-use anchor_lang::prelude::Pubkey;
-use axum::{
-    extract::{Extension, Path},
-    Json,
-};
+use axum::{extract::Extension, Json};
+use bpl_token_metadata_client::instructions::create_sign_memo_instruction;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{signer::Signer, transaction::Transaction};
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
+use utoipa::ToSchema;
 
 use crate::{
-    error::AppError, handlers::SignMemoParams, utils::solana::create_sign_memo_instruction, State,
+    error::AppError,
+    handlers::{Address, Args, SignMemoParams},
+    State,
 };
 
 use super::PayResponse;
 
+/// Returns a transaction with a memo signed by the account.
+#[utoipa::path(
+    post,
+    path = "/v2/signmemo",
+    params(SignMemoParams),
+    request_body = SignMemoData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "memo"
+)]
 pub async fn handler(
-    Path(SignMemoParams { message, memo }): Path<SignMemoParams>,
     Extension(state): Extension<Arc<State>>,
-    Json(data): Json<Data>,
+    Args(SignMemoParams { message, memo }, data): Args<SignMemoParams, Data>,
 ) -> Result<Json<PayResponse>, AppError> {
-    let signer = Pubkey::from_str(&data.account)?;
+    let signer = data.account.0;
     let payer = state.platform_signer.pubkey();
 
     tracing::debug!(
@@ -26,7 +37,7 @@ pub async fn handler(
         payer = payer.to_string(),
         memo = memo
     );
-    let instruction = create_sign_memo_instruction(payer, memo, signer)?;
+    let instruction = create_sign_memo_instruction(payer, memo, signer);
 
     let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
     let latest_blockhash = state.solana.get_latest_blockhash().await?;
@@ -42,7 +53,8 @@ pub async fn handler(
     }))
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = SignMemoData)]
 pub struct Data {
-    pub account: String,
+    pub account: Address,
 }
@@ -0,0 +1,86 @@
+use super::PayResponse;
+use crate::{
+    error::AppError,
+    handlers::{Address, Args, StampParams},
+    State,
+};
+use axum::{Extension, Json};
+use bpl_token_metadata_client::instructions::stamp_promo_card_instruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns a transaction adding a stamp to the account's stamp card for a promo.
+#[utoipa::path(
+    post,
+    path = "/v2/promo/stamp",
+    params(StampParams),
+    request_body = StampPromoData,
+    responses(
+        (status = 200, body = PayResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "promo"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Args(
+        StampParams {
+            mint: Address(mint),
+            device: Address(device),
+            device_owner: Address(device_owner),
+            location: Address(location),
+            campaign: Address(campaign),
+            message,
+            memo,
+        },
+        data,
+    ): Args<StampParams, Data>,
+) -> Result<Json<PayResponse>, AppError> {
+    tracing::debug!(
+        %mint,
+        %device,
+        %device_owner,
+        %location,
+        %campaign,
+        message = message,
+        memo = memo
+    );
+
+    let payer = state.platform_signer.pubkey();
+    let token_owner = data.account.0;
+    let token_program = state.solana.get_token_program(&mint).await?;
+
+    let instruction = stamp_promo_card_instruction(
+        payer,
+        device_owner,
+        device,
+        location,
+        campaign,
+        token_owner,
+        mint,
+        token_program,
+        memo,
+    );
+
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+
+    // platform_signer signs as payer and also as device_owner if device_owner is platform_signer.
+    tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+
+    let serialized = bincode::serialize(&tx)?;
+    let transaction = base64::encode(serialized);
+
+    Ok(Json(PayResponse {
+        transaction,
+        message,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = StampPromoData)]
+pub struct Data {
+    pub account: Address,
+}
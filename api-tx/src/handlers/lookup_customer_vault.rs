@@ -0,0 +1,53 @@
+use crate::{error::AppError, utils::customer_vault::identity_hash, State};
+use axum::{Extension, Json};
+use bpl_token_metadata::utils::find_customer_vault_address;
+use bpl_token_metadata_client::accounts::fetch_customer_vault;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Returns the vault address for a phone number or email and sends a one time password to it,
+/// which the customer includes when claiming the vault's tokens into their wallet.
+#[utoipa::path(
+    post,
+    path = "/v2/vault/lookup",
+    request_body = LookupCustomerVaultData,
+    responses(
+        (status = 200, body = VaultResponse),
+        (status = 400, body = ErrorResponse),
+    ),
+    tag = "vault"
+)]
+pub async fn handler(
+    Extension(state): Extension<Arc<State>>,
+    Json(data): Json<Data>,
+) -> Result<Json<VaultResponse>, AppError> {
+    let identity_hash = identity_hash(&state.vault_salt, &data.identity)?;
+    let vault = find_customer_vault_address(&identity_hash).0;
+    let exists = fetch_customer_vault(&state.solana, &identity_hash)
+        .await?
+        .is_some();
+    tracing::debug!(vault = vault.to_string(), exists = exists);
+
+    if exists {
+        state.otp.send_code(&data.identity).await?;
+    }
+
+    Ok(Json(VaultResponse {
+        vault: vault.to_string(),
+        exists,
+    }))
+}
+
+/// `identity` is the customer's phone number or email.
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[schema(as = LookupCustomerVaultData)]
+pub struct Data {
+    pub identity: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct VaultResponse {
+    pub vault: String,
+    pub exists: bool,
+}
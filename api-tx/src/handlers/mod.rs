@@ -1,98 +1,561 @@
-use serde::{Deserialize, Serialize};
+use crate::error::AppError;
+use anchor_lang::prelude::Pubkey;
+use async_trait::async_trait;
+use axum::{
+    body::{Bytes, HttpBody},
+    extract::{rejection::PathRejection, FromRequest, FromRequestParts, Path, Query},
+    http::{request::Parts, Request},
+    BoxError,
+};
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::{Map, Value};
+use std::{fmt, str::FromStr};
+use utoipa::{
+    openapi::{ObjectBuilder, RefOr, Schema, SchemaType},
+    IntoParams, ToSchema,
+};
 
+pub mod create_allowlist;
+pub mod create_metadata_snapshot;
+pub mod create_vouchers;
 pub mod get_app_id;
+pub mod get_approve_mint_request_tx;
 pub mod get_burn_delegated_promo_tx;
+pub mod get_check_in_tx;
+pub mod get_claim_customer_vault_tx;
+pub mod get_claim_voucher_tx;
+pub mod get_clawback_promo_tx;
+pub mod get_close_sponsorship_tx;
 pub mod get_create_campaign_tx;
 pub mod get_create_device_tx;
 pub mod get_create_location_tx;
+pub mod get_create_membership_tx;
 pub mod get_create_merchant_tx;
 pub mod get_create_promo_tx;
+pub mod get_create_sponsorship_tx;
 pub mod get_delegate_promo_tx;
+pub mod get_gift_promo_tx;
+pub mod get_issue_membership_pass_tx;
+pub mod get_join_campaign_tx;
+pub mod get_mint_promo_to_vault_tx;
 pub mod get_mint_promo_tx;
+pub mod get_openapi;
+pub mod get_promo_metadata;
+pub mod get_redeem_promo_tx;
+pub mod get_reject_mint_request_tx;
+pub mod get_renew_membership_pass_tx;
+pub mod get_request_promo_mint_tx;
+pub mod get_set_campaign_location_limits_tx;
 pub mod get_sign_memo_tx;
+pub mod get_stamp_promo_tx;
+pub mod lookup_customer_vault;
 
-#[derive(Deserialize, Debug)]
+/// Arguments of a request, from the path of v1 routes and the query string of v2 routes. Both
+/// are merged, with the path taking precedence, so optional arguments such as `memo` can also
+/// be passed as queries to v1 routes. Lists such as `locations` are separated by `/` in paths
+/// and `,` in queries.
+#[derive(Debug)]
+pub struct Params<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Params<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let (path, query) = request_params(parts, state).await?;
+        Ok(Params(merge_params(state, path, vec![], query).await?))
+    }
+}
+
+/// Arguments of a request together with its json body. Arguments can also be sent as fields of
+/// the body, so v2 requests can keep `memo`, `message` and other arguments out of the url. The
+/// path takes precedence over the body, which takes precedence over the query string. Lists can
+/// be sent as arrays in the body.
+#[derive(Debug)]
+pub struct Args<T, D>(pub T, pub D);
+
+#[async_trait]
+impl<T, D, S, B> FromRequest<S, B> for Args<T, D>
+where
+    T: DeserializeOwned + Send,
+    D: DeserializeOwned + Send,
+    S: Send + Sync,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        let (mut parts, body) = req.into_parts();
+        let (path, query) = request_params(&mut parts, state).await?;
+        let bytes = Bytes::from_request(Request::from_parts(parts, body), state)
+            .await
+            .map_err(|rejection| AppError::ParamsError(rejection.body_text()))?;
+        let body: Value = if bytes.is_empty() {
+            Value::Object(Map::new())
+        } else {
+            serde_json::from_slice(&bytes)
+                .map_err(|error| AppError::ParamsError(error.to_string()))?
+        };
+
+        let body_params = body
+            .as_object()
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), param_value(value)?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let params = merge_params(state, path, body_params, query).await?;
+        let data =
+            D::deserialize(body).map_err(|error| AppError::ParamsError(error.to_string()))?;
+
+        Ok(Args(params, data))
+    }
+}
+
+// Body fields as they would be written in a query string, with arrays joined by commas. Objects
+// and nulls aren't arguments.
+fn param_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Array(values) => Some(
+            values
+                .iter()
+                .map(param_value)
+                .collect::<Option<Vec<_>>>()?
+                .join(","),
+        ),
+        Value::Object(_) | Value::Null => None,
+    }
+}
+
+type Pairs = Vec<(String, String)>;
+
+async fn request_params<S: Send + Sync>(
+    parts: &mut Parts,
+    state: &S,
+) -> Result<(Pairs, Pairs), AppError> {
+    let path = match Path::<Vec<(String, String)>>::from_request_parts(parts, state).await {
+        Ok(Path(path)) => path,
+        Err(PathRejection::MissingPathParams(_)) => vec![],
+        Err(rejection) => return Err(AppError::ParamsError(rejection.body_text())),
+    };
+    let Query(query) = Query::<Vec<(String, String)>>::from_request_parts(parts, state)
+        .await
+        .map_err(|rejection| AppError::ParamsError(rejection.body_text()))?;
+
+    Ok((path, query))
+}
+
+async fn merge_params<T, S>(
+    state: &S,
+    path: Pairs,
+    body: Pairs,
+    query: Pairs,
+) -> Result<T, AppError>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    let merged_query = {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        let mut keys = vec![];
+        for (key, value) in path.iter().chain(body.iter()).chain(query.iter()) {
+            if !keys.contains(&key) {
+                serializer.append_pair(key, value);
+                keys.push(key);
+            }
+        }
+        serializer.finish()
+    };
+
+    let (mut merged, _) = Request::builder()
+        .uri(format!("/?{merged_query}"))
+        .body(())
+        .map_err(|error| AppError::ParamsError(error.to_string()))?
+        .into_parts();
+    let Query(params) = Query::<T>::from_request_parts(&mut merged, state)
+        .await
+        .map_err(|rejection| AppError::ParamsError(rejection.body_text()))?;
+
+    Ok(params)
+}
+
+/// Splits a list of addresses from a path wildcard or a comma separated query.
+pub fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(|c| c == '/' || c == ',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Base58 encoded address. Arguments and body fields that are addresses are deserialized as
+/// this, so invalid addresses are rejected when the request is extracted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Address(pub Pubkey);
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let address = String::deserialize(deserializer)?;
+        Pubkey::from_str(&address)
+            .map(Address)
+            .map_err(|_| de::Error::custom(format!("invalid address: {address}")))
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<Pubkey> for Address {
+    fn from(pubkey: Pubkey) -> Self {
+        Address(pubkey)
+    }
+}
+
+impl<'s> ToSchema<'s> for Address {
+    fn schema() -> (&'s str, RefOr<Schema>) {
+        (
+            "Address",
+            ObjectBuilder::new()
+                .schema_type(SchemaType::String)
+                .description(Some("Base58 encoded address"))
+                .into(),
+        )
+    }
+}
+
+/// List of addresses, separated by `/` in path wildcards and `,` in queries, or an array in
+/// json bodies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressList(pub Vec<Pubkey>);
+
+impl<'de> Deserialize<'de> for AddressList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let list = String::deserialize(deserializer)?;
+        split_list(&list)
+            .map(|address| {
+                Pubkey::from_str(address)
+                    .map_err(|_| de::Error::custom(format!("invalid address: {address}")))
+            })
+            .collect::<Result<_, _>>()
+            .map(AddressList)
+    }
+}
+
+impl fmt::Display for AddressList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        f.write_str(&list.join(","))
+    }
+}
+
+impl<'s> ToSchema<'s> for AddressList {
+    fn schema() -> (&'s str, RefOr<Schema>) {
+        (
+            "AddressList",
+            ObjectBuilder::new()
+                .schema_type(SchemaType::String)
+                .description(Some("Base58 encoded addresses separated by commas"))
+                .into(),
+        )
+    }
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct MintParams {
-    pub mint: String,
-    pub device: String,
-    pub device_owner: String,
-    pub location: String,
-    pub campaign: String,
+    pub mint: Address,
+    pub device: Address,
+    pub device_owner: Address,
+    pub location: Address,
+    pub campaign: Address,
+    pub message: String,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StampParams {
+    pub mint: Address,
+    pub device: Address,
+    pub device_owner: Address,
+    pub location: Address,
+    pub campaign: Address,
+    pub message: String,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VoucherParams {
+    pub mint: Address,
+    pub device: Address,
+    pub device_owner: Address,
+    pub location: Address,
+    pub campaign: Address,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ClaimVoucherParams {
+    pub mint: Address,
+    pub device: Address,
+    pub device_owner: Address,
+    pub location: Address,
+    pub campaign: Address,
+    pub voucher: String,
+    pub signature: String,
     pub message: String,
     pub memo: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RequestMintParams {
+    pub mint: Address,
+    pub campaign: Address,
+    pub message: String,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ClaimVaultParams {
+    pub message: String,
+    /// Mints of the tokens to claim
+    pub mints: AddressList,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GiftParams {
+    pub mint: Address,
+    pub recipient: Address,
+    pub message: String,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MintRequestParams {
+    pub mint: Address,
+    pub device: Address,
+    pub location: Address,
+    pub campaign: Address,
+    pub token_owner: Address,
+    pub message: String,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DelegateParams {
-    pub mint: String,
-    pub device_owner: String,
-    pub device: String,
-    pub location: String,
-    pub campaign: String,
+    pub mint: Address,
+    pub device_owner: Address,
+    pub device: Address,
+    pub location: Address,
+    pub campaign: Address,
     pub message: String,
     pub memo: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct BurnDelegatedParams {
-    pub mint: String,
-    pub token_account: String,
-    pub device: String,
-    pub location: String,
-    pub campaign: String,
+    pub mint: Address,
+    pub token_account: Address,
+    pub device: Address,
+    pub location: Address,
+    pub campaign: Address,
+    pub message: String,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RedeemParams {
+    pub mint: Address,
+    pub device: Address,
+    pub device_owner: Address,
+    pub location: Address,
+    pub campaign: Address,
     pub message: String,
     pub memo: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ClawbackParams {
+    pub owner: Address,
+    pub campaign: Address,
+    pub mint: Address,
+    pub token_account: Address,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PromoParams {
-    pub owner: String,
-    pub campaign: String,
+    pub owner: Address,
+    pub campaign: Address,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MembershipParams {
+    pub owner: Address,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IssueMembershipParams {
+    pub owner: Address,
+    pub mint: Address,
+    pub token_owner: Address,
     pub memo: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RenewMembershipParams {
+    pub owner: Address,
+    pub mint: Address,
+    pub pass_mint: Address,
+    pub periods: u16,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CheckInParams {
+    pub mint: Address,
+    pub pass_mint: Address,
+    pub device: Address,
+    pub device_owner: Address,
+    pub location: Address,
+    pub message: String,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MetadataParams {
+    pub template: String,
+    pub mint: Address,
+    pub owner: Option<Address>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SignMemoParams {
     pub memo: String,
     pub message: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct BasicParams {
-    pub payer: String,
+    pub payer: Address,
     pub memo: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct MerchantParams {
-    pub owner: String,
+    pub owner: Address,
     pub memo: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LocationParams {
-    pub owner: String,
+    pub owner: Address,
     pub memo: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DeviceParams {
-    pub merchant_owner: String,
-    pub location: String,
-    pub owner: String,
+    pub merchant_owner: Address,
+    pub location: Address,
+    pub owner: Address,
     pub memo: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CampaignParams {
-    pub owner: String,
+    pub owner: Address,
     pub lamports: u64,
     pub memo: Option<String>,
-    pub locations: String,
+    /// Locations to add to the campaign
+    pub locations: AddressList,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JoinCampaignParams {
+    pub campaign_owner: Address,
+    pub campaign: Address,
+    pub owner: Address,
+    /// Locations to add to the campaign
+    pub locations: AddressList,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SponsorshipParams {
+    pub sponsor: Address,
+    pub campaign: Address,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CampaignLocationLimitsParams {
+    pub owner: Address,
+    pub campaign: Address,
+    pub location: Address,
+    pub memo: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub struct PayResponse {
     pub transaction: String,
     pub message: String,
 }
+
+/// Transactions that are too big to be sent as one, to be signed and submitted in `step`
+/// order.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct PayStepsResponse {
+    pub transactions: Vec<StepTransaction>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct StepTransaction {
+    pub step: usize,
+    pub transaction: String,
+}
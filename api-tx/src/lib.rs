@@ -2,7 +2,7 @@ use axum::{
     error_handling::HandleErrorLayer,
     http::{header, Method, StatusCode},
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
     Router,
 };
 use base58::ToBase58;
@@ -17,7 +17,11 @@ use tower_http::{
     trace::TraceLayer,
 };
 use url::Url;
-use utils::solana::{Cluster, Solana};
+use utils::{
+    customer_vault::OtpProvider,
+    metadata::TemplateCache,
+    solana::{Cluster, Solana},
+};
 
 pub mod error;
 pub mod handlers;
@@ -43,6 +47,31 @@ pub struct State {
     pub solana: Solana,
     pub bundlr: bundlr_sdk::Bundlr<bundlr_sdk::currency::solana::Solana>,
     pub data_url: Url,
+    pub otp: Arc<dyn OtpProvider>,
+    // Salt for customer vault identity hashes.
+    pub vault_salt: String,
+    // Base url dynamic promo metadata uris point at.
+    pub metadata_url: String,
+    // Public base url of this api that voucher claim urls point at.
+    pub app_url: String,
+    // Metadata templates fetched from Arweave.
+    pub templates: TemplateCache,
+}
+
+/// Settings for customer vaults and the urls this api builds, read from the command line or
+/// environment by main.
+pub struct Config {
+    /// Sends one time passwords to customers with vaults. [LocalOtpProvider] never delivers
+    /// them, so it is only for development and tests.
+    ///
+    /// [LocalOtpProvider]: utils::customer_vault::LocalOtpProvider
+    pub otp: Arc<dyn OtpProvider>,
+    /// Salt for customer vault identity hashes. Shouldn't change once vaults have been created.
+    pub vault_salt: String,
+    /// Base url dynamic promo metadata uris point at.
+    pub metadata_url: String,
+    /// Public base url of this api that voucher claim urls point at.
+    pub app_url: String,
 }
 
 impl State {
@@ -53,6 +82,7 @@ impl State {
         platform_signer: Keypair,
         data_url: Url,
         bundlr_pub_info: bundlr_sdk::bundlr::PubInfo,
+        config: Config,
     ) -> Self {
         let currency = bundlr_sdk::currency::solana::SolanaBuilder::new()
             .wallet(&platform_signer.to_base58_string())
@@ -82,6 +112,11 @@ impl State {
             },
             bundlr,
             data_url,
+            otp: config.otp,
+            vault_salt: config.vault_salt,
+            metadata_url: config.metadata_url,
+            app_url: config.app_url,
+            templates: TemplateCache::default(),
         }
     }
 }
@@ -92,6 +127,7 @@ pub fn create_app(
     platform_signer: Keypair,
     data_url: Url,
     bundlr_pub_info: bundlr_sdk::bundlr::PubInfo,
+    config: Config,
 ) -> Router {
     let cors = CorsLayer::new()
         .allow_methods([Method::GET, Method::POST])
@@ -107,6 +143,54 @@ pub fn create_app(
             "/promo/mint/:mint/:device/:device_owner/:location/:campaign/:message/:memo",
             get(get_app_id::handler).post(get_mint_promo_tx::handler),
         )
+        .route(
+            "/promo/stamp/:mint/:device/:device_owner/:location/:campaign/:message",
+            get(get_app_id::handler).post(get_stamp_promo_tx::handler),
+        )
+        .route(
+            "/promo/stamp/:mint/:device/:device_owner/:location/:campaign/:message/:memo",
+            get(get_app_id::handler).post(get_stamp_promo_tx::handler),
+        )
+        .route(
+            "/promo/claim/:mint/:device/:device_owner/:location/:campaign/:voucher/:signature/:message",
+            get(get_app_id::handler).post(get_claim_voucher_tx::handler),
+        )
+        .route(
+            "/promo/claim/:mint/:device/:device_owner/:location/:campaign/:voucher/:signature/:message/:memo",
+            get(get_app_id::handler).post(get_claim_voucher_tx::handler),
+        )
+        .route(
+            "/promo/request/:mint/:campaign/:message",
+            get(get_app_id::handler).post(get_request_promo_mint_tx::handler),
+        )
+        .route(
+            "/promo/request/:mint/:campaign/:message/:memo",
+            get(get_app_id::handler).post(get_request_promo_mint_tx::handler),
+        )
+        .route(
+            "/promo/approve/:mint/:device/:location/:campaign/:token_owner/:message",
+            get(get_app_id::handler).post(get_approve_mint_request_tx::handler),
+        )
+        .route(
+            "/promo/approve/:mint/:device/:location/:campaign/:token_owner/:message/:memo",
+            get(get_app_id::handler).post(get_approve_mint_request_tx::handler),
+        )
+        .route(
+            "/promo/reject/:mint/:device/:location/:campaign/:token_owner/:message",
+            get(get_app_id::handler).post(get_reject_mint_request_tx::handler),
+        )
+        .route(
+            "/promo/reject/:mint/:device/:location/:campaign/:token_owner/:message/:memo",
+            get(get_app_id::handler).post(get_reject_mint_request_tx::handler),
+        )
+        .route(
+            "/promo/gift/:mint/:recipient/:message",
+            get(get_app_id::handler).post(get_gift_promo_tx::handler),
+        )
+        .route(
+            "/promo/gift/:mint/:recipient/:message/:memo",
+            get(get_app_id::handler).post(get_gift_promo_tx::handler),
+        )
         .route(
             "/promo/delegate/:mint/:device_owner/:device/:location/:campaign/:message",
             get(get_app_id::handler).post(get_delegate_promo_tx::handler),
@@ -123,6 +207,18 @@ pub fn create_app(
             "/promo/burn-delegated/:mint/:token_account/:device/:location/:campaign/:message/:memo",
             get(get_app_id::handler).post(get_burn_delegated_promo_tx::handler),
         )
+        .route(
+            "/promo/redeem/:mint/:device/:device_owner/:location/:campaign/:message",
+            get(get_app_id::handler).post(get_redeem_promo_tx::handler),
+        )
+        .route(
+            "/promo/redeem/:mint/:device/:device_owner/:location/:campaign/:message/:memo",
+            get(get_app_id::handler).post(get_redeem_promo_tx::handler),
+        )
+        .route(
+            "/promo/clawback/:owner/:campaign/:mint/:token_account",
+            get(get_app_id::handler).post(get_clawback_promo_tx::handler),
+        )
         .route(
             "/promo/create/:owner/:campaign",
             get(get_app_id::handler).post(get_create_promo_tx::handler),
@@ -131,6 +227,40 @@ pub fn create_app(
             "/promo/create/:owner/:campaign/:memo",
             get(get_app_id::handler).post(get_create_promo_tx::handler),
         )
+        .route("/allowlist/create", post(create_allowlist::handler))
+        .route(
+            "/metadata/:template/:mint",
+            get(get_promo_metadata::handler),
+        )
+        .route(
+            "/metadata/:template/:mint/:owner",
+            get(get_promo_metadata::handler),
+        )
+        .route(
+            "/metadata/snapshot/:template/:mint",
+            post(create_metadata_snapshot::handler),
+        )
+        .route(
+            "/metadata/snapshot/:template/:mint/:owner",
+            post(create_metadata_snapshot::handler),
+        )
+        .route(
+            "/voucher/create/:mint/:device/:device_owner/:location/:campaign",
+            post(create_vouchers::handler),
+        )
+        .route(
+            "/vault/mint/:mint/:device/:device_owner/:location/:campaign/:message",
+            post(get_mint_promo_to_vault_tx::handler),
+        )
+        .route(
+            "/vault/mint/:mint/:device/:device_owner/:location/:campaign/:message/:memo",
+            post(get_mint_promo_to_vault_tx::handler),
+        )
+        .route("/vault/lookup", post(lookup_customer_vault::handler))
+        .route(
+            "/vault/claim/:message/*mints",
+            post(get_claim_customer_vault_tx::handler),
+        )
         .route(
             "/signmemo/:message/:memo",
             get(get_app_id::handler).post(get_sign_memo_tx::handler),
@@ -163,6 +293,69 @@ pub fn create_app(
             "/campaign/create/:owner/:lamports/:memo/*locations",
             get(get_app_id::handler).post(get_create_campaign_tx::handler),
         )
+        .route(
+            "/campaign/join/:campaign_owner/:campaign/:owner/*locations",
+            get(get_app_id::handler).post(get_join_campaign_tx::handler),
+        )
+        .route(
+            "/campaign/sponsor/:sponsor/:campaign",
+            get(get_app_id::handler).post(get_create_sponsorship_tx::handler),
+        )
+        .route(
+            "/campaign/sponsor/:sponsor/:campaign/:memo",
+            get(get_app_id::handler).post(get_create_sponsorship_tx::handler),
+        )
+        .route(
+            "/campaign/sponsor/close/:sponsor/:campaign",
+            get(get_app_id::handler).post(get_close_sponsorship_tx::handler),
+        )
+        .route(
+            "/campaign/sponsor/close/:sponsor/:campaign/:memo",
+            get(get_app_id::handler).post(get_close_sponsorship_tx::handler),
+        )
+        .route(
+            "/campaign/limits/:owner/:campaign/:location",
+            get(get_app_id::handler).post(get_set_campaign_location_limits_tx::handler),
+        )
+        .route(
+            "/campaign/limits/:owner/:campaign/:location/:memo",
+            get(get_app_id::handler).post(get_set_campaign_location_limits_tx::handler),
+        )
+        .route(
+            "/membership/create/:owner",
+            get(get_app_id::handler).post(get_create_membership_tx::handler),
+        )
+        .route(
+            "/membership/create/:owner/:memo",
+            get(get_app_id::handler).post(get_create_membership_tx::handler),
+        )
+        .route(
+            "/membership/issue/:owner/:mint/:token_owner",
+            get(get_app_id::handler).post(get_issue_membership_pass_tx::handler),
+        )
+        .route(
+            "/membership/issue/:owner/:mint/:token_owner/:memo",
+            get(get_app_id::handler).post(get_issue_membership_pass_tx::handler),
+        )
+        .route(
+            "/membership/renew/:owner/:mint/:pass_mint/:periods",
+            get(get_app_id::handler).post(get_renew_membership_pass_tx::handler),
+        )
+        .route(
+            "/membership/renew/:owner/:mint/:pass_mint/:periods/:memo",
+            get(get_app_id::handler).post(get_renew_membership_pass_tx::handler),
+        )
+        .route(
+            "/membership/checkin/:mint/:pass_mint/:device/:device_owner/:location/:message",
+            get(get_app_id::handler).post(get_check_in_tx::handler),
+        )
+        .route(
+            "/membership/checkin/:mint/:pass_mint/:device/:device_owner/:location/:message/:memo",
+            get(get_app_id::handler).post(get_check_in_tx::handler),
+        )
+        .nest("/v2", create_v2_router())
+        .route("/openapi.json", get(get_openapi::handler))
+        .route("/docs", get(get_openapi::docs_handler))
         .layer(
             ServiceBuilder::new()
                 .layer(cors)
@@ -178,11 +371,129 @@ pub fn create_app(
                     platform_signer,
                     data_url,
                     bundlr_pub_info,
+                    config,
                 ))))
                 .into_inner(),
         )
 }
 
+/// Routes taking their arguments as query strings rather than path segments, e.g.
+/// `/v2/campaign/join?campaign_owner=...&campaign=...&owner=...&locations=a,b`. They share
+/// handlers with the v1 routes, and request bodies are the same.
+fn create_v2_router() -> Router {
+    Router::new()
+        .route(
+            "/promo/mint",
+            get(get_app_id::handler).post(get_mint_promo_tx::handler),
+        )
+        .route(
+            "/promo/stamp",
+            get(get_app_id::handler).post(get_stamp_promo_tx::handler),
+        )
+        .route(
+            "/promo/claim",
+            get(get_app_id::handler).post(get_claim_voucher_tx::handler),
+        )
+        .route(
+            "/promo/request",
+            get(get_app_id::handler).post(get_request_promo_mint_tx::handler),
+        )
+        .route(
+            "/promo/approve",
+            get(get_app_id::handler).post(get_approve_mint_request_tx::handler),
+        )
+        .route(
+            "/promo/reject",
+            get(get_app_id::handler).post(get_reject_mint_request_tx::handler),
+        )
+        .route(
+            "/promo/gift",
+            get(get_app_id::handler).post(get_gift_promo_tx::handler),
+        )
+        .route(
+            "/promo/delegate",
+            get(get_app_id::handler).post(get_delegate_promo_tx::handler),
+        )
+        .route(
+            "/promo/burn-delegated",
+            get(get_app_id::handler).post(get_burn_delegated_promo_tx::handler),
+        )
+        .route(
+            "/promo/redeem",
+            get(get_app_id::handler).post(get_redeem_promo_tx::handler),
+        )
+        .route(
+            "/promo/clawback",
+            get(get_app_id::handler).post(get_clawback_promo_tx::handler),
+        )
+        .route(
+            "/promo/create",
+            get(get_app_id::handler).post(get_create_promo_tx::handler),
+        )
+        .route("/allowlist/create", post(create_allowlist::handler))
+        .route("/metadata", get(get_promo_metadata::handler))
+        .route(
+            "/metadata/snapshot",
+            post(create_metadata_snapshot::handler),
+        )
+        .route("/voucher/create", post(create_vouchers::handler))
+        .route("/vault/mint", post(get_mint_promo_to_vault_tx::handler))
+        .route("/vault/lookup", post(lookup_customer_vault::handler))
+        .route("/vault/claim", post(get_claim_customer_vault_tx::handler))
+        .route(
+            "/signmemo",
+            get(get_app_id::handler).post(get_sign_memo_tx::handler),
+        )
+        .route(
+            "/merchant/create",
+            get(get_app_id::handler).post(get_create_merchant_tx::handler),
+        )
+        .route(
+            "/location/create",
+            get(get_app_id::handler).post(get_create_location_tx::handler),
+        )
+        .route(
+            "/device/create",
+            get(get_app_id::handler).post(get_create_device_tx::handler),
+        )
+        .route(
+            "/campaign/create",
+            get(get_app_id::handler).post(get_create_campaign_tx::handler),
+        )
+        .route(
+            "/campaign/join",
+            get(get_app_id::handler).post(get_join_campaign_tx::handler),
+        )
+        .route(
+            "/campaign/sponsor",
+            get(get_app_id::handler).post(get_create_sponsorship_tx::handler),
+        )
+        .route(
+            "/campaign/sponsor/close",
+            get(get_app_id::handler).post(get_close_sponsorship_tx::handler),
+        )
+        .route(
+            "/campaign/limits",
+            get(get_app_id::handler).post(get_set_campaign_location_limits_tx::handler),
+        )
+        .route(
+            "/membership/create",
+            get(get_app_id::handler).post(get_create_membership_tx::handler),
+        )
+        .route(
+            "/membership/issue",
+            get(get_app_id::handler).post(get_issue_membership_pass_tx::handler),
+        )
+        .route(
+            "/membership/renew",
+            get(get_app_id::handler).post(get_renew_membership_pass_tx::handler),
+        )
+        .route(
+            "/membership/checkin",
+            get(get_app_id::handler).post(get_check_in_tx::handler),
+        )
+}
+
 async fn handle_error(error: BoxError) -> impl IntoResponse {
     if error.is::<tower::timeout::error::Elapsed>() {
         return (StatusCode::REQUEST_TIMEOUT, Cow::from("request timed out"));
@@ -208,11 +519,19 @@ pub mod test {
     use anchor_lang::{prelude::Pubkey, AnchorDeserialize};
     use axum::{
         body::Body,
+        extract::{FromRequest, FromRequestParts},
         http::{Method, Request, StatusCode},
     };
-    use bpl_token_metadata::utils::{find_campaign_address, find_location_address};
+    use bpl_token_metadata::{
+        state::{GiftPolicy, PromoTerms},
+        utils::{
+            allowlist_leaf, find_campaign_address, find_location_address, verify_allowlist_proof,
+        },
+    };
+    use bpl_token_metadata_client::instructions::*;
     use bundlr_sdk::{bundlr::get_pub_info, consts::BUNDLR_DEFAULT_URL, currency::CurrencyType};
-    use handlers::PayResponse;
+    use error::{AppError, ErrorResponse};
+    use handlers::{Address, PayResponse};
     use solana_sdk::{signature::Signer, transaction::Transaction};
     use std::{
         net::{SocketAddr, TcpListener},
@@ -221,72 +540,1123 @@ pub mod test {
     use tokio::fs;
     use tower::ServiceExt;
     use tracing_subscriber::{fmt, prelude::*, EnvFilter};
-    use utils::solana::*;
+    use utils::{
+        allowlist::AllowlistTree,
+        customer_vault::LocalOtpProvider,
+        metadata::{check_template, get_dynamic_metadata_uri, render_metadata, PromoState},
+        multipart::{
+            get_args, get_membership_args, get_memo_args, get_promo_dynamic_args,
+            get_promo_gift_policy_args, get_promo_terms_args,
+        },
+        solana::*,
+        transactions::get_campaign_location_transactions,
+    };
+    use utoipa::{openapi::PathItemType, OpenApi};
+
+    const MESSAGE: &str = "This is a really long message that tells you to do something.";
+    const PLATFORM: &str = "2R7GkXvQQS4iHptUvQMhDvRSNXL8tAuuASNvCYgz3GQW";
+    const DATA_URL: &str = "https://shining-sailfish-15.hasura.app/v1/graphql/";
+
+    fn test_config() -> Config {
+        Config {
+            otp: Arc::new(LocalOtpProvider::default()),
+            vault_salt: "test-salt".to_string(),
+            metadata_url: "https://tx.bokoup.dev".to_string(),
+            app_url: "https://tx.bokoup.dev".to_string(),
+        }
+    }
+
+    pub async fn run_tests() {
+        dotenv::dotenv().ok();
+        std::env::set_var("RUST_LOG", "bpl_api_tx=trace");
+        tracing_subscriber::registry()
+            .with(fmt::layer())
+            .with(EnvFilter::from_default_env())
+            .try_init()
+            .unwrap_or(());
+        // dotenv::dotenv().ok();
+        // fund_accounts().await;
+        // test_create_merchant().await;
+        // test_create_location().await;
+        // test_create_device().await;
+    }
+
+    #[tokio::test]
+    async fn test_app_id_ding() {
+        run_tests().await;
+
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        dbg!(platform_signer.pubkey().to_string());
+        // ok to be devnet, only pulling blockhash - will succeed even if localnet validator not running
+        let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
+            .await
+            .unwrap();
+
+        let balance = bundlr_sdk::bundlr::get_balance(
+            &url::Url::from_str(BUNDLR_DEFAULT_URL).unwrap(),
+            CurrencyType::Solana,
+            &platform_signer.pubkey().to_string(),
+            &reqwest::Client::new(),
+        )
+        .await
+        .unwrap();
+
+        let app = create_app(
+            Cluster::Devnet,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            platform_signer,
+            Url::from_str(DATA_URL).unwrap(),
+            pub_info,
+            test_config(),
+        );
+
+        let mint = Pubkey::new_unique();
+        let message = urlencoding::encode(MESSAGE);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/promo/mint/{}/{}/{}/{}/{}/{}",
+                        mint.to_string(),
+                        mint.to_string(),
+                        mint.to_string(),
+                        mint.to_string(),
+                        mint.to_string(),
+                        message.into_owned(),
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let parsed_response: get_app_id::ResponseData = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            parsed_response,
+            get_app_id::ResponseData {
+                label: get_app_id::LABEL.to_string(),
+                icon: get_app_id::ICON.to_string(),
+            }
+        );
+    }
+
+    // Testing end user requesting mint tx where merchant has added platform signer to group members
+    // to pay for transaction fees with no further merchant approval required.
+    #[tokio::test]
+    async fn test_get_mint_promo_tx() {
+        run_tests().await;
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
+            .await
+            .unwrap();
+
+        let app = create_app(
+            Cluster::Devnet,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            platform_signer,
+            Url::from_str(DATA_URL).unwrap(),
+            pub_info,
+            test_config(),
+        );
+
+        let mint = Pubkey::new_unique();
+        let device = Pubkey::new_unique();
+        let location = Pubkey::new_unique();
+        let campaign = Pubkey::new_unique();
+        let token_owner = Pubkey::new_unique();
+        let device_owner = Pubkey::new_unique();
+
+        let data = get_mint_promo_tx::Data {
+            account: Address(token_owner),
+            referrer: None,
+        };
+        let message = urlencoding::encode(MESSAGE);
+        let memo = "jingus";
+        let memo_encoded = urlencoding::encode(memo);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!(
+                        "/promo/mint/{}/{}/{}/{}/{}/{}/{}",
+                        mint.to_string(),
+                        device.to_string(),
+                        device_owner.to_string(),
+                        location.to_string(),
+                        campaign.to_string(),
+                        message.into_owned(),
+                        memo_encoded.into_owned()
+                    ))
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&data).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let parsed_response: PayResponse = serde_json::from_slice(&body).unwrap();
+
+        let txd: Transaction = bincode::deserialize(
+            &base64::decode::<String>(parsed_response.transaction.clone()).unwrap(),
+        )
+        .unwrap();
+
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let instruction = mint_promo_instruction(
+            platform_signer.pubkey(),
+            device_owner,
+            device,
+            location,
+            campaign,
+            token_owner,
+            mint,
+            anchor_spl::token::ID,
+            vec![],
+            None,
+            Some(memo.to_string()),
+        );
+
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&platform_signer.pubkey()));
+
+        let recent_blockhash = txd.message.recent_blockhash;
+
+        tx.try_partial_sign(&[&platform_signer], recent_blockhash)
+            .unwrap();
+
+        let serialized = bincode::serialize(&tx).unwrap();
+        let transaction = base64::encode(serialized);
+
+        assert_eq!(
+            parsed_response,
+            PayResponse {
+                transaction,
+                message: MESSAGE.to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_stamp_promo_tx() {
+        run_tests().await;
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
+            .await
+            .unwrap();
+
+        let app = create_app(
+            Cluster::Devnet,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            platform_signer,
+            Url::from_str(DATA_URL).unwrap(),
+            pub_info,
+            test_config(),
+        );
+
+        let mint = Pubkey::new_unique();
+        let device = Pubkey::new_unique();
+        let location = Pubkey::new_unique();
+        let campaign = Pubkey::new_unique();
+        let token_owner = Pubkey::new_unique();
+        let device_owner = Pubkey::new_unique();
+
+        let data = get_stamp_promo_tx::Data {
+            account: Address(token_owner),
+        };
+        let message = urlencoding::encode(MESSAGE);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!(
+                        "/promo/stamp/{}/{}/{}/{}/{}/{}",
+                        mint.to_string(),
+                        device.to_string(),
+                        device_owner.to_string(),
+                        location.to_string(),
+                        campaign.to_string(),
+                        message.into_owned(),
+                    ))
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&data).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let parsed_response: PayResponse = serde_json::from_slice(&body).unwrap();
+
+        let txd: Transaction = bincode::deserialize(
+            &base64::decode::<String>(parsed_response.transaction.clone()).unwrap(),
+        )
+        .unwrap();
+
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let instruction = stamp_promo_card_instruction(
+            platform_signer.pubkey(),
+            device_owner,
+            device,
+            location,
+            campaign,
+            token_owner,
+            mint,
+            anchor_spl::token::ID,
+            None,
+        );
+
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&platform_signer.pubkey()));
+        tx.try_partial_sign(&[&platform_signer], txd.message.recent_blockhash)
+            .unwrap();
+
+        let serialized = bincode::serialize(&tx).unwrap();
+        let transaction = base64::encode(serialized);
+
+        assert_eq!(
+            parsed_response,
+            PayResponse {
+                transaction,
+                message: MESSAGE.to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_request_promo_mint_tx() {
+        run_tests().await;
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
+            .await
+            .unwrap();
+
+        let app = create_app(
+            Cluster::Devnet,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            platform_signer,
+            Url::from_str(DATA_URL).unwrap(),
+            pub_info,
+            test_config(),
+        );
+
+        let mint = Pubkey::new_unique();
+        let campaign = Pubkey::new_unique();
+        let token_owner = Pubkey::new_unique();
+
+        let data = get_request_promo_mint_tx::Data {
+            account: Address(token_owner),
+        };
+        let message = urlencoding::encode(MESSAGE);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!(
+                        "/promo/request/{}/{}/{}",
+                        mint.to_string(),
+                        campaign.to_string(),
+                        message.into_owned(),
+                    ))
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&data).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let parsed_response: PayResponse = serde_json::from_slice(&body).unwrap();
+
+        let txd: Transaction = bincode::deserialize(
+            &base64::decode::<String>(parsed_response.transaction.clone()).unwrap(),
+        )
+        .unwrap();
+
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let instruction = request_promo_mint_instruction(
+            platform_signer.pubkey(),
+            token_owner,
+            campaign,
+            mint,
+            anchor_spl::token::ID,
+            None,
+        );
+
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&platform_signer.pubkey()));
+        tx.try_partial_sign(&[&platform_signer], txd.message.recent_blockhash)
+            .unwrap();
+
+        let serialized = bincode::serialize(&tx).unwrap();
+        let transaction = base64::encode(serialized);
+
+        assert_eq!(
+            parsed_response,
+            PayResponse {
+                transaction,
+                message: MESSAGE.to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_vouchers() {
+        run_tests().await;
+        let merchant_owner =
+            parse_string_to_keypair(&std::env::var("MERCHANT_OWNER_KEYPAIR").unwrap());
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+        let device_owner = platform_signer.pubkey();
+
+        let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
+            .await
+            .unwrap();
+
+        let app = create_app(
+            Cluster::Devnet,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            platform_signer,
+            Url::from_str(DATA_URL).unwrap(),
+            pub_info,
+            test_config(),
+        );
+
+        let mint = Pubkey::new_unique();
+        let device = Pubkey::new_unique();
+        let location = Pubkey::new_unique();
+        let campaign = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let authorize = |signer: &Keypair, timestamp: i64| {
+            let mut data = create_vouchers::Data {
+                count: 3,
+                expiry: 1_900_000_000,
+                recipient: Some(Address(recipient)),
+                message: MESSAGE.to_string(),
+                authority: Address(merchant_owner.pubkey()),
+                timestamp,
+                authorization: String::new(),
+            };
+            let message = create_vouchers::authorization_message(&mint, &device, &data);
+            data.authorization = signer.sign_message(message.as_bytes()).as_ref().to_base58();
+            data
+        };
+
+        let cases = [
+            (authorize(&Keypair::new(), now), "invalid authorization"),
+            (
+                authorize(
+                    &merchant_owner,
+                    now - 2 * create_vouchers::AUTHORIZATION_TTL,
+                ),
+                "authorization expired",
+            ),
+            // Vouchers are only created for devices that exist with the given owner and location.
+            (authorize(&merchant_owner, now), "account not found"),
+        ];
+
+        for (data, error) in cases {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri(format!(
+                            "/voucher/create/{}/{}/{}/{}/{}",
+                            mint, device, device_owner, location, campaign,
+                        ))
+                        .header(header::CONTENT_TYPE, "application/json")
+                        .body(Body::from(serde_json::to_vec(&data).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let parsed_response: ErrorResponse = serde_json::from_slice(&body).unwrap();
+            assert!(
+                parsed_response.error.contains(error),
+                "{}",
+                parsed_response.error
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_allowlist() {
+        run_tests().await;
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
+            .await
+            .unwrap();
+
+        let app = create_app(
+            Cluster::Devnet,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            platform_signer,
+            Url::from_str(DATA_URL).unwrap(),
+            pub_info,
+            test_config(),
+        );
+
+        let csv = std::iter::once("wallet,segment".to_string())
+            .chain((0..5).map(|_| format!("{},vip", Pubkey::new_unique())))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/allowlist/create")
+                    .header(header::CONTENT_TYPE, "text/csv")
+                    .body(Body::from(csv.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let parsed_response: create_allowlist::AllowlistResponse =
+            serde_json::from_slice(&body).unwrap();
+
+        let tree = AllowlistTree::from_csv(&csv).unwrap();
+        assert_eq!(parsed_response.root, tree.root().to_base58());
+        assert_eq!(parsed_response.wallets, 5);
+    }
+
+    #[test]
+    fn test_allowlist_proofs() {
+        for count in 1..=9 {
+            let wallets: Vec<Pubkey> = (0..count).map(|_| Pubkey::new_unique()).collect();
+            let tree = AllowlistTree::new(wallets.clone()).unwrap();
+
+            for wallet in &wallets {
+                let proof = tree.proof(wallet).unwrap();
+                assert!(verify_allowlist_proof(
+                    &proof,
+                    &tree.root(),
+                    allowlist_leaf(wallet)
+                ));
+                assert!(!verify_allowlist_proof(
+                    &proof,
+                    &tree.root(),
+                    allowlist_leaf(&Pubkey::new_unique())
+                ));
+            }
+
+            assert_eq!(tree.proof(&Pubkey::new_unique()), None);
+        }
+    }
+
+    #[test]
+    fn test_get_args_name_length() {
+        let args = |name: &str| {
+            let mut obj = serde_json::json!({ "name": name, "active": true })
+                .as_object()
+                .unwrap()
+                .clone();
+            get_args(&mut obj)
+        };
+
+        // longer than a seed but within the limit in bytes
+        let name = "東京駅八重洲北口店 テストロケーション";
+        assert_eq!(args(name).unwrap(), (name.to_string(), true));
+
+        // within the limit in characters but not in bytes
+        assert!(matches!(
+            args(&"店".repeat(30)),
+            Err(AppError::MultipartMetadataError(_))
+        ));
+        assert!(matches!(args(""), Err(AppError::MultipartMetadataError(_))));
+    }
+
+    #[test]
+    fn test_identity_hash() {
+        use utils::customer_vault::{identity_hash, normalize_identity};
+
+        assert_eq!(
+            normalize_identity(" Customer@Example.com ").unwrap(),
+            "customer@example.com"
+        );
+        assert_eq!(
+            normalize_identity("+1 (555) 010-9999").unwrap(),
+            "+15550109999"
+        );
+        assert!(normalize_identity("555").is_err());
+        assert_eq!(
+            identity_hash("salt", "+1 555 010 9999").unwrap(),
+            identity_hash("salt", "+1 (555) 010-9999").unwrap()
+        );
+        assert_ne!(
+            identity_hash("salt", "customer@example.com").unwrap(),
+            identity_hash("pepper", "customer@example.com").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_otp_provider() {
+        use utils::customer_vault::OTP_MAX_ATTEMPTS;
+
+        let otp = LocalOtpProvider::default();
+        let identity = "customer@example.com";
+
+        otp.send_code(identity).await.unwrap();
+        let code = otp.code(identity).unwrap();
+
+        assert!(!otp.verify_code(identity, "not the code").await.unwrap());
+        assert!(otp.verify_code(identity, &code).await.unwrap());
+        // codes can only be used once
+        assert!(!otp.verify_code(identity, &code).await.unwrap());
+
+        // codes are invalidated after too many wrong guesses
+        otp.send_code(identity).await.unwrap();
+        let code = otp.code(identity).unwrap();
+        for _ in 0..OTP_MAX_ATTEMPTS {
+            assert!(!otp.verify_code(identity, "not the code").await.unwrap());
+        }
+        assert!(otp.code(identity).is_none());
+        assert!(!otp.verify_code(identity, &code).await.unwrap());
+    }
+
+    #[test]
+    fn test_get_promo_gift_policy_args() {
+        let policy =
+            |value: serde_json::Value| get_promo_gift_policy_args(value.as_object().unwrap());
+
+        assert_eq!(
+            policy(serde_json::json!({})).unwrap(),
+            GiftPolicy::Disallowed
+        );
+        assert_eq!(
+            policy(serde_json::json!({ "giftPolicy": "allowed" })).unwrap(),
+            GiftPolicy::Allowed
+        );
+        assert_eq!(
+            policy(serde_json::json!({ "giftPolicy": "allowedOnce" })).unwrap(),
+            GiftPolicy::AllowedOnce
+        );
+        assert!(policy(serde_json::json!({ "giftPolicy": true })).is_err());
+    }
+
+    #[test]
+    fn test_get_promo_terms_args() {
+        let terms = |attributes: serde_json::Value| {
+            get_promo_terms_args(
+                serde_json::json!({ "attributes": attributes })
+                    .as_object()
+                    .unwrap(),
+            )
+        };
+
+        assert_eq!(
+            terms(serde_json::json!([
+                { "trait_type": "promoType", "value": "buyXProductGetYFree" },
+                { "trait_type": "productId", "value": "coffee" },
+                { "trait_type": "buyXProduct", "value": 3 },
+                { "trait_type": "getYProduct", "value": 1 },
+            ]))
+            .unwrap(),
+            Some(PromoTerms::BuyXProductGetYFree {
+                product_id: "coffee".to_string(),
+                buy_x_product: 3,
+                get_y_product: 1,
+            })
+        );
+        assert_eq!(
+            terms(serde_json::json!([
+                { "trait_type": "promoType", "value": "amountOff" },
+                { "trait_type": "amountOff", "value": 500 },
+            ]))
+            .unwrap(),
+            Some(PromoTerms::AmountOff {
+                buy_x_currency: 0,
+                amount_off: 500,
+            })
+        );
+        assert_eq!(
+            terms(serde_json::json!([{ "trait_type": "promoType", "value": "loyalty" }])).unwrap(),
+            None
+        );
+
+        // missing and out of range values
+        assert!(terms(serde_json::json!([
+            { "trait_type": "promoType", "value": "buyXCurrencyGetYPercent" },
+            { "trait_type": "buyXCurrency", "value": 10000 },
+        ]))
+        .is_err());
+        assert!(terms(serde_json::json!([
+            { "trait_type": "promoType", "value": "buyXCurrencyGetYPercent" },
+            { "trait_type": "buyXCurrency", "value": 10000 },
+            { "trait_type": "getYPercent", "value": 120 },
+        ]))
+        .is_err());
+        assert!(terms(serde_json::json!([
+            { "trait_type": "promoType", "value": "freeItem" },
+            { "trait_type": "productId", "value": "" },
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn test_get_campaign_location_transactions() {
+        let [payer, owner, campaign] = [(); 3].map(|_| Pubkey::new_unique());
+        let locations: Vec<Pubkey> = (0..40).map(|_| Pubkey::new_unique()).collect();
+        let create_ix = solana_sdk::system_instruction::transfer(&owner, &campaign, 1);
+
+        let transactions = get_campaign_location_transactions(
+            &payer,
+            &owner,
+            &campaign,
+            vec![create_ix.clone()],
+            &locations,
+            false,
+        )
+        .unwrap();
+
+        assert!(transactions.len() > 1);
+        assert_eq!(transactions[0].message.instructions[0].data, create_ix.data);
+
+        // Every location is linked once, in order, and every transaction fits.
+        let mut linked = vec![];
+        for tx in transactions.iter() {
+            assert!(
+                bincode::serialized_size(tx).unwrap()
+                    <= solana_sdk::packet::PACKET_DATA_SIZE as u64
+            );
+            let ix = tx.message.instructions.last().unwrap();
+            linked.extend(
+                ix.accounts[7..]
+                    .iter()
+                    .step_by(2)
+                    .map(|i| tx.message.account_keys[*i as usize]),
+            );
+        }
+        assert_eq!(linked, locations);
+
+        // Without locations only the given instructions are returned.
+        let transactions = get_campaign_location_transactions(
+            &payer,
+            &owner,
+            &campaign,
+            vec![create_ix],
+            &[],
+            false,
+        )
+        .unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].message.instructions.len(), 1);
+    }
+
+    #[test]
+    fn test_get_promo_dynamic_args() {
+        let dynamic = |value: serde_json::Value| get_promo_dynamic_args(value.as_object().unwrap());
+
+        assert!(!dynamic(serde_json::json!({})).unwrap());
+        assert!(dynamic(serde_json::json!({ "dynamic": true })).unwrap());
+        assert!(dynamic(serde_json::json!({
+            "dynamic": true,
+            "tiers": [{ "name": "Silver", "minBalance": 5 }],
+        }))
+        .unwrap());
+        assert!(dynamic(serde_json::json!({ "dynamic": "yes" })).is_err());
+        assert!(dynamic(serde_json::json!({ "tiers": [{ "name": "Silver" }] })).is_err());
+        assert!(dynamic(serde_json::json!({ "dynamic": true, "tiers": [{}] })).is_err());
+    }
+
+    #[test]
+    fn test_render_metadata() {
+        let template = serde_json::json!({
+            "name": "Coffee Card",
+            "description": "{usesLeft} uses left",
+            "image": "https://arweave.net/bronze",
+            "properties": {
+                "files": [{ "uri": "https://arweave.net/bronze", "type": "image/png" }],
+                "category": "image"
+            },
+            "attributes": [{ "trait_type": "promoType", "value": "loyalty" }],
+            "tiers": [
+                { "name": "Silver", "minBalance": 5, "image": "https://arweave.net/silver" },
+                { "name": "Gold", "minBalance": 10, "minUses": 2, "image": "https://arweave.net/gold" },
+            ],
+        });
+        let template = template.as_object().unwrap();
+        let attribute = |metadata: &serde_json::Map<String, serde_json::Value>, name: &str| {
+            metadata["attributes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|a| a["trait_type"] == name)
+                .map(|a| a["value"].clone())
+        };
+
+        // promo wide
+        let metadata = render_metadata(
+            template,
+            &PromoState {
+                mint_count: 3,
+                burn_count: 1,
+                max_mint: Some(10),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(metadata.get("tiers").is_none());
+        assert_eq!(metadata["image"], "https://arweave.net/bronze");
+        assert_eq!(attribute(&metadata, "promoType").unwrap(), "loyalty");
+        assert_eq!(attribute(&metadata, "remainingMints").unwrap(), 7);
+        assert!(attribute(&metadata, "tier").is_none());
+        assert!(attribute(&metadata, "balance").is_none());
+
+        // holder in the silver tier, not gold until they have used 2
+        let holder = PromoState {
+            mint_count: 12,
+            balance: Some(11),
+            uses: Some(1),
+            ..Default::default()
+        };
+        let metadata = render_metadata(template, &holder).unwrap();
+        assert_eq!(metadata["image"], "https://arweave.net/silver");
+        assert_eq!(
+            metadata["properties"]["files"][0]["uri"],
+            "https://arweave.net/silver"
+        );
+        assert_eq!(metadata["description"], "11 uses left");
+        assert_eq!(attribute(&metadata, "tier").unwrap(), "Silver");
+
+        let metadata = render_metadata(
+            template,
+            &PromoState {
+                uses: Some(2),
+                ..holder
+            },
+        )
+        .unwrap();
+        assert_eq!(metadata["image"], "https://arweave.net/gold");
+        assert_eq!(attribute(&metadata, "tier").unwrap(), "Gold");
+    }
+
+    #[test]
+    fn test_check_template() {
+        let mint = Pubkey::new_unique();
+        let uri = get_dynamic_metadata_uri("https://tx.bokoup.dev/", "template-id", &mint);
+
+        assert!(check_template("template-id", &mint, &uri).is_ok());
+        assert!(check_template("other-template", &mint, &uri).is_err());
+        assert!(check_template("template-id", &Pubkey::new_unique(), &uri).is_err());
+        // Promos that aren't dynamic have an arweave metadata uri.
+        assert!(check_template("template-id", &mint, "https://arweave.net/template-id").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_params() {
+        let campaign_owner = Pubkey::new_unique();
+        let campaign = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let locations = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let (mut parts, _) = Request::builder()
+            .uri(format!(
+                "/v2/campaign/join?campaign_owner={}&campaign={}&owner={}&locations={},{}",
+                campaign_owner, campaign, owner, locations[0], locations[1]
+            ))
+            .body(Body::empty())
+            .unwrap()
+            .into_parts();
+
+        let handlers::Params(params) =
+            handlers::Params::<handlers::JoinCampaignParams>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+
+        assert_eq!(params.campaign_owner, Address(campaign_owner));
+        assert_eq!(params.campaign, Address(campaign));
+        assert_eq!(params.owner, Address(owner));
+        assert_eq!(params.locations.0, locations);
+
+        let (mut parts, _) = Request::builder()
+            .uri(format!(
+                "/v2/campaign/join?campaign_owner={}&campaign=invalid&owner={}&locations={}",
+                campaign_owner, owner, locations[0]
+            ))
+            .body(Body::empty())
+            .unwrap()
+            .into_parts();
+
+        let result =
+            handlers::Params::<handlers::JoinCampaignParams>::from_request_parts(&mut parts, &())
+                .await;
+
+        assert!(matches!(result, Err(AppError::ParamsError(_))));
+
+        let (mut parts, _) = Request::builder()
+            .uri(format!(
+                "/v2/signmemo?message={}",
+                urlencoding::encode(MESSAGE)
+            ))
+            .body(Body::empty())
+            .unwrap()
+            .into_parts();
+
+        let result =
+            handlers::Params::<handlers::SignMemoParams>::from_request_parts(&mut parts, &()).await;
+
+        assert!(matches!(result, Err(AppError::ParamsError(_))));
+
+        let [mint, device, device_owner, location, campaign, token_owner] =
+            [(); 6].map(|_| Pubkey::new_unique());
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(format!(
+                "/v2/promo/mint?mint={}&device={}&device_owner={}&location={}&campaign={}",
+                mint, device, device_owner, location, campaign
+            ))
+            .body(Body::from(
+                serde_json::json!({
+                    "account": token_owner.to_string(),
+                    "message": MESSAGE,
+                    "memo": "memo from the body",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let handlers::Args(params, data) = handlers::Args::<
+            handlers::MintParams,
+            get_mint_promo_tx::Data,
+        >::from_request(request, &())
+        .await
+        .unwrap();
+
+        assert_eq!(params.mint, Address(mint));
+        assert_eq!(params.campaign, Address(campaign));
+        assert_eq!(params.message, MESSAGE);
+        assert_eq!(params.memo.as_deref(), Some("memo from the body"));
+        assert_eq!(data.account, Address(token_owner));
+        assert_eq!(data.referrer, None);
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(format!(
+                "/v2/promo/mint?mint={}&device={}&device_owner={}&location={}&campaign={}",
+                mint, device, device_owner, location, campaign
+            ))
+            .body(Body::from(
+                serde_json::json!({ "account": "invalid", "message": MESSAGE }).to_string(),
+            ))
+            .unwrap();
+
+        let result = handlers::Args::<handlers::MintParams, get_mint_promo_tx::Data>::from_request(
+            request,
+            &(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::ParamsError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_openapi() {
+        let doc = get_openapi::ApiDoc::openapi();
+
+        // Every documented operation is routed, without the state needed to handle it.
+        for (path, item) in doc.paths.paths.iter() {
+            for operation in item.operations.keys() {
+                let method = match operation {
+                    PathItemType::Get => Method::GET,
+                    _ => Method::POST,
+                };
+                let response = Router::new()
+                    .nest("/v2", create_v2_router())
+                    .oneshot(
+                        Request::builder()
+                            .method(method.clone())
+                            .uri(path)
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+
+                assert_ne!(
+                    response.status(),
+                    StatusCode::NOT_FOUND,
+                    "{} {}",
+                    method,
+                    path
+                );
+                assert_ne!(
+                    response.status(),
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    "{} {}",
+                    method,
+                    path
+                );
+            }
+        }
+
+        // Every referenced schema is a component.
+        let schemas = doc.components.as_ref().unwrap().schemas.clone();
+        let json = serde_json::to_string(&doc).unwrap();
+        for reference in json.split("\"#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.contains_key(name), "{}", name);
+        }
+    }
 
-    const MESSAGE: &str = "This is a really long message that tells you to do something.";
-    const PLATFORM: &str = "2R7GkXvQQS4iHptUvQMhDvRSNXL8tAuuASNvCYgz3GQW";
-    const DATA_URL: &str = "https://shining-sailfish-15.hasura.app/v1/graphql/";
+    #[test]
+    fn test_get_memo_args() {
+        let mut obj = serde_json::json!({ "name": "Promo", "memo": "memo from the metadata" })
+            .as_object()
+            .unwrap()
+            .clone();
 
-    pub async fn run_tests() {
-        dotenv::dotenv().ok();
-        std::env::set_var("RUST_LOG", "bpl_api_tx=trace");
-        tracing_subscriber::registry()
-            .with(fmt::layer())
-            .with(EnvFilter::from_default_env())
-            .try_init()
-            .unwrap_or(());
-        // dotenv::dotenv().ok();
-        // fund_accounts().await;
-        // test_create_merchant().await;
-        // test_create_location().await;
-        // test_create_device().await;
+        assert_eq!(
+            get_memo_args(&mut obj.clone(), Some("memo argument".to_string())).unwrap(),
+            Some("memo argument".to_string())
+        );
+        assert_eq!(
+            get_memo_args(&mut obj, None).unwrap(),
+            Some("memo from the metadata".to_string())
+        );
+        // The memo isn't uploaded with the metadata.
+        assert!(!obj.contains_key("memo"));
+        assert_eq!(get_memo_args(&mut obj, None).unwrap(), None);
+
+        obj.insert("memo".to_string(), 1.into());
+        assert!(get_memo_args(&mut obj, None).is_err());
+    }
+
+    #[test]
+    fn test_get_membership_args() {
+        let membership = |attributes: serde_json::Value| {
+            get_membership_args(
+                serde_json::json!({
+                    "name": "Coffee Club",
+                    "symbol": "CLUB",
+                    "attributes": attributes,
+                })
+                .as_object()
+                .unwrap(),
+            )
+        };
+
+        assert_eq!(
+            membership(serde_json::json!([{ "trait_type": "durationDays", "value": 30 }])).unwrap(),
+            (
+                "Coffee Club".to_string(),
+                "CLUB".to_string(),
+                30 * 24 * 60 * 60,
+                None
+            )
+        );
+        assert_eq!(
+            membership(serde_json::json!([
+                { "trait_type": "durationDays", "value": 365 },
+                { "trait_type": "maxSupply", "value": 100 },
+            ]))
+            .unwrap()
+            .3,
+            Some(100)
+        );
+
+        // missing and out of range values
+        assert!(membership(serde_json::json!([])).is_err());
+        assert!(
+            membership(serde_json::json!([{ "trait_type": "durationDays", "value": 0 }])).is_err()
+        );
+        assert!(membership(serde_json::json!([
+            { "trait_type": "durationDays", "value": 30 },
+            { "trait_type": "maxSupply", "value": -1 },
+        ]))
+        .is_err());
     }
 
     #[tokio::test]
-    async fn test_app_id_ding() {
+    async fn test_get_redeem_promo_tx() {
         run_tests().await;
-
         let platform_signer =
             parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
 
-        dbg!(platform_signer.pubkey().to_string());
-        // ok to be devnet, only pulling blockhash - will succeed even if localnet validator not running
         let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
             .await
             .unwrap();
 
-        let balance = bundlr_sdk::bundlr::get_balance(
-            &url::Url::from_str(BUNDLR_DEFAULT_URL).unwrap(),
-            CurrencyType::Solana,
-            &platform_signer.pubkey().to_string(),
-            &reqwest::Client::new(),
-        )
-        .await
-        .unwrap();
-
         let app = create_app(
             Cluster::Devnet,
             Pubkey::from_str(PLATFORM.into()).unwrap(),
             platform_signer,
             Url::from_str(DATA_URL).unwrap(),
             pub_info,
+            test_config(),
         );
 
         let mint = Pubkey::new_unique();
+        let device = Pubkey::new_unique();
+        let location = Pubkey::new_unique();
+        let campaign = Pubkey::new_unique();
+        let token_owner = Pubkey::new_unique();
+        let device_owner = Pubkey::new_unique();
+
+        let data = get_redeem_promo_tx::Data {
+            account: Address(token_owner),
+            sponsor: None,
+        };
         let message = urlencoding::encode(MESSAGE);
+        let memo = "jingus";
+        let memo_encoded = urlencoding::encode(memo);
+
         let response = app
             .oneshot(
                 Request::builder()
+                    .method(Method::POST)
                     .uri(format!(
-                        "/promo/mint/{}/{}/{}/{}/{}/{}",
-                        mint.to_string(),
-                        mint.to_string(),
-                        mint.to_string(),
-                        mint.to_string(),
+                        "/promo/redeem/{}/{}/{}/{}/{}/{}/{}",
                         mint.to_string(),
+                        device.to_string(),
+                        device_owner.to_string(),
+                        location.to_string(),
+                        campaign.to_string(),
                         message.into_owned(),
+                        memo_encoded.into_owned()
                     ))
-                    .body(Body::empty())
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&data).unwrap()))
                     .unwrap(),
             )
             .await
@@ -295,21 +1665,51 @@ pub mod test {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
-        let parsed_response: get_app_id::ResponseData = serde_json::from_slice(&body).unwrap();
+        let parsed_response: PayResponse = serde_json::from_slice(&body).unwrap();
+
+        let txd: Transaction = bincode::deserialize(
+            &base64::decode::<String>(parsed_response.transaction.clone()).unwrap(),
+        )
+        .unwrap();
+
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let instruction = redeem_promo_instruction(
+            platform_signer.pubkey(),
+            device_owner,
+            device,
+            location,
+            campaign,
+            token_owner,
+            mint,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            anchor_spl::token::ID,
+            None,
+            Some(memo.to_string()),
+        );
+
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&platform_signer.pubkey()));
+
+        let recent_blockhash = txd.message.recent_blockhash;
+
+        tx.try_partial_sign(&[&platform_signer], recent_blockhash)
+            .unwrap();
+
+        let serialized = bincode::serialize(&tx).unwrap();
+        let transaction = base64::encode(serialized);
 
         assert_eq!(
             parsed_response,
-            get_app_id::ResponseData {
-                label: get_app_id::LABEL.to_string(),
-                icon: get_app_id::ICON.to_string(),
+            PayResponse {
+                transaction,
+                message: MESSAGE.to_string(),
             }
         );
     }
 
-    // Testing end user requesting mint tx where merchant has added platform signer to group members
-    // to pay for transaction fees with no further merchant approval required.
     #[tokio::test]
-    async fn test_get_mint_promo_tx() {
+    async fn test_get_clawback_promo_tx() {
         run_tests().await;
         let platform_signer =
             parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
@@ -324,35 +1724,26 @@ pub mod test {
             platform_signer,
             Url::from_str(DATA_URL).unwrap(),
             pub_info,
+            test_config(),
         );
 
-        let mint = Pubkey::new_unique();
-        let device = Pubkey::new_unique();
-        let location = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
         let campaign = Pubkey::new_unique();
-        let token_owner = Pubkey::new_unique();
-        let device_owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
 
-        let data = get_mint_promo_tx::Data {
-            account: token_owner.to_string(),
+        let data = get_clawback_promo_tx::Data {
+            burn: false,
+            reason: "minted by compromised device".to_string(),
         };
-        let message = urlencoding::encode(MESSAGE);
-        let memo = "jingus";
-        let memo_encoded = urlencoding::encode(memo);
 
         let response = app
             .oneshot(
                 Request::builder()
                     .method(Method::POST)
                     .uri(format!(
-                        "/promo/mint/{}/{}/{}/{}/{}/{}/{}",
-                        mint.to_string(),
-                        device.to_string(),
-                        device_owner.to_string(),
-                        location.to_string(),
-                        campaign.to_string(),
-                        message.into_owned(),
-                        memo_encoded.into_owned()
+                        "/promo/clawback/{}/{}/{}/{}",
+                        owner, campaign, mint, token_account
                     ))
                     .header(header::CONTENT_TYPE, "application/json")
                     .body(Body::from(serde_json::to_vec(&data).unwrap()))
@@ -374,23 +1765,19 @@ pub mod test {
         let platform_signer =
             parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
 
-        let instruction = mint_promo_instruction(
+        let instruction = clawback_promo_instruction(
             platform_signer.pubkey(),
-            device_owner,
-            device,
-            location,
+            owner,
             campaign,
-            token_owner,
             mint,
-            Some(memo.to_string()),
-        )
-        .unwrap();
+            token_account,
+            anchor_spl::token::ID,
+            data.burn,
+            data.reason,
+        );
 
         let mut tx = Transaction::new_with_payer(&[instruction], Some(&platform_signer.pubkey()));
-
-        let recent_blockhash = txd.message.recent_blockhash;
-
-        tx.try_partial_sign(&[&platform_signer], recent_blockhash)
+        tx.try_partial_sign(&[&platform_signer], txd.message.recent_blockhash)
             .unwrap();
 
         let serialized = bincode::serialize(&tx).unwrap();
@@ -400,7 +1787,7 @@ pub mod test {
             parsed_response,
             PayResponse {
                 transaction,
-                message: MESSAGE.to_string(),
+                message: "Claw back promo tokens".to_string(),
             }
         );
     }
@@ -421,6 +1808,7 @@ pub mod test {
             platform_signer,
             Url::from_str(DATA_URL).unwrap(),
             pub_info,
+            test_config(),
         );
 
         let mint = Pubkey::new_unique();
@@ -431,7 +1819,8 @@ pub mod test {
         let token_owner = Pubkey::new_unique();
 
         let data = get_mint_promo_tx::Data {
-            account: token_owner.to_string(),
+            account: Address(token_owner),
+            referrer: None,
         };
 
         let message = urlencoding::encode(MESSAGE);
@@ -480,9 +1869,9 @@ pub mod test {
             location,
             token_owner,
             mint,
+            anchor_spl::token::ID,
             Some(memo.to_string()),
-        )
-        .unwrap();
+        );
 
         let mut tx = Transaction::new_with_payer(&[instruction], Some(&platform_signer.pubkey()));
         let recent_blockhash = txd.message.recent_blockhash;
@@ -517,6 +1906,7 @@ pub mod test {
             platform_signer,
             Url::from_str(DATA_URL).unwrap(),
             pub_info,
+            test_config(),
         );
 
         let mint = Pubkey::new_unique();
@@ -527,7 +1917,8 @@ pub mod test {
         let device_owner = Pubkey::new_unique();
 
         let data = get_mint_promo_tx::Data {
-            account: device_owner.to_string(),
+            account: Address(device_owner),
+            referrer: None,
         };
 
         let message = urlencoding::encode(MESSAGE);
@@ -577,9 +1968,11 @@ pub mod test {
             token_account,
             mint,
             Pubkey::from_str(PLATFORM.into()).unwrap(),
+            anchor_spl::token::ID,
+            None,
+            None,
             Some(memo.to_string()),
-        )
-        .unwrap();
+        );
 
         let mut tx = Transaction::new_with_payer(&[instruction], Some(&platform_signer.pubkey()));
         let recent_blockhash = txd.message.recent_blockhash;
@@ -615,6 +2008,7 @@ pub mod test {
             platform_signer,
             Url::from_str(DATA_URL).unwrap(),
             pub_info,
+            test_config(),
         );
 
         let platform_signer =
@@ -631,13 +2025,15 @@ pub mod test {
             Keypair::from_bytes(&platform_signer.to_bytes()).unwrap(),
             Url::from_str(DATA_URL).unwrap(),
             pub_info,
+            test_config(),
         );
 
         let pre_memo = r#"{"jingus": "amongus"}"#;
         let memo = urlencoding::encode(pre_memo);
 
         let data = get_mint_promo_tx::Data {
-            account: signer.pubkey().to_string(),
+            account: Address(signer.pubkey()),
+            referrer: None,
         };
 
         let message = urlencoding::encode(MESSAGE);
@@ -672,8 +2068,7 @@ pub mod test {
             state.platform_signer.pubkey(),
             pre_memo.to_string(),
             signer.pubkey(),
-        )
-        .unwrap();
+        );
 
         let mut tx =
             Transaction::new_with_payer(&[instruction], Some(&state.platform_signer.pubkey()));
@@ -718,6 +2113,7 @@ pub mod test {
                         platform_signer,
                         Url::from_str(DATA_URL).unwrap(),
                         pub_info,
+                        test_config(),
                     )
                     .into_make_service(),
                 )
@@ -813,6 +2209,7 @@ pub mod test {
                         platform_signer,
                         Url::from_str(DATA_URL).unwrap(),
                         pub_info,
+                        test_config(),
                     )
                     .into_make_service(),
                 )
@@ -908,6 +2305,7 @@ pub mod test {
                         platform_signer,
                         Url::from_str(DATA_URL).unwrap(),
                         pub_info,
+                        test_config(),
                     )
                     .into_make_service(),
                 )
@@ -989,6 +2387,7 @@ pub mod test {
                         platform_signer,
                         Url::from_str(DATA_URL).unwrap(),
                         pub_info,
+                        test_config(),
                     )
                     .into_make_service(),
                 )
@@ -1000,6 +2399,12 @@ pub mod test {
             "name": "Test Campaign",
             "reference": "012345677",
             "description": "bokout test location",
+            "attributes": [
+                {
+                    "trait_type": "maxBurn",
+                    "value": 2000,
+                },
+            ],
             "active": true
         });
 
@@ -1045,6 +2450,135 @@ pub mod test {
         .unwrap();
 
         assert_eq!(instruction.data.name, "Test Campaign".to_string());
+        assert_eq!(instruction.data.max_mint, None);
+        assert_eq!(instruction.data.max_burn, Some(2000));
+    }
+
+    #[tokio::test]
+    async fn test_set_campaign_location_limits() {
+        run_tests().await;
+        let merchant_owner =
+            parse_string_to_keypair(&std::env::var("MERCHANT_OWNER_KEYPAIR").unwrap());
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
+            .await
+            .unwrap();
+
+        let app = create_app(
+            Cluster::Devnet,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            platform_signer,
+            Url::from_str(DATA_URL).unwrap(),
+            pub_info,
+            test_config(),
+        );
+
+        let campaign = find_campaign_address(&merchant_owner.pubkey(), "Test Campaign").0;
+        let location = find_location_address(&merchant_owner.pubkey(), "Test Location").0;
+
+        let data = get_set_campaign_location_limits_tx::Data {
+            location_max_mint: Some(500),
+            device_max_burn: Some(50),
+            ..Default::default()
+        };
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!(
+                        "/campaign/limits/{}/{}/{}",
+                        merchant_owner.pubkey(),
+                        campaign,
+                        location,
+                    ))
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&data).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let parsed_response: PayResponse = serde_json::from_slice(&body).unwrap();
+
+        let tx: Transaction =
+            bincode::deserialize(&base64::decode::<String>(parsed_response.transaction).unwrap())
+                .unwrap();
+
+        let instruction =
+            bpl_token_metadata::instruction::SetCampaignLocationLimits::try_from_slice(
+                &tx.message.instructions[0].data[8..],
+            )
+            .unwrap();
+
+        assert_eq!(instruction.location_limits.max_mint, Some(500));
+        assert_eq!(instruction.location_limits.max_burn, None);
+        assert_eq!(instruction.device_limits.max_mint, None);
+        assert_eq!(instruction.device_limits.max_burn, Some(50));
+    }
+
+    #[tokio::test]
+    async fn test_join_campaign() {
+        run_tests().await;
+        let merchant_owner =
+            parse_string_to_keypair(&std::env::var("MERCHANT_OWNER_KEYPAIR").unwrap());
+        let platform_signer =
+            parse_string_to_keypair(&std::env::var("PLATFORM_SIGNER_KEYPAIR").unwrap());
+
+        let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
+            .await
+            .unwrap();
+
+        let app = create_app(
+            Cluster::Devnet,
+            Pubkey::from_str(PLATFORM.into()).unwrap(),
+            platform_signer,
+            Url::from_str(DATA_URL).unwrap(),
+            pub_info,
+            test_config(),
+        );
+
+        let campaign = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let locations = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!(
+                        "/campaign/join/{}/{}/{}/{}/{}",
+                        merchant_owner.pubkey(),
+                        campaign,
+                        owner,
+                        locations[0],
+                        locations[1],
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let parsed_response: PayResponse = serde_json::from_slice(&body).unwrap();
+
+        let tx: Transaction =
+            bincode::deserialize(&base64::decode::<String>(parsed_response.transaction).unwrap())
+                .unwrap();
+
+        assert_eq!(tx.message.instructions.len(), 3);
+        assert!(tx.message.account_keys.contains(&merchant_owner.pubkey()));
+        assert!(tx.message.account_keys.contains(&owner));
+        // Both merchant owners still need to sign.
+        assert_eq!(tx.message.header.num_required_signatures, 3);
     }
 
     #[tokio::test]
@@ -1073,6 +2607,7 @@ pub mod test {
                         platform_signer,
                         Url::from_str(DATA_URL).unwrap(),
                         pub_info,
+                        test_config(),
                     )
                     .into_make_service(),
                 )
@@ -1,9 +1,16 @@
 use anchor_lang::prelude::Pubkey;
-use bpl_api_tx::{create_app, parse_string_to_keypair, utils::solana::Cluster};
+use bpl_api_tx::{
+    create_app, parse_string_to_keypair,
+    utils::{
+        customer_vault::{LocalOtpProvider, OtpProvider, TwilioOtpProvider},
+        solana::Cluster,
+    },
+    Config,
+};
 use bundlr_sdk::{bundlr::get_pub_info, consts::BUNDLR_DEFAULT_URL};
-use clap::Parser;
+use clap::{builder::NonEmptyStringValueParser, CommandFactory, ErrorKind, Parser, ValueEnum};
 use solana_sdk::signer::Signer;
-use std::{net::SocketAddr, str::FromStr};
+use std::{net::SocketAddr, str::FromStr, sync::Arc};
 use tracing_subscriber::prelude::*;
 use url::Url;
 
@@ -20,6 +27,38 @@ struct Args {
     platform: Pubkey,
     #[clap(long, env = "PLATFORM_SIGNER_KEYPAIR")]
     platform_signer: String,
+    /// Sends one time passwords to customers with vaults. `local` never delivers codes and is
+    /// only for development.
+    #[clap(long, env = "OTP_PROVIDER", value_enum, default_value = "twilio")]
+    otp_provider: OtpProviderKind,
+    /// Required by the twilio otp provider
+    #[clap(long, env = "TWILIO_ACCOUNT_SID", value_parser = NonEmptyStringValueParser::new())]
+    twilio_account_sid: Option<String>,
+    /// Required by the twilio otp provider
+    #[clap(long, env = "TWILIO_AUTH_TOKEN", value_parser = NonEmptyStringValueParser::new())]
+    twilio_auth_token: Option<String>,
+    /// Twilio Verify service that sends the codes, required by the twilio otp provider
+    #[clap(
+        long,
+        env = "TWILIO_VERIFY_SERVICE_SID",
+        value_parser = NonEmptyStringValueParser::new()
+    )]
+    twilio_verify_service_sid: Option<String>,
+    /// Salt for customer vault identity hashes. Shouldn't change once vaults have been created.
+    #[clap(long, env = "CUSTOMER_VAULT_SALT", value_parser = NonEmptyStringValueParser::new())]
+    customer_vault_salt: String,
+    /// Base url dynamic promo metadata uris point at.
+    #[clap(long, env = "METADATA_URL", default_value = "https://tx.bokoup.dev")]
+    metadata_url: String,
+    /// Public base url of this api that voucher claim urls point at.
+    #[clap(long, env = "APP_URL", default_value = "https://tx.bokoup.dev")]
+    app_url: String,
+}
+
+#[derive(Clone, ValueEnum)]
+enum OtpProviderKind {
+    Twilio,
+    Local,
 }
 
 #[tokio::main]
@@ -34,6 +73,29 @@ async fn main() {
 
     let args = Args::parse();
 
+    let otp: Arc<dyn OtpProvider> = match args.otp_provider {
+        OtpProviderKind::Twilio => match (
+            args.twilio_account_sid,
+            args.twilio_auth_token,
+            args.twilio_verify_service_sid,
+        ) {
+            (Some(account_sid), Some(auth_token), Some(service_sid)) => {
+                Arc::new(TwilioOtpProvider::new(account_sid, auth_token, service_sid))
+            }
+            _ => Args::command()
+                .error(
+                    ErrorKind::MissingRequiredArgument,
+                    "the twilio otp provider requires --twilio-account-sid, --twilio-auth-token \
+                    and --twilio-verify-service-sid",
+                )
+                .exit(),
+        },
+        OtpProviderKind::Local => {
+            tracing::warn!("local otp provider doesn't send codes to customers");
+            Arc::new(LocalOtpProvider::default())
+        }
+    };
+
     let pub_info = get_pub_info(&Url::from_str(BUNDLR_DEFAULT_URL).unwrap())
         .await
         .unwrap();
@@ -53,6 +115,12 @@ async fn main() {
         platform_signer,
         data_url,
         pub_info,
+        Config {
+            otp,
+            vault_salt: args.customer_vault_salt,
+            metadata_url: args.metadata_url,
+            app_url: args.app_url,
+        },
     );
     let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
     tracing::debug!("listening on {}", addr);
@@ -0,0 +1,202 @@
+use crate::{error::AppError, State};
+use anchor_lang::prelude::Pubkey;
+use base58::ToBase58;
+use bpl_token_metadata::utils::{allowlist_leaf, allowlist_node, find_metadata_address};
+use bpl_token_metadata_client::accounts::fetch_promo;
+use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+
+/// Merkle tree of the wallets eligible to receive an allowlisted promo.
+///
+/// Leaves are kept in the order the wallets were provided so that the uploaded wallet list
+/// rebuilds the same tree. A node without a sibling is carried up to the next layer as is.
+pub struct AllowlistTree {
+    wallets: Vec<Pubkey>,
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl AllowlistTree {
+    pub fn new(wallets: Vec<Pubkey>) -> Result<Self, AppError> {
+        let mut unique = Vec::with_capacity(wallets.len());
+        for wallet in wallets {
+            if !unique.contains(&wallet) {
+                unique.push(wallet);
+            }
+        }
+
+        if unique.is_empty() {
+            return Err(AppError::AllowlistError(
+                "allowlist should include at least one wallet".to_string(),
+            ));
+        }
+
+        let mut layers = vec![unique.iter().map(allowlist_leaf).collect::<Vec<[u8; 32]>>()];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => allowlist_node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(layer);
+        }
+
+        Ok(Self {
+            wallets: unique,
+            layers,
+        })
+    }
+
+    /// Builds the tree from csv with a wallet address in the first column of each row. A
+    /// header row is skipped if its first column isn't an address.
+    pub fn from_csv(csv: &str) -> Result<Self, AppError> {
+        let wallets = csv
+            .lines()
+            .map(|line| line.split(',').next().unwrap_or_default().trim())
+            .filter(|wallet| !wallet.is_empty())
+            .enumerate()
+            .filter_map(|(i, wallet)| match Pubkey::from_str(wallet) {
+                Ok(pubkey) => Some(Ok(pubkey)),
+                Err(_) if i == 0 => None,
+                Err(_) => Some(Err(AppError::AllowlistError(format!(
+                    "invalid wallet address: {}",
+                    wallet
+                )))),
+            })
+            .collect::<Result<Vec<Pubkey>, AppError>>()?;
+
+        Self::new(wallets)
+    }
+
+    /// Builds the tree from the json wallet list uploaded to Arweave.
+    pub fn from_json(value: &Value) -> Result<Self, AppError> {
+        let wallets = value["wallets"]
+            .as_array()
+            .ok_or(AppError::AllowlistError(
+                "wallets field should exist".to_string(),
+            ))?
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .ok_or(AppError::AllowlistError(
+                        "wallets should be strings".to_string(),
+                    ))
+                    .and_then(|s| Ok(Pubkey::from_str(s)?))
+            })
+            .collect::<Result<Vec<Pubkey>, AppError>>()?;
+
+        Self::new(wallets)
+    }
+
+    pub fn to_json(&self) -> Map<String, Value> {
+        let mut obj = Map::new();
+        obj.insert("root".to_string(), self.root().to_base58().into());
+        obj.insert(
+            "wallets".to_string(),
+            json!(self
+                .wallets
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()),
+        );
+        obj
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.wallets.len()
+    }
+
+    /// Returns the proof for a wallet, or `None` if it isn't on the allowlist.
+    pub fn proof(&self, wallet: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.wallets.iter().position(|w| w == wallet)?;
+        let mut proof = vec![];
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
+
+/// Returns the uri of the uploaded allowlist if the `allowlist` attribute exists.
+pub fn get_allowlist_uri(metadata_data_obj: &Map<String, Value>) -> Option<String> {
+    metadata_data_obj
+        .get("attributes")?
+        .as_array()?
+        .iter()
+        .find_map(|a| {
+            let attribute = a.as_object()?;
+            if attribute.get("trait_type")? == "allowlist" {
+                attribute.get("value")?.as_str().map(ToString::to_string)
+            } else {
+                None
+            }
+        })
+}
+
+/// Fetches an uploaded allowlist and rebuilds its tree.
+pub async fn get_allowlist(uri: &str, state: &State) -> Result<AllowlistTree, AppError> {
+    let value: Value = state.solana.client.get(uri).send().await?.json().await?;
+
+    AllowlistTree::from_json(&value)
+}
+
+/// Returns the proof for the token owner to mint an allowlisted promo, empty if the promo
+/// doesn't have an allowlist or isn't found, in which case the transaction will fail when it
+/// is submitted.
+pub async fn get_allowlist_proof(
+    mint: &Pubkey,
+    token_owner: &Pubkey,
+    state: &State,
+) -> Result<Vec<[u8; 32]>, AppError> {
+    let promo = match fetch_promo(&state.solana, mint).await? {
+        Some(promo) => promo,
+        None => return Ok(vec![]),
+    };
+
+    if promo.allowlist.is_none() {
+        return Ok(vec![]);
+    }
+
+    let data = state
+        .solana
+        .get_account_data(&find_metadata_address(mint).0)
+        .await?
+        .ok_or(AppError::AllowlistError(
+            "promo metadata account not found".to_string(),
+        ))?;
+    let metadata =
+        Metadata::safe_deserialize(&data).map_err(|e| AppError::AllowlistError(e.to_string()))?;
+
+    let metadata_json: Value = state
+        .solana
+        .client
+        .get(metadata.data.uri.trim_end_matches(char::from(0)))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    let uri = metadata_json
+        .as_object()
+        .and_then(get_allowlist_uri)
+        .ok_or(AppError::AllowlistError(
+            "promo metadata should include allowlist attribute".to_string(),
+        ))?;
+
+    get_allowlist(&uri, state)
+        .await?
+        .proof(token_owner)
+        .ok_or(AppError::NotAllowlisted)
+}
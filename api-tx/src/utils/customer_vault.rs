@@ -0,0 +1,202 @@
+//! Customer vaults hold promo tokens for customers without a wallet, keyed by a salted hash of
+//! their phone number or email. Customers prove they control the phone number or email with a
+//! one time password before the platform signs a claim of the tokens into their wallet.
+
+use crate::error::AppError;
+use async_trait::async_trait;
+use solana_sdk::hash::hashv;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Normalizes a phone number or email so the same customer always gets the same vault. Emails
+/// are lowercased and phone numbers are reduced to their digits, keeping a leading `+`.
+pub fn normalize_identity(identity: &str) -> Result<String, AppError> {
+    let identity = identity.trim();
+    if identity.contains('@') {
+        return Ok(identity.to_lowercase());
+    }
+
+    let digits: String = identity.chars().filter(char::is_ascii_digit).collect();
+    if digits.len() < 7 {
+        return Err(AppError::CustomerVaultError(
+            "identity should be a phone number or email".to_string(),
+        ));
+    }
+    match identity.starts_with('+') {
+        true => Ok(format!("+{digits}")),
+        false => Ok(digits),
+    }
+}
+
+/// Returns the hash the customer vault address is derived from. The salt keeps phone numbers
+/// and emails from being recovered from the hashes on chain.
+pub fn identity_hash(salt: &str, identity: &str) -> Result<[u8; 32], AppError> {
+    let identity = normalize_identity(identity)?;
+    Ok(hashv(&[salt.as_bytes(), identity.as_bytes()]).to_bytes())
+}
+
+/// Sends and verifies one time passwords for a phone number or email.
+#[async_trait]
+pub trait OtpProvider: Send + Sync {
+    async fn send_code(&self, identity: &str) -> Result<(), AppError>;
+
+    /// Returns whether the code is the one last sent to the identity. Codes can only be used
+    /// once, expire after a few minutes and are invalidated after too many wrong guesses.
+    async fn verify_code(&self, identity: &str, code: &str) -> Result<bool, AppError>;
+}
+
+/// How long a code sent by [LocalOtpProvider] can be used for.
+pub const OTP_CODE_TTL: Duration = Duration::from_secs(10 * 60);
+/// Wrong guesses allowed before a code sent by [LocalOtpProvider] is invalidated.
+pub const OTP_MAX_ATTEMPTS: u8 = 5;
+
+struct SentCode {
+    code: String,
+    sent_at: Instant,
+    attempts: u8,
+}
+
+/// Provider for local development and tests that keeps codes in memory instead of sending
+/// them. Only used when selected explicitly, since customers never receive the codes.
+#[derive(Default)]
+pub struct LocalOtpProvider {
+    codes: Mutex<HashMap<String, SentCode>>,
+}
+
+impl LocalOtpProvider {
+    /// Returns the code last sent to the identity, if it hasn't been used or expired.
+    pub fn code(&self, identity: &str) -> Option<String> {
+        let identity = normalize_identity(identity).ok()?;
+        self.codes
+            .lock()
+            .unwrap()
+            .get(&identity)
+            .filter(|sent| sent.sent_at.elapsed() < OTP_CODE_TTL)
+            .map(|sent| sent.code.clone())
+    }
+}
+
+#[async_trait]
+impl OtpProvider for LocalOtpProvider {
+    async fn send_code(&self, identity: &str) -> Result<(), AppError> {
+        let identity = normalize_identity(identity)?;
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| AppError::CustomerVaultError(e.to_string()))?
+            .as_nanos();
+        let hash = hashv(&[identity.as_bytes(), &nanos.to_le_bytes()]).to_bytes();
+        let code = format!(
+            "{:06}",
+            u32::from_le_bytes(hash[..4].try_into().unwrap()) % 1_000_000
+        );
+
+        tracing::debug!("local otp code issued");
+        self.codes.lock().unwrap().insert(
+            identity,
+            SentCode {
+                code,
+                sent_at: Instant::now(),
+                attempts: 0,
+            },
+        );
+        Ok(())
+    }
+
+    async fn verify_code(&self, identity: &str, code: &str) -> Result<bool, AppError> {
+        let identity = normalize_identity(identity)?;
+        let mut codes = self.codes.lock().unwrap();
+        let Some(sent) = codes.get_mut(&identity) else {
+            return Ok(false);
+        };
+
+        if sent.sent_at.elapsed() >= OTP_CODE_TTL {
+            codes.remove(&identity);
+            return Ok(false);
+        }
+        if sent.code == code {
+            codes.remove(&identity);
+            return Ok(true);
+        }
+        sent.attempts += 1;
+        if sent.attempts >= OTP_MAX_ATTEMPTS {
+            codes.remove(&identity);
+        }
+        Ok(false)
+    }
+}
+
+/// Sends codes by sms, or by email for identities with an `@`, through a Twilio Verify
+/// service. Twilio generates and stores the codes, expires them and limits attempts to check
+/// them, so nothing about the code passes through the app other than the customer's guess.
+pub struct TwilioOtpProvider {
+    account_sid: String,
+    auth_token: String,
+    service_sid: String,
+    client: reqwest::Client,
+}
+
+impl TwilioOtpProvider {
+    pub fn new(account_sid: String, auth_token: String, service_sid: String) -> Self {
+        Self {
+            account_sid,
+            auth_token,
+            service_sid,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    async fn post(&self, endpoint: &str, form: &[(&str, &str)]) -> Result<String, AppError> {
+        let response = self
+            .client
+            .post(format!(
+                "https://verify.twilio.com/v2/Services/{}/{endpoint}",
+                self.service_sid
+            ))
+            .basic_auth(&self.account_sid, Some(&self.auth_token))
+            .form(form)
+            .send()
+            .await?;
+
+        // Twilio returns 404 for checks once a verification has expired, been approved or run
+        // out of attempts, which is a wrong code as far as the customer is concerned.
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok("not_found".to_string());
+        }
+        if !response.status().is_success() {
+            return Err(AppError::CustomerVaultError(format!(
+                "otp provider returned {}",
+                response.status()
+            )));
+        }
+        let body: serde_json::Value = response.json().await?;
+        Ok(body["status"].as_str().unwrap_or_default().to_string())
+    }
+}
+
+#[async_trait]
+impl OtpProvider for TwilioOtpProvider {
+    async fn send_code(&self, identity: &str) -> Result<(), AppError> {
+        let identity = normalize_identity(identity)?;
+        let channel = match identity.contains('@') {
+            true => "email",
+            false => "sms",
+        };
+        self.post("Verifications", &[("To", &identity), ("Channel", channel)])
+            .await?;
+        Ok(())
+    }
+
+    async fn verify_code(&self, identity: &str, code: &str) -> Result<bool, AppError> {
+        let identity = normalize_identity(identity)?;
+        let status = self
+            .post("VerificationCheck", &[("To", &identity), ("Code", code)])
+            .await?;
+        Ok(status == "approved")
+    }
+}
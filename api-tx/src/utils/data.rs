@@ -62,6 +62,31 @@ query TokenAccountQuery($id: String!) {
   }  
   "#;
 
+pub const PROMO_STATE_QUERY: &str = r#"
+    query PromoStateQuery($mint: String!) {
+        promo(where: { mint: { _eq: $mint } }) {
+            mintCount
+            burnCount
+            maxMint
+            maxBurn
+            metadataObject {
+                uri
+            }
+        }
+    }
+    "#;
+
+pub const HOLDER_STATE_QUERY: &str = r#"
+    query HolderStateQuery($mint: String!, $owner: String!) {
+        tokenAccount(where: { mint: { _eq: $mint }, owner: { _eq: $owner } }) {
+            amount
+        }
+        redeemPromoToken(where: { mint: { _eq: $mint }, tokenOwner: { _eq: $owner } }) {
+            signature
+        }
+    }
+    "#;
+
 pub fn get_mint_object_from_promo_group_query(result: &Value) -> Option<&Value> {
     result
         .as_object()
@@ -0,0 +1,320 @@
+use crate::{
+    error::AppError,
+    utils::data::{HOLDER_STATE_QUERY, PROMO_STATE_QUERY},
+    State,
+};
+use anchor_lang::prelude::Pubkey;
+use serde_json::{json, Map, Value};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+pub const ARWEAVE_URL: &str = "https://arweave.net/";
+
+/// Max number of templates kept in a [TemplateCache].
+pub const MAX_CACHED_TEMPLATES: usize = 1024;
+
+/// Indexed state of a promo, and of a token holder when rendering for one, that dynamic
+/// metadata is computed from.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PromoState {
+    pub mint_count: u64,
+    pub burn_count: u64,
+    pub max_mint: Option<u64>,
+    pub max_burn: Option<u64>,
+    /// Tokens held, each of which is a remaining use
+    pub balance: Option<u64>,
+    /// Tokens redeemed
+    pub uses: Option<u64>,
+    /// Metadata uri of the promo, which for dynamic promos includes the template it was created
+    /// with
+    pub metadata_uri: String,
+}
+
+impl PromoState {
+    /// Values available to tiers and placeholders, holder values only when rendering for one.
+    pub fn values(&self) -> Vec<(&'static str, u64)> {
+        let mut values = vec![
+            ("mintCount", self.mint_count),
+            ("burnCount", self.burn_count),
+        ];
+        if let Some(max_mint) = self.max_mint {
+            values.push(("remainingMints", max_mint.saturating_sub(self.mint_count)));
+        }
+        if let Some(max_burn) = self.max_burn {
+            values.push(("remainingBurns", max_burn.saturating_sub(self.burn_count)));
+        }
+        if let Some(balance) = self.balance {
+            values.push(("balance", balance));
+            values.push(("usesLeft", balance));
+        }
+        if let Some(uses) = self.uses {
+            values.push(("uses", uses));
+        }
+        values
+    }
+}
+
+/// Returns the Arweave id of an uploaded metadata json uri.
+pub fn get_arweave_id(uri: &str) -> Result<&str, AppError> {
+    uri.strip_prefix(ARWEAVE_URL)
+        .ok_or(AppError::MetadataRenderError(format!(
+            "{} should be an arweave uri",
+            uri
+        )))
+}
+
+/// Returns the uri of the rendering endpoint that dynamic promo metadata points at.
+pub fn get_dynamic_metadata_uri(metadata_url: &str, template: &str, mint: &Pubkey) -> String {
+    format!(
+        "{}/metadata/{}/{}",
+        metadata_url.trim_end_matches('/'),
+        template,
+        mint
+    )
+}
+
+/// Checks that the template is the one recorded in the metadata uri of the promo when it was
+/// created, so a promo can't be rendered with another promo's template.
+pub fn check_template(template: &str, mint: &Pubkey, metadata_uri: &str) -> Result<(), AppError> {
+    if metadata_uri.ends_with(&format!("/metadata/{}/{}", template, mint)) {
+        Ok(())
+    } else {
+        Err(AppError::MetadataRenderError(format!(
+            "template {} is not the template of mint {}",
+            template, mint
+        )))
+    }
+}
+
+/// Metadata templates already fetched from Arweave. Uploads are immutable, so templates are
+/// kept until the cache is full, at which point it is cleared.
+#[derive(Default)]
+pub struct TemplateCache(Mutex<HashMap<String, Arc<Map<String, Value>>>>);
+
+impl TemplateCache {
+    fn get(&self, template: &str) -> Option<Arc<Map<String, Value>>> {
+        self.0.lock().unwrap().get(template).cloned()
+    }
+
+    fn insert(&self, template: &str, value: Arc<Map<String, Value>>) {
+        let mut templates = self.0.lock().unwrap();
+        if templates.len() >= MAX_CACHED_TEMPLATES {
+            templates.clear();
+        }
+        templates.insert(template.to_string(), value);
+    }
+}
+
+/// Fetches a metadata template uploaded to Arweave, or returns it from the cache if it has
+/// already been fetched.
+pub async fn get_template(
+    template: &str,
+    state: &State,
+) -> Result<Arc<Map<String, Value>>, AppError> {
+    if template.is_empty()
+        || !template
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(AppError::MetadataRenderError(
+            "template should be an arweave id".to_string(),
+        ));
+    }
+
+    if let Some(value) = state.templates.get(template) {
+        return Ok(value);
+    }
+
+    let value: Value = state
+        .solana
+        .client
+        .get(format!("{}{}", ARWEAVE_URL, template))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    match value {
+        Value::Object(value) => {
+            let value = Arc::new(value);
+            state.templates.insert(template, value.clone());
+            Ok(value)
+        }
+        _ => Err(AppError::MetadataRenderError(
+            "template should be an object".to_string(),
+        )),
+    }
+}
+
+async fn query_data(query: &str, variables: Value, state: &State) -> Result<Value, AppError> {
+    let result: Value = state
+        .solana
+        .client
+        .post(state.data_url.clone())
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if let Some(errors) = result.get("errors") {
+        tracing::error!(?errors);
+        return Err(AppError::DataQueryError);
+    }
+    result.get("data").cloned().ok_or(AppError::DataQueryError)
+}
+
+// Bigint columns can be returned as numbers or strings depending on the data api settings.
+fn get_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Fetches the indexed state of a promo from the data api, including the balance and uses of
+/// the token owner if there is one.
+pub async fn get_promo_state(
+    mint: &Pubkey,
+    owner: Option<&Pubkey>,
+    state: &State,
+) -> Result<PromoState, AppError> {
+    let data = query_data(
+        PROMO_STATE_QUERY,
+        json!({ "mint": mint.to_string() }),
+        state,
+    )
+    .await?;
+    let promo = data["promo"]
+        .as_array()
+        .and_then(|promos| promos.first())
+        .ok_or(AppError::MetadataRenderError(format!(
+            "promo not found for mint {}",
+            mint
+        )))?;
+
+    let mut promo_state = PromoState {
+        mint_count: get_u64(&promo["mintCount"]).unwrap_or_default(),
+        burn_count: get_u64(&promo["burnCount"]).unwrap_or_default(),
+        max_mint: get_u64(&promo["maxMint"]),
+        max_burn: get_u64(&promo["maxBurn"]),
+        metadata_uri: promo["metadataObject"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        ..Default::default()
+    };
+
+    if let Some(owner) = owner {
+        let data = query_data(
+            HOLDER_STATE_QUERY,
+            json!({ "mint": mint.to_string(), "owner": owner.to_string() }),
+            state,
+        )
+        .await?;
+        promo_state.balance = Some(
+            data["tokenAccount"]
+                .as_array()
+                .map(|accounts| accounts.iter().filter_map(|a| get_u64(&a["amount"])).sum())
+                .unwrap_or_default(),
+        );
+        promo_state.uses = Some(
+            data["redeemPromoToken"]
+                .as_array()
+                .map(|redemptions| redemptions.len() as u64)
+                .unwrap_or_default(),
+        );
+    }
+
+    Ok(promo_state)
+}
+
+/// Renders metadata json from a template and the state of the promo.
+///
+/// The template is the metadata uploaded when the promo was created. Its optional `tiers` are
+/// objects with a `name` and minimums for any of the state values, e.g. `minBalance` or
+/// `minUses`. The last tier whose minimums are all met overrides the `image`, `animation_url`
+/// and `description` of the template, and is added as the `tier` attribute. The state values
+/// are added as attributes, and `{balance}` style placeholders in the name and description
+/// are replaced with them.
+pub fn render_metadata(
+    template: &Map<String, Value>,
+    promo_state: &PromoState,
+) -> Result<Map<String, Value>, AppError> {
+    let mut metadata = template.clone();
+    let values = promo_state.values();
+
+    let tiers = match metadata.remove("tiers") {
+        None => vec![],
+        Some(Value::Array(tiers)) => tiers,
+        Some(_) => {
+            return Err(AppError::MetadataRenderError(
+                "tiers should be an array".to_string(),
+            ))
+        }
+    };
+
+    let mut current_tier = None;
+    for tier in tiers.iter() {
+        let tier = tier.as_object().ok_or(AppError::MetadataRenderError(
+            "tiers should be objects".to_string(),
+        ))?;
+        let met = tier
+            .iter()
+            .filter_map(|(k, v)| Some((k.strip_prefix("min")?, v)))
+            .all(|(name, min)| {
+                let min = get_u64(min).unwrap_or(u64::MAX);
+                values
+                    .iter()
+                    .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| *value >= min)
+                    .unwrap_or_default()
+            });
+        if met {
+            current_tier = Some(tier);
+        }
+    }
+
+    let mut attributes = match metadata.remove("attributes") {
+        Some(Value::Array(attributes)) => attributes,
+        _ => vec![],
+    };
+
+    if let Some(tier) = current_tier {
+        for field in ["image", "animation_url", "description"] {
+            if let Some(value) = tier.get(field) {
+                metadata.insert(field.to_string(), value.clone());
+            }
+        }
+        if let (Some(image), Some(Value::Object(properties))) =
+            (tier.get("image"), metadata.get_mut("properties"))
+        {
+            if let Some(Value::Array(files)) = properties.get_mut("files") {
+                if let Some(Value::Object(file)) = files.first_mut() {
+                    file.insert("uri".to_string(), image.clone());
+                }
+            }
+        }
+        if let Some(name) = tier.get("name") {
+            attributes.push(json!({ "trait_type": "tier", "value": name }));
+        }
+    }
+
+    for (name, value) in values.iter() {
+        attributes.push(json!({ "trait_type": name, "value": value }));
+    }
+    metadata.insert("attributes".to_string(), attributes.into());
+
+    for field in ["name", "description"] {
+        if let Some(Value::String(text)) = metadata.get_mut(field) {
+            for (name, value) in values.iter() {
+                *text = text.replace(&format!("{{{}}}", name), &value.to_string());
+            }
+        }
+    }
+
+    Ok(metadata)
+}
@@ -1,4 +1,8 @@
+pub mod allowlist;
 pub mod bundlr;
+pub mod customer_vault;
 pub mod data;
+pub mod metadata;
 pub mod multipart;
 pub mod solana;
+pub mod transactions;
@@ -1,6 +1,15 @@
 use crate::error::AppError;
+use anchor_lang::prelude::Pubkey;
 use axum::extract::Multipart;
+use bpl_token_metadata::{
+    state::{
+        GiftPolicy, PromoExtensions, PromoSchedule, PromoTerms, ReferralReward, TimeWindow,
+        MAX_PRODUCT_ID_LENGTH, MAX_SCHEDULE_WINDOWS,
+    },
+    utils::MAX_NAME_LENGTH,
+};
 use serde_json::{Map, Value};
+use std::str::FromStr;
 
 /// Returns metadata and image data if image is a multipart field.
 pub async fn get_metadata(
@@ -51,6 +60,14 @@ pub fn get_args(metadata_data_obj: &mut Map<String, Value>) -> Result<(String, b
         ))?
         .to_string();
 
+    // Names are stored as utf-8, so the limit is in bytes rather than characters.
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(AppError::MultipartMetadataError(format!(
+            "name should be between 1 and {} bytes",
+            MAX_NAME_LENGTH
+        )));
+    }
+
     let active = metadata_data_obj["active"]
         .as_bool()
         .ok_or(AppError::MultipartMetadataError(
@@ -60,9 +77,137 @@ pub fn get_args(metadata_data_obj: &mut Map<String, Value>) -> Result<(String, b
     Ok((name, active))
 }
 
+/// Memo for the transaction from the `memo` field of the metadata json, so multipart requests
+/// don't need to pass it in the url. The field is removed so it isn't uploaded with the
+/// metadata. A `memo` argument takes precedence.
+pub fn get_memo_args(
+    metadata_data_obj: &mut Map<String, Value>,
+    memo: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let field = match metadata_data_obj.remove("memo") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(field)) => Some(field),
+        Some(_) => {
+            return Err(AppError::MultipartMetadataError(
+                "memo field should be a string".to_string(),
+            ))
+        }
+    };
+
+    Ok(memo.or(field))
+}
+
+/// Returns the name, symbol, pass duration in seconds and max supply of a membership. The
+/// duration is read from the `durationDays` attribute and the max supply from the optional
+/// `maxSupply` attribute.
+pub fn get_membership_args(
+    metadata_data_obj: &Map<String, Value>,
+) -> Result<(String, String, i64, Option<u64>), AppError> {
+    let get_field = |name: &str| {
+        metadata_data_obj
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(ToString::to_string)
+            .ok_or(AppError::MultipartMetadataError(format!(
+                "{} field should exist",
+                name
+            )))
+    };
+    let name = get_field("name")?;
+    let symbol = get_field("symbol")?;
+
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(AppError::MultipartMetadataError(format!(
+            "name should be between 1 and {} bytes",
+            MAX_NAME_LENGTH
+        )));
+    }
+
+    let attributes = metadata_data_obj
+        .get("attributes")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let get_attribute = |name: &str| {
+        attributes.iter().find_map(|a| {
+            let attribute = a.as_object()?;
+            if attribute.get("trait_type")? == name {
+                attribute.get("value").cloned()
+            } else {
+                None
+            }
+        })
+    };
+
+    let duration = get_attribute("durationDays")
+        .and_then(|v| v.as_i64())
+        .filter(|days| *days > 0)
+        .ok_or(AppError::MultipartMetadataError(
+            "durationDays attribute should be a positive integer".to_string(),
+        ))?
+        * 24
+        * 60
+        * 60;
+
+    let max_supply = get_attribute("maxSupply")
+        .map(|v| {
+            v.as_u64().ok_or(AppError::MultipartMetadataError(
+                "maxSupply should be a positive integer".to_string(),
+            ))
+        })
+        .transpose()?;
+
+    Ok((name, symbol, duration, max_supply))
+}
+
+/// Returns campaign-wide max_mint and max_burn if `maxMint` and `maxBurn` attributes exist in
+/// json data.
+pub fn get_campaign_args(
+    metadata_data_obj: &Map<String, Value>,
+) -> Result<(Option<u32>, Option<u32>), AppError> {
+    let attributes = match metadata_data_obj.get("attributes").map(|v| v.as_array()) {
+        Some(Some(attributes)) => attributes,
+        _ => return Ok((None, None)),
+    };
+
+    let get_limit = |name: &str| {
+        attributes
+            .iter()
+            .find_map(|a| {
+                let attribute = a.as_object()?;
+                if attribute.get("trait_type")? == name {
+                    attribute.get("value")
+                } else {
+                    None
+                }
+            })
+            .map(|v| {
+                v.as_u64().and_then(|v| u32::try_from(v).ok()).ok_or(
+                    AppError::MultipartMetadataError(format!(
+                        "{} should be a positive integer",
+                        name
+                    )),
+                )
+            })
+            .transpose()
+    };
+
+    Ok((get_limit("maxMint")?, get_limit("maxBurn")?))
+}
+
 pub fn get_promo_args(
     metadata_data_obj: &mut Map<String, Value>,
-) -> Result<(String, String, Option<u32>, Option<u32>, bool), AppError> {
+) -> Result<
+    (
+        String,
+        String,
+        Option<u32>,
+        Option<u32>,
+        bool,
+        Option<PromoSchedule>,
+    ),
+    AppError,
+> {
     let name = metadata_data_obj["name"]
         .as_str()
         .ok_or(AppError::CreatePromoRequestError(
@@ -83,8 +228,8 @@ pub fn get_promo_args(
             "active field should exist".to_string(),
         ))?;
 
-    // Return max_mint and max_burn if attributes exists in json data.
-    let (max_mint, max_burn) = if let Some(value) = metadata_data_obj.get("attributes") {
+    // Return max_mint, max_burn and schedule if attributes exists in json data.
+    let (max_mint, max_burn, schedule) = if let Some(value) = metadata_data_obj.get("attributes") {
         if let Some(attributes) = value.as_array() {
             let max_mint: Option<u32> = attributes
                 .iter()
@@ -122,12 +267,406 @@ pub fn get_promo_args(
                 .first()
                 .map(|v| v.clone() as u32);
 
-            (max_mint, max_burn)
+            let schedule = get_promo_schedule(attributes)?;
+
+            (max_mint, max_burn, schedule)
         } else {
-            (None, None)
+            (None, None, None)
         }
     } else {
-        (None, None)
+        (None, None, None)
+    };
+    Ok((name, symbol, max_mint, max_burn, active, schedule))
+}
+
+/// Returns a promo schedule if any of the `scheduleDays`, `scheduleWindows`,
+/// `scheduleUtcOffset` or `scheduleOnMint` attributes exist.
+///
+/// Days are comma separated, e.g. `Mon,Tue`, windows are comma separated `HH:MM-HH:MM` in
+/// local time and the offset is `+HH:MM` or `-HH:MM` from UTC.
+fn get_promo_schedule(attributes: &[Value]) -> Result<Option<PromoSchedule>, AppError> {
+    let get_attribute = |name: &str| {
+        attributes.iter().find_map(|a| {
+            let attribute = a.as_object()?;
+            if attribute.get("trait_type")? == name {
+                attribute.get("value")
+            } else {
+                None
+            }
+        })
+    };
+
+    let days = get_attribute("scheduleDays");
+    let windows = get_attribute("scheduleWindows");
+    let utc_offset = get_attribute("scheduleUtcOffset");
+    let check_mint = get_attribute("scheduleOnMint");
+
+    if days.is_none() && windows.is_none() && utc_offset.is_none() && check_mint.is_none() {
+        return Ok(None);
+    }
+
+    let schedule_error =
+        |message: &str| AppError::CreatePromoRequestError(format!("schedule: {}", message));
+
+    let days = match days {
+        None => 0b0111_1111,
+        Some(value) => {
+            let value = value
+                .as_str()
+                .ok_or_else(|| schedule_error("scheduleDays should be a string"))?;
+            let mut days = 0u8;
+            for day in value.split(',') {
+                let bit = match day.trim() {
+                    "Sun" => 0,
+                    "Mon" => 1,
+                    "Tue" => 2,
+                    "Wed" => 3,
+                    "Thu" => 4,
+                    "Fri" => 5,
+                    "Sat" => 6,
+                    day => return Err(schedule_error(&format!("invalid day {}", day))),
+                };
+                days |= 1 << bit;
+            }
+            days
+        }
     };
-    Ok((name, symbol, max_mint, max_burn, active))
+
+    let windows = match windows {
+        None => vec![],
+        Some(value) => {
+            let value = value
+                .as_str()
+                .ok_or_else(|| schedule_error("scheduleWindows should be a string"))?;
+            let windows = value
+                .split(',')
+                .map(|window| {
+                    let (start, end) = window
+                        .trim()
+                        .split_once('-')
+                        .ok_or_else(|| schedule_error(&format!("invalid window {}", window)))?;
+                    let time_window = TimeWindow {
+                        start: parse_minutes(start)
+                            .filter(|m| *m < 24 * 60)
+                            .ok_or_else(|| schedule_error(&format!("invalid time {}", start)))?,
+                        end: parse_minutes(end)
+                            .ok_or_else(|| schedule_error(&format!("invalid time {}", end)))?,
+                    };
+                    if time_window.start == time_window.end {
+                        return Err(schedule_error(&format!("empty window {}", window)));
+                    }
+                    Ok(time_window)
+                })
+                .collect::<Result<Vec<TimeWindow>, AppError>>()?;
+            if windows.len() > MAX_SCHEDULE_WINDOWS {
+                return Err(schedule_error(&format!(
+                    "no more than {} windows allowed",
+                    MAX_SCHEDULE_WINDOWS
+                )));
+            }
+            windows
+        }
+    };
+
+    let utc_offset = match utc_offset {
+        None => 0,
+        Some(value) => {
+            let value = value
+                .as_str()
+                .ok_or_else(|| schedule_error("scheduleUtcOffset should be a string"))?;
+            let (sign, offset) = match value.split_at(value.len().min(1)) {
+                ("+", offset) => (1, offset),
+                ("-", offset) => (-1, offset),
+                _ => return Err(schedule_error(&format!("invalid offset {}", value))),
+            };
+            let minutes = parse_minutes(offset)
+                .filter(|m| *m <= 14 * 60)
+                .ok_or_else(|| schedule_error(&format!("invalid offset {}", value)))?;
+            sign * minutes as i16
+        }
+    };
+
+    let check_mint = match check_mint {
+        None => false,
+        Some(value) => value
+            .as_bool()
+            .ok_or_else(|| schedule_error("scheduleOnMint should be a boolean"))?,
+    };
+
+    Ok(Some(PromoSchedule {
+        days,
+        windows,
+        utc_offset,
+        check_mint,
+    }))
+}
+
+/// Returns the referral reward if the `referralRewardMint` attribute exists, with the max number
+/// of rewards from the optional `maxReferralRewards` attribute.
+pub fn get_promo_referral_args(
+    metadata_data_obj: &Map<String, Value>,
+) -> Result<Option<ReferralReward>, AppError> {
+    let attributes = match metadata_data_obj.get("attributes").map(|v| v.as_array()) {
+        Some(Some(attributes)) => attributes,
+        _ => return Ok(None),
+    };
+
+    let get_attribute = |name: &str| {
+        attributes.iter().find_map(|a| {
+            let attribute = a.as_object()?;
+            if attribute.get("trait_type")? == name {
+                attribute.get("value")
+            } else {
+                None
+            }
+        })
+    };
+
+    let reward_mint = match get_attribute("referralRewardMint") {
+        Some(value) => value
+            .as_str()
+            .and_then(|v| Pubkey::from_str(v).ok())
+            .ok_or(AppError::CreatePromoRequestError(
+                "referralRewardMint should be a mint address".to_string(),
+            ))?,
+        None => return Ok(None),
+    };
+
+    let max_rewards = get_attribute("maxReferralRewards")
+        .map(|value| {
+            value.as_u64().and_then(|v| u32::try_from(v).ok()).ok_or(
+                AppError::CreatePromoRequestError(
+                    "maxReferralRewards should be a positive integer".to_string(),
+                ),
+            )
+        })
+        .transpose()?;
+
+    Ok(Some(ReferralReward {
+        reward_mint,
+        max_rewards,
+        reward_count: 0,
+    }))
+}
+
+/// Returns the number of stamps needed for a stamp card reward if the `stampThreshold`
+/// attribute exists.
+pub fn get_promo_stamp_card_args(
+    metadata_data_obj: &Map<String, Value>,
+) -> Result<Option<u32>, AppError> {
+    let attributes = match metadata_data_obj.get("attributes").map(|v| v.as_array()) {
+        Some(Some(attributes)) => attributes,
+        _ => return Ok(None),
+    };
+
+    attributes
+        .iter()
+        .find_map(|a| {
+            let attribute = a.as_object()?;
+            if attribute.get("trait_type")? == "stampThreshold" {
+                attribute.get("value")
+            } else {
+                None
+            }
+        })
+        .map(|v| {
+            v.as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .filter(|v| *v > 0)
+                .ok_or(AppError::CreatePromoRequestError(
+                    "stampThreshold should be a positive integer".to_string(),
+                ))
+        })
+        .transpose()
+}
+
+/// Returns whether customers have to request mints for approval by a campaign device, from the
+/// optional `requiresApproval` field.
+pub fn get_promo_approval_args(metadata_data_obj: &Map<String, Value>) -> Result<bool, AppError> {
+    match metadata_data_obj.get("requiresApproval") {
+        None => Ok(false),
+        Some(value) => value.as_bool().ok_or(AppError::CreatePromoRequestError(
+            "requiresApproval should be a boolean".to_string(),
+        )),
+    }
+}
+
+/// Returns the gift policy from the optional `giftPolicy` field, one of `disallowed`, `allowed`
+/// or `allowedOnce`. Promo tokens can't be gifted if it's missing.
+pub fn get_promo_gift_policy_args(
+    metadata_data_obj: &Map<String, Value>,
+) -> Result<GiftPolicy, AppError> {
+    match metadata_data_obj.get("giftPolicy").map(|v| v.as_str()) {
+        None => Ok(GiftPolicy::Disallowed),
+        Some(Some("disallowed")) => Ok(GiftPolicy::Disallowed),
+        Some(Some("allowed")) => Ok(GiftPolicy::Allowed),
+        Some(Some("allowedOnce")) => Ok(GiftPolicy::AllowedOnce),
+        Some(_) => Err(AppError::CreatePromoRequestError(
+            "giftPolicy should be one of disallowed, allowed or allowedOnce".to_string(),
+        )),
+    }
+}
+
+/// Returns whether the promo has dynamic metadata from the optional `dynamic` field. Dynamic
+/// promos can have `tiers` that are applied when their metadata is rendered.
+pub fn get_promo_dynamic_args(metadata_data_obj: &Map<String, Value>) -> Result<bool, AppError> {
+    let dynamic = match metadata_data_obj.get("dynamic") {
+        None => false,
+        Some(Value::Bool(dynamic)) => *dynamic,
+        Some(_) => {
+            return Err(AppError::CreatePromoRequestError(
+                "dynamic should be a boolean".to_string(),
+            ))
+        }
+    };
+
+    match metadata_data_obj.get("tiers") {
+        None => Ok(dynamic),
+        Some(_) if !dynamic => Err(AppError::CreatePromoRequestError(
+            "tiers should only be included with dynamic metadata".to_string(),
+        )),
+        Some(Value::Array(tiers)) if tiers.iter().all(|t| t.get("name").is_some()) => Ok(true),
+        Some(_) => Err(AppError::CreatePromoRequestError(
+            "tiers should be an array of objects with names".to_string(),
+        )),
+    }
+}
+
+/// Returns the promo terms for the `promoType` attribute, read from the attributes of that type,
+/// e.g. `productId`, `buyXProduct` and `getYProduct` for `buyXProductGetYFree`. Returns `None`
+/// if there is no `promoType` or it's `loyalty`, which isn't stored on chain.
+pub fn get_promo_terms_args(
+    metadata_data_obj: &Map<String, Value>,
+) -> Result<Option<PromoTerms>, AppError> {
+    let attributes = match metadata_data_obj.get("attributes").map(|v| v.as_array()) {
+        Some(Some(attributes)) => attributes,
+        _ => return Ok(None),
+    };
+
+    let get_attribute = |name: &str| {
+        attributes.iter().find_map(|a| {
+            let attribute = a.as_object()?;
+            if attribute.get("trait_type")? == name {
+                attribute.get("value")
+            } else {
+                None
+            }
+        })
+    };
+
+    let terms_error =
+        |message: String| AppError::CreatePromoRequestError(format!("terms: {}", message));
+    let get_integer = |name: &str| {
+        get_attribute(name)
+            .ok_or_else(|| terms_error(format!("{} should exist", name)))?
+            .as_u64()
+            .ok_or_else(|| terms_error(format!("{} should be a positive integer", name)))
+    };
+    let get_product_id = || {
+        get_attribute("productId")
+            .and_then(|v| v.as_str())
+            .map(ToString::to_string)
+            .ok_or_else(|| terms_error("productId should be a string".to_string()))
+    };
+
+    let terms = match get_attribute("promoType").map(|v| v.as_str()) {
+        None | Some(Some("loyalty")) => return Ok(None),
+        Some(Some("buyXProductGetYFree")) => PromoTerms::BuyXProductGetYFree {
+            product_id: get_product_id()?,
+            buy_x_product: u32::try_from(get_integer("buyXProduct")?)
+                .map_err(|_| terms_error("buyXProduct is too large".to_string()))?,
+            get_y_product: u32::try_from(get_integer("getYProduct")?)
+                .map_err(|_| terms_error("getYProduct is too large".to_string()))?,
+        },
+        Some(Some("buyXCurrencyGetYPercent")) => PromoTerms::BuyXCurrencyGetYPercent {
+            buy_x_currency: get_integer("buyXCurrency")?,
+            get_y_percent: u8::try_from(get_integer("getYPercent")?)
+                .map_err(|_| terms_error("getYPercent should be at most 100".to_string()))?,
+        },
+        Some(Some("amountOff")) => PromoTerms::AmountOff {
+            buy_x_currency: match get_attribute("buyXCurrency") {
+                Some(_) => get_integer("buyXCurrency")?,
+                None => 0,
+            },
+            amount_off: get_integer("amountOff")?,
+        },
+        Some(Some("freeItem")) => PromoTerms::FreeItem {
+            product_id: get_product_id()?,
+        },
+        _ => {
+            return Err(terms_error(
+                "promoType should be one of buyXProductGetYFree, buyXCurrencyGetYPercent, \
+                amountOff, freeItem or loyalty"
+                    .to_string(),
+            ))
+        }
+    };
+
+    // Same checks as the program so the request fails before anything is uploaded.
+    if !terms.is_valid() {
+        return Err(terms_error(format!(
+            "invalid values, product ids should be 1 to {} bytes, quantities and amounts \
+            positive and percents between 1 and 100",
+            MAX_PRODUCT_ID_LENGTH
+        )));
+    }
+
+    Ok(Some(terms))
+}
+
+/// Returns minutes from midnight for a `HH:MM` time, allowing `24:00` as the end of the day.
+fn parse_minutes(time: &str) -> Option<u16> {
+    let (hours, minutes) = time.trim().split_once(':')?;
+    let (hours, minutes) = (hours.parse::<u16>().ok()?, minutes.parse::<u16>().ok()?);
+    if minutes >= 60 || hours * 60 + minutes > 24 * 60 {
+        return None;
+    }
+    Some(hours * 60 + minutes)
+}
+
+/// Returns the token program and Token-2022 extensions for a promo mint.
+///
+/// `tokenProgram` is either `token` (default) or `token2022` and `extensions` is an optional
+/// list of `nonTransferable`, `permanentDelegate` and `metadataPointer`. Extensions can only be
+/// used with `token2022`.
+pub fn get_promo_token_args(
+    metadata_data_obj: &mut Map<String, Value>,
+) -> Result<(Pubkey, PromoExtensions), AppError> {
+    let token_program = match metadata_data_obj.get("tokenProgram").map(|v| v.as_str()) {
+        None | Some(Some("token")) => anchor_spl::token::ID,
+        Some(Some("token2022")) => anchor_spl::token_2022::ID,
+        _ => {
+            return Err(AppError::CreatePromoRequestError(
+                "tokenProgram should be either token or token2022".to_string(),
+            ))
+        }
+    };
+
+    let mut extensions = PromoExtensions::default();
+    if let Some(value) = metadata_data_obj.get("extensions") {
+        let values = value.as_array().ok_or(AppError::CreatePromoRequestError(
+            "extensions should be an array".to_string(),
+        ))?;
+        for value in values {
+            match value.as_str() {
+                Some("nonTransferable") => extensions.non_transferable = true,
+                Some("permanentDelegate") => extensions.permanent_delegate = true,
+                Some("metadataPointer") => extensions.metadata_pointer = true,
+                _ => {
+                    return Err(AppError::CreatePromoRequestError(format!(
+                        "unsupported extension: {}",
+                        value
+                    )))
+                }
+            }
+        }
+    }
+
+    if !extensions.is_empty() && token_program != anchor_spl::token_2022::ID {
+        return Err(AppError::CreatePromoRequestError(
+            "extensions require tokenProgram token2022".to_string(),
+        ));
+    }
+
+    Ok((token_program, extensions))
 }
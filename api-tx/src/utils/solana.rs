@@ -1,483 +1,13 @@
 use crate::error::AppError;
-use anchor_lang::{
-    prelude::Pubkey,
-    InstructionData, ToAccountMetas,
-    {
-        solana_program::{instruction::Instruction, sysvar},
-        system_program,
-    },
-};
-use bpl_token_metadata::{
-    accounts::{
-        BurnDelegatedPromoToken as burn_delegated_promo_token_accounts,
-        CreateCampaign as create_campaign_accounts,
-        CreateCampaignLocation as create_campaign_location_accounts,
-        CreateDevice as create_device_accounts, CreateLocation as create_location_accounts,
-        CreateMerchant as create_merchant_accounts, CreatePromo as create_promo_accounts,
-        DelegatePromoToken as delegate_promo_token_accounts,
-        MintPromoToken as mint_promo_token_accounts, SignMemo as sign_memo_accounts,
-    },
-    instruction::{
-        BurnDelegatedPromoToken as burn_delegated_promo_token_instruction,
-        CreateCampaign as create_campaign_instruction,
-        CreateCampaignLocation as create_campaign_location_instruction,
-        CreateDevice as create_device_instruction, CreateLocation as create_location_instruction,
-        CreateMerchant as create_merchant_instruction, CreatePromo as create_promo_instruction,
-        DelegatePromoToken as delegate_promo_token_instruction,
-        MintPromoToken as mint_promo_token_instruction, SignMemo as sign_memo_instruction,
-    },
-    state::{Campaign, DataV2, Device, Location, Merchant, Promo},
-    utils::{
-        find_admin_address, find_associated_token_address, find_authority_address,
-        find_campaign_address, find_campaign_location_address, find_device_address,
-        find_location_address, find_merchant_address, find_metadata_address, find_promo_address,
-    },
-};
+use anchor_lang::prelude::Pubkey;
+use async_trait::async_trait;
+use bpl_token_metadata_client::{accounts::AccountFetcher, ClientError};
 use serde::{Deserialize, Serialize};
 
 use serde_json::{json, Value};
 use solana_sdk::{commitment_config::CommitmentLevel, hash::Hash};
 use std::str::FromStr;
 
-pub fn create_merchant_instruction(
-    payer: Pubkey,
-    owner: Pubkey,
-    name: String,
-    uri: String,
-    active: bool,
-    memo: Option<String>,
-) -> Result<Instruction, AppError> {
-    let merchant = find_merchant_address(&owner).0;
-
-    let data = Merchant {
-        owner,
-        name,
-        uri,
-        active,
-    };
-
-    let accounts = create_merchant_accounts {
-        payer,
-        owner,
-        merchant,
-        memo_program: spl_memo::ID,
-        rent: sysvar::rent::id(),
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = create_merchant_instruction { data, memo }.data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
-pub fn create_location_instruction(
-    payer: Pubkey,
-    owner: Pubkey,
-    name: String,
-    uri: String,
-    active: bool,
-    memo: Option<String>,
-) -> Result<Instruction, AppError> {
-    let (merchant, _) = find_merchant_address(&owner);
-    let (location, _) = find_location_address(&owner, &name);
-
-    let data = Location {
-        merchant,
-        name,
-        uri,
-        active,
-    };
-
-    let accounts = create_location_accounts {
-        payer,
-        owner,
-        merchant,
-        location,
-        memo_program: spl_memo::ID,
-        rent: sysvar::rent::id(),
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = create_location_instruction { data, memo }.data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
-pub fn create_device_instruction(
-    payer: Pubkey,
-    merchant_owner: Pubkey,
-    location: Pubkey,
-    owner: Pubkey,
-    name: String,
-    uri: String,
-    active: bool,
-    memo: Option<String>,
-) -> Result<Instruction, AppError> {
-    let (merchant, _) = find_merchant_address(&merchant_owner);
-    let (device, _) = find_device_address(&location, &name);
-
-    let data = Device {
-        owner,
-        location,
-        name,
-        uri,
-        active,
-    };
-
-    let accounts = create_device_accounts {
-        payer,
-        merchant_owner,
-        merchant,
-        location,
-        device,
-        memo_program: spl_memo::ID,
-        rent: sysvar::rent::id(),
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = create_device_instruction { data, memo }.data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
-pub fn create_campaign_instruction(
-    payer: Pubkey,
-    owner: Pubkey,
-    name: String,
-    uri: String,
-    lamports: u64,
-    locations: Vec<Pubkey>,
-    active: bool,
-    memo: Option<String>,
-) -> Result<Vec<Instruction>, AppError> {
-    let merchant = find_merchant_address(&owner).0;
-    let campaign = find_campaign_address(&merchant, &name).0;
-
-    let data = Campaign {
-        merchant,
-        name,
-        uri,
-        active,
-    };
-
-    let accounts = create_campaign_accounts {
-        payer,
-        owner,
-        merchant,
-        campaign,
-        memo_program: spl_memo::ID,
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = create_campaign_instruction {
-        data,
-        lamports,
-        memo,
-    }
-    .data();
-
-    let mut instructions: Vec<Instruction> = Vec::new();
-
-    instructions.push(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    });
-
-    // Create campaign location instructions.
-    for location in locations {
-        let ix = create_campaign_location_instruction(payer, owner, campaign, location, None)?;
-        instructions.push(ix);
-    }
-
-    Ok(instructions)
-}
-
-pub fn create_campaign_location_instruction(
-    payer: Pubkey,
-    owner: Pubkey,
-    campaign: Pubkey,
-    location: Pubkey,
-    memo: Option<String>,
-) -> Result<Instruction, AppError> {
-    let merchant = find_merchant_address(&owner).0;
-    let campaign_location = find_campaign_location_address(&campaign, &location).0;
-
-    let accounts = create_campaign_location_accounts {
-        payer,
-        owner,
-        merchant,
-        campaign,
-        campaign_location,
-        location,
-        memo_program: spl_memo::ID,
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = create_campaign_location_instruction { memo }.data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
-pub fn create_promo_instruction(
-    payer: Pubkey,
-    owner: Pubkey,
-    campaign: Pubkey,
-    mint: Pubkey,
-    platform: Pubkey,
-    name: String,
-    symbol: String,
-    uri: String,
-    max_mint: Option<u32>,
-    max_burn: Option<u32>,
-    active: bool,
-    is_mutable: bool,
-    memo: Option<String>,
-) -> Result<Instruction, AppError> {
-    let authority = find_authority_address().0;
-    let promo = find_promo_address(&mint).0;
-    let metadata = find_metadata_address(&mint).0;
-    let admin_settings = find_admin_address().0;
-    let merchant = find_merchant_address(&owner).0;
-
-    let accounts = create_promo_accounts {
-        payer,
-        owner,
-        merchant,
-        campaign,
-        mint,
-        metadata,
-        authority,
-        promo,
-        platform,
-        admin_settings,
-        metadata_program: mpl_token_metadata::ID,
-        token_program: anchor_spl::token::ID,
-        memo_program: spl_memo::ID,
-        rent: sysvar::rent::id(),
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let promo_data = Promo {
-        campaign,
-        mint,
-        metadata,
-        mint_count: 0,
-        burn_count: 0,
-        max_mint,
-        max_burn,
-        active,
-    };
-
-    let metadata_data = DataV2 {
-        name,
-        symbol,
-        uri,
-        seller_fee_basis_points: 0,
-        creators: None,
-        collection: None,
-        uses: None,
-    };
-
-    let data = create_promo_instruction {
-        promo_data,
-        metadata_data,
-        is_mutable,
-        memo,
-    }
-    .data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
-pub fn mint_promo_instruction(
-    payer: Pubkey,
-    device_owner: Pubkey,
-    device: Pubkey,
-    location: Pubkey,
-    campaign: Pubkey,
-    token_owner: Pubkey,
-    mint: Pubkey,
-    memo: Option<String>,
-) -> Result<Instruction, AppError> {
-    let authority = find_authority_address().0;
-    let promo = find_promo_address(&mint).0;
-    let token_account = find_associated_token_address(&token_owner, &mint);
-    let campaign_location = find_campaign_location_address(&campaign, &location).0;
-
-    tracing::debug!(
-        device_owner = device_owner.to_string(),
-        device = device.to_string(),
-        lcoation = location.to_string(),
-        campaign = campaign.to_string(),
-        campaign_location = campaign_location.to_string(),
-        token_owner = token_owner.to_string(),
-        token_account = token_account.to_string(),
-        mint = mint.to_string(),
-        memo = memo.clone().unwrap_or("".to_string())
-    );
-
-    let accounts = mint_promo_token_accounts {
-        payer,
-        device_owner,
-        device,
-        campaign,
-        campaign_location,
-        token_owner,
-        mint,
-        authority,
-        promo,
-        token_account,
-        token_program: anchor_spl::token::ID,
-        memo_program: spl_memo::ID,
-        associated_token_program: anchor_spl::associated_token::ID,
-        rent: sysvar::rent::id(),
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = mint_promo_token_instruction { memo }.data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
-pub fn delegate_promo_instruction(
-    payer: Pubkey,
-    device_owner: Pubkey,
-    device: Pubkey,
-    campaign: Pubkey,
-    location: Pubkey,
-    token_owner: Pubkey,
-    mint: Pubkey,
-    memo: Option<String>,
-) -> Result<Instruction, AppError> {
-    let promo = find_promo_address(&mint).0;
-    let token_account = find_associated_token_address(&token_owner, &mint);
-    let campaign_location = find_campaign_location_address(&campaign, &location).0;
-
-    let accounts = delegate_promo_token_accounts {
-        payer,
-        device_owner,
-        device,
-        campaign,
-        campaign_location,
-        token_owner,
-        mint,
-        promo,
-        token_account,
-        memo_program: spl_memo::ID,
-        token_program: anchor_spl::token::ID,
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = delegate_promo_token_instruction { memo }.data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
-pub fn burn_delegated_promo_instruction(
-    payer: Pubkey,
-    device_owner: Pubkey,
-    device: Pubkey,
-    location: Pubkey,
-    campaign: Pubkey,
-    token_account: Pubkey,
-    mint: Pubkey,
-    platform: Pubkey,
-    memo: Option<String>,
-) -> Result<Instruction, AppError> {
-    let authority = find_authority_address().0;
-    let promo = find_promo_address(&mint).0;
-    let admin_settings = find_admin_address().0;
-    let campaign_location = find_campaign_location_address(&campaign, &location).0;
-    // let token_account = find_associated_token_address(&token_owner, &mint);
-
-    let accounts = burn_delegated_promo_token_accounts {
-        payer,
-        device_owner,
-        device,
-        campaign,
-        campaign_location,
-        mint,
-        authority,
-        promo,
-        platform,
-        admin_settings,
-        token_account,
-        memo_program: spl_memo::ID,
-        token_program: anchor_spl::token::ID,
-        associated_token_program: anchor_spl::associated_token::ID,
-        rent: sysvar::rent::id(),
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = burn_delegated_promo_token_instruction { memo }.data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
-pub fn create_sign_memo_instruction(
-    payer: Pubkey,
-    memo: String,
-    signer: Pubkey,
-) -> Result<Instruction, AppError> {
-    let accounts = sign_memo_accounts {
-        payer,
-        signer,
-        memo_program: spl_memo::ID,
-        system_program: system_program::ID,
-    }
-    .to_account_metas(Some(true));
-
-    let data = sign_memo_instruction { memo }.data();
-
-    Ok(Instruction {
-        program_id: bpl_token_metadata::id(),
-        accounts,
-        data,
-    })
-}
-
 // Needed to do this since nonblocking client not avaiable in 1.14.18.
 pub struct Solana {
     pub cluster: Cluster,
@@ -624,6 +154,73 @@ impl Solana {
         Ok(result["result"].as_str().unwrap().to_string())
     }
 
+    /// Returns the token program that owns a promo mint.
+    ///
+    /// Defaults to spl-token if the mint isn't found, in which case the transaction will fail
+    /// when it is submitted.
+    pub async fn get_token_program(&self, mint: &Pubkey) -> Result<Pubkey, AppError> {
+        let mut config = serde_json::Map::new();
+        config.insert(
+            "commitment".to_string(),
+            Value::String(self.commitment.to_string()),
+        );
+        config.insert("encoding".to_string(), json!("base64"));
+
+        let post_object = PostObject {
+            method: String::from("getAccountInfo"),
+            params: vec![json!(mint.to_string()), Value::Object(config)],
+            ..Default::default()
+        };
+
+        let result: Value = self
+            .client
+            .post(self.cluster.url())
+            .json(&post_object)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match result["result"]["value"]["owner"].as_str() {
+            Some(owner) if owner == anchor_spl::token_2022::ID.to_string() => {
+                Ok(anchor_spl::token_2022::ID)
+            }
+            _ => Ok(anchor_spl::token::ID),
+        }
+    }
+
+    /// Returns account data or `None` if the account isn't found.
+    pub async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, AppError> {
+        let mut config = serde_json::Map::new();
+        config.insert(
+            "commitment".to_string(),
+            Value::String(self.commitment.to_string()),
+        );
+        config.insert("encoding".to_string(), json!("base64"));
+
+        let post_object = PostObject {
+            method: String::from("getAccountInfo"),
+            params: vec![json!(address.to_string()), Value::Object(config)],
+            ..Default::default()
+        };
+
+        let result: Value = self
+            .client
+            .post(self.cluster.url())
+            .json(&post_object)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match result["result"]["value"]["data"][0].as_str() {
+            Some(data) => Ok(Some(base64::decode(data).map_err(|e| {
+                AppError::GenericError(format!("account data not valid base64: {}", e))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
     /// Returns wallet balance.
     pub async fn get_balance(&self, address: &Pubkey) -> Result<u64, AppError> {
         let client = reqwest::Client::new();
@@ -651,6 +248,15 @@ impl Solana {
     }
 }
 
+#[async_trait]
+impl AccountFetcher for Solana {
+    async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        Solana::get_account_data(self, address)
+            .await
+            .map_err(|e| ClientError::AccountFetchError(e.to_string()))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PostObject {
     pub jsonrpc: String,
@@ -0,0 +1,85 @@
+use crate::{error::AppError, handlers::StepTransaction, State};
+use anchor_lang::prelude::Pubkey;
+use bpl_token_metadata_client::instructions::create_campaign_locations_instruction;
+use solana_sdk::{instruction::Instruction, packet::PACKET_DATA_SIZE, transaction::Transaction};
+
+fn fits(instructions: &[Instruction], payer: &Pubkey) -> Result<bool, AppError> {
+    let tx = Transaction::new_with_payer(instructions, Some(payer));
+    Ok(bincode::serialized_size(&tx)? <= PACKET_DATA_SIZE as u64)
+}
+
+/// Returns the transactions that link `locations` to a campaign, the first starting with
+/// `instructions`, e.g. the instruction creating the campaign. Each transaction links as many
+/// locations as fit within the transaction size limit, so they have to be submitted in order.
+pub fn get_campaign_location_transactions(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    campaign: &Pubkey,
+    instructions: Vec<Instruction>,
+    locations: &[Pubkey],
+    member: bool,
+) -> Result<Vec<Transaction>, AppError> {
+    let with_locations = |instructions: &[Instruction], locations: &[Pubkey]| {
+        let mut instructions = instructions.to_vec();
+        if !locations.is_empty() {
+            instructions.push(create_campaign_locations_instruction(
+                *payer, *owner, *campaign, locations, member, None,
+            ));
+        }
+        instructions
+    };
+
+    let mut transactions = vec![];
+    let mut instructions = instructions;
+    let mut chunk: Vec<Pubkey> = vec![];
+
+    for location in locations {
+        chunk.push(*location);
+        if fits(&with_locations(&instructions, &chunk), payer)? {
+            continue;
+        }
+
+        chunk.pop();
+        if instructions.is_empty() && chunk.is_empty() {
+            return Err(AppError::GenericError(
+                "campaign location instruction doesn't fit in a transaction".to_string(),
+            ));
+        }
+        transactions.push(Transaction::new_with_payer(
+            &with_locations(&instructions, &chunk),
+            Some(payer),
+        ));
+        instructions = vec![];
+        chunk = vec![*location];
+    }
+
+    if !instructions.is_empty() || !chunk.is_empty() {
+        transactions.push(Transaction::new_with_payer(
+            &with_locations(&instructions, &chunk),
+            Some(payer),
+        ));
+    }
+
+    Ok(transactions)
+}
+
+/// Signs each transaction as payer and serializes it with its step.
+pub async fn sign_step_transactions(
+    transactions: Vec<Transaction>,
+    state: &State,
+) -> Result<Vec<StepTransaction>, AppError> {
+    let latest_blockhash = state.solana.get_latest_blockhash().await?;
+
+    transactions
+        .into_iter()
+        .enumerate()
+        .map(|(step, mut tx)| {
+            tx.try_partial_sign(&[&state.platform_signer], latest_blockhash)?;
+            let serialized = bincode::serialize(&tx)?;
+            Ok(StepTransaction {
+                step,
+                transaction: base64::encode(serialized),
+            })
+        })
+        .collect()
+}
@@ -1,6 +1,9 @@
 #!/bin/bash
 # --update-secrets /keys/platform_signer/platform_signer-keypair.json=PLATFORM_SIGNER_KEYPAIR:1
 # moved PLATFORM_SIGNER_KEYPAIR to environment variable
+# CUSTOMER_VAULT_SALT and the twilio otp provider credentials are read from Secret Manager, so
+# the customer-vault-salt, twilio-account-sid, twilio-auth-token and twilio-verify-service-sid
+# secrets need to exist before deploying.
 
 SERVICE_NAME=demo-api-v2
 
@@ -9,4 +12,5 @@ docker push us-west1-docker.pkg.dev/bokoup/demo/$SERVICE_NAME
 gcloud beta run deploy $SERVICE_NAME --image us-west1-docker.pkg.dev/bokoup/demo/$SERVICE_NAME --platform managed --region us-west1 --allow-unauthenticated \
 --min-instances 1 \
 --update-env-vars RUST_LOG=DEBUG \
+--update-secrets CUSTOMER_VAULT_SALT=customer-vault-salt:latest,TWILIO_ACCOUNT_SID=twilio-account-sid:latest,TWILIO_AUTH_TOKEN=twilio-auth-token:latest,TWILIO_VERIFY_SERVICE_SID=twilio-verify-service-sid:latest \
 --service-account demo-bokoup@bokoup.iam.gserviceaccount.com
\ No newline at end of file
@@ -1,4 +1,4 @@
-use anchor_lang::prelude::Pubkey;
+use anchor_lang::{prelude::Pubkey, system_program};
 use serde::{Deserialize, Serialize};
 use solana_sdk::signature::Signature;
 
@@ -43,7 +43,11 @@ pub struct AccountData<'a> {
     pub write_version: u64,
 }
 
-pub async fn process<'a>(pg_client: deadpool_postgres::Object, message: MessageData<'a>) {
+pub async fn process<'a>(
+    pg_client: deadpool_postgres::Object,
+    token_accounts: &programs::spl_token::TokenAccountKeys,
+    message: MessageData<'a>,
+) {
     match message {
         MessageData::Account(message) => {
             if message.account.owner == programs::bpl_token_metadata::ID.as_ref() {
@@ -53,7 +57,14 @@ pub async fn process<'a>(pg_client: deadpool_postgres::Object, message: MessageD
             } else if message.account.owner == programs::mpl_token_metadata::ID.as_ref() {
                 programs::mpl_token_metadata::process(pg_client, message).await
             } else if message.account.owner == programs::spl_token::ID.as_ref() {
-                programs::spl_token::process(pg_client, message).await
+                programs::spl_token::process(pg_client, token_accounts, message).await
+            } else if message.account.owner == programs::spl_token::TOKEN_2022_ID.as_ref() {
+                programs::spl_token::process_token_2022(pg_client, token_accounts, message).await
+            } else if message.account.lamports == 0
+                && message.account.owner == system_program::ID.as_ref()
+            {
+                programs::spl_token::process_closed_account(pg_client, token_accounts, message)
+                    .await
             };
         }
         MessageData::Transaction(message) => {
@@ -37,6 +37,12 @@ async fn main() {
         .unwrap();
     tracing::info!(pool_size = args.pg_pool_size);
 
+    // load the token accounts already indexed, so their closes can be recognized
+    let token_accounts =
+        bpl_indexer::programs::spl_token::TokenAccountKeys::load(&pg_pool.get().await.unwrap())
+            .await
+            .unwrap();
+
     // connect to nats
     let nats_connection = nats::connect(args.nats_url.as_str()).unwrap();
     let sub = nats_connection.subscribe("messages.*").unwrap();
@@ -46,6 +52,6 @@ async fn main() {
     for msg in sub.messages() {
         let message: bpl_indexer::MessageData = bincode::deserialize(msg.data.as_slice()).unwrap();
         let pg_client = pg_pool.get().await.unwrap();
-        bpl_indexer::process(pg_client, message).await;
+        bpl_indexer::process(pg_client, &token_accounts, message).await;
     }
 }
@@ -2,10 +2,16 @@ use crate::{AccountMessageData, TransactionMessageData};
 use anchor_lang::{AccountDeserialize, Discriminator};
 use bpl_api_data::{
     queries::bpl_token_metadata::{
-        admin_settings, burn_delegated_promo_token, campaign, campaign_location,
-        create_admin_settings, create_campaign, create_campaign_location, create_device,
-        create_location, create_merchant, create_promo, delegate_promo_token, device, location,
-        merchant, mint_promo_token, promo, sign_memo,
+        admin_settings, approve_mint_request, burn_delegated_promo_token, campaign,
+        campaign_location, campaign_member, check_in, claim_customer_vault, claim_promo_voucher,
+        clawback_promo_token, close_sponsorship, create_admin_settings, create_campaign,
+        create_campaign_location, create_campaign_locations, create_device, create_location,
+        create_membership, create_merchant, create_promo, create_sponsorship, customer_vault,
+        delegate_promo_token, device, gift, gift_promo_token, issue_membership_pass, join_campaign,
+        location, membership, membership_pass, merchant, mint_promo_token,
+        mint_promo_token_to_vault, mint_request, promo, redeem_promo_token, referral,
+        reject_mint_request, renew_membership_pass, request_promo_mint, sign_memo, sponsorship,
+        stamp_card, stamp_promo_card, voucher_claim,
     },
     Client,
 };
@@ -127,6 +133,174 @@ async fn process_campaign_location<'a>(
     }
 }
 
+#[tracing::instrument(skip_all)]
+async fn process_campaign_member<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::CampaignMember::try_deserialize(buf) {
+        Ok(ref account) => {
+            campaign_member::upsert(pg_client, key, account, slot, write_version).await
+        }
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_referral<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::Referral::try_deserialize(buf) {
+        Ok(ref account) => referral::upsert(pg_client, key, account, slot, write_version).await,
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_sponsorship<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::Sponsorship::try_deserialize(buf) {
+        Ok(ref account) => sponsorship::upsert(pg_client, key, account, slot, write_version).await,
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_stamp_card<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::StampCard::try_deserialize(buf) {
+        Ok(ref account) => stamp_card::upsert(pg_client, key, account, slot, write_version).await,
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_voucher_claim<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::VoucherClaim::try_deserialize(buf) {
+        Ok(ref account) => {
+            voucher_claim::upsert(pg_client, key, account, slot, write_version).await
+        }
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_gift<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::Gift::try_deserialize(buf) {
+        Ok(ref account) => gift::upsert(pg_client, key, account, slot, write_version).await,
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_mint_request<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::MintRequest::try_deserialize(buf) {
+        Ok(ref account) => mint_request::upsert(pg_client, key, account, slot, write_version).await,
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_customer_vault<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::CustomerVault::try_deserialize(buf) {
+        Ok(ref account) => {
+            customer_vault::upsert(pg_client, key, account, slot, write_version).await
+        }
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_membership<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::Membership::try_deserialize(buf) {
+        Ok(ref account) => membership::upsert(pg_client, key, account, slot, write_version).await,
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_membership_pass<'a>(
+    pg_client: &Client,
+    key: &[u8],
+    buf: &mut &[u8],
+    slot: u64,
+    write_version: u64,
+) {
+    match state::MembershipPass::try_deserialize(buf) {
+        Ok(ref account) => {
+            membership_pass::upsert(pg_client, key, account, slot, write_version).await
+        }
+        Err(error) => {
+            tracing::error!(id = bs58::encode(key).into_string(), ?error)
+        }
+    }
+}
+
 pub async fn process<'a>(pg_client: deadpool_postgres::Object, message: AccountMessageData<'a>) {
     let key = message.account.pubkey.as_ref();
     let mut buf = message.account.data.as_ref();
@@ -147,8 +321,28 @@ pub async fn process<'a>(pg_client: deadpool_postgres::Object, message: AccountM
         process_campaign(&pg_client, key, &mut buf, slot, write_version).await
     } else if discriminator == state::CampaignLocation::discriminator() {
         process_campaign_location(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::CampaignMember::discriminator() {
+        process_campaign_member(&pg_client, key, &mut buf, slot, write_version).await
     } else if discriminator == state::Promo::discriminator() {
         process_promo(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::Referral::discriminator() {
+        process_referral(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::StampCard::discriminator() {
+        process_stamp_card(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::Sponsorship::discriminator() {
+        process_sponsorship(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::VoucherClaim::discriminator() {
+        process_voucher_claim(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::MintRequest::discriminator() {
+        process_mint_request(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::Gift::discriminator() {
+        process_gift(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::CustomerVault::discriminator() {
+        process_customer_vault(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::Membership::discriminator() {
+        process_membership(&pg_client, key, &mut buf, slot, write_version).await
+    } else if discriminator == state::MembershipPass::discriminator() {
+        process_membership_pass(&pg_client, key, &mut buf, slot, write_version).await
     } else {
         ()
     }
@@ -164,10 +358,28 @@ impl Discriminatorio {
     pub const CREATE_DEVICE: [u8; 8] = [56, 101, 5, 177, 25, 113, 80, 174];
     pub const CREATE_CAMPAIGN: [u8; 8] = [111, 131, 187, 98, 160, 193, 114, 244];
     pub const CREATE_CAMPAIGN_LOCATION: [u8; 8] = [82, 9, 70, 52, 189, 11, 188, 239];
+    pub const CREATE_CAMPAIGN_LOCATIONS: [u8; 8] = [249, 192, 86, 34, 219, 195, 185, 181];
+    pub const JOIN_CAMPAIGN: [u8; 8] = [139, 142, 101, 28, 183, 90, 68, 4];
+    pub const CREATE_SPONSORSHIP: [u8; 8] = [23, 59, 189, 110, 39, 5, 73, 159];
+    pub const CLOSE_SPONSORSHIP: [u8; 8] = [48, 49, 37, 139, 2, 175, 255, 161];
     pub const CREATE_PROMO: [u8; 8] = [135, 231, 68, 194, 63, 31, 192, 82];
     pub const MINT_PROMO_TOKEN: [u8; 8] = [75, 139, 89, 205, 32, 105, 163, 161];
+    pub const STAMP_PROMO_CARD: [u8; 8] = [90, 224, 252, 252, 247, 155, 175, 50];
+    pub const CLAIM_PROMO_VOUCHER: [u8; 8] = [33, 220, 214, 159, 230, 183, 200, 98];
+    pub const REQUEST_PROMO_MINT: [u8; 8] = [147, 232, 118, 244, 182, 187, 127, 83];
+    pub const APPROVE_MINT_REQUEST: [u8; 8] = [118, 25, 122, 172, 236, 89, 39, 76];
+    pub const REJECT_MINT_REQUEST: [u8; 8] = [95, 142, 147, 85, 22, 230, 95, 158];
+    pub const GIFT_PROMO_TOKEN: [u8; 8] = [44, 169, 97, 130, 78, 253, 142, 210];
+    pub const MINT_PROMO_TOKEN_TO_VAULT: [u8; 8] = [112, 157, 2, 138, 12, 249, 164, 71];
+    pub const CLAIM_CUSTOMER_VAULT: [u8; 8] = [102, 5, 206, 161, 203, 254, 61, 128];
+    pub const CREATE_MEMBERSHIP: [u8; 8] = [138, 184, 106, 226, 75, 60, 47, 70];
+    pub const ISSUE_MEMBERSHIP_PASS: [u8; 8] = [28, 139, 160, 194, 0, 46, 147, 221];
+    pub const RENEW_MEMBERSHIP_PASS: [u8; 8] = [20, 49, 207, 14, 86, 168, 202, 64];
+    pub const CHECK_IN: [u8; 8] = [209, 253, 4, 217, 250, 241, 207, 50];
     pub const DELEGATE_PROMO_TOKEN: [u8; 8] = [85, 206, 226, 194, 207, 166, 164, 22];
     pub const BURN_DELEGATED_PROMO_TOKEN: [u8; 8] = [119, 36, 30, 56, 83, 96, 21, 132];
+    pub const REDEEM_PROMO_TOKEN: [u8; 8] = [181, 253, 243, 230, 93, 59, 32, 129];
+    pub const CLAWBACK_PROMO_TOKEN: [u8; 8] = [48, 184, 246, 119, 222, 58, 247, 146];
     pub const SIGN_MEMO: [u8; 8] = [163, 48, 14, 17, 151, 234, 75, 51];
 }
 
@@ -243,6 +455,48 @@ pub async fn process_transaction<'a>(
             )
             .await
         }
+        Discriminatorio::CREATE_CAMPAIGN_LOCATIONS => {
+            create_campaign_locations::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+
+        Discriminatorio::JOIN_CAMPAIGN => {
+            join_campaign::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+
+        Discriminatorio::CREATE_SPONSORSHIP => {
+            create_sponsorship::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::CLOSE_SPONSORSHIP => {
+            close_sponsorship::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
 
         Discriminatorio::CREATE_PROMO => {
             create_promo::upsert(
@@ -265,6 +519,126 @@ pub async fn process_transaction<'a>(
             )
             .await
         }
+        Discriminatorio::STAMP_PROMO_CARD => {
+            stamp_promo_card::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::CLAIM_PROMO_VOUCHER => {
+            claim_promo_voucher::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::REQUEST_PROMO_MINT => {
+            request_promo_mint::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::APPROVE_MINT_REQUEST => {
+            approve_mint_request::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::REJECT_MINT_REQUEST => {
+            reject_mint_request::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::GIFT_PROMO_TOKEN => {
+            gift_promo_token::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::MINT_PROMO_TOKEN_TO_VAULT => {
+            mint_promo_token_to_vault::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::CLAIM_CUSTOMER_VAULT => {
+            claim_customer_vault::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::CREATE_MEMBERSHIP => {
+            create_membership::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::ISSUE_MEMBERSHIP_PASS => {
+            issue_membership_pass::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::RENEW_MEMBERSHIP_PASS => {
+            renew_membership_pass::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::CHECK_IN => {
+            check_in::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
         Discriminatorio::DELEGATE_PROMO_TOKEN => {
             delegate_promo_token::upsert(
                 &pg_client,
@@ -286,6 +660,27 @@ pub async fn process_transaction<'a>(
             )
             .await
         }
+        Discriminatorio::REDEEM_PROMO_TOKEN => {
+            redeem_promo_token::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.balances,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
+        Discriminatorio::CLAWBACK_PROMO_TOKEN => {
+            clawback_promo_token::upsert(
+                &pg_client,
+                &message.signature,
+                &message.accounts,
+                &message.data,
+                message.slot,
+            )
+            .await
+        }
         Discriminatorio::SIGN_MEMO => {
             sign_memo::upsert(
                 &pg_client,
@@ -1,5 +1,5 @@
 use crate::AccountMessageData;
-use anchor_lang::AccountDeserialize;
+use anchor_lang_mpl::AccountDeserialize;
 
 use bpl_api_data::queries::mpl_auction_house::{
     auction_house, bid_receipt, listing_receipt, purchase_receipt,
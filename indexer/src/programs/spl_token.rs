@@ -1,10 +1,42 @@
 use crate::AccountMessageData;
-use anchor_lang::AccountDeserialize;
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
 pub use anchor_spl::token::{spl_token::ID, Mint, TokenAccount};
+use anchor_spl::token_2022::spl_token_2022::extension::AccountType;
+pub use anchor_spl::token_2022::ID as TOKEN_2022_ID;
 use bpl_api_data::{
     queries::spl_token::{mint, token_account},
     Client,
 };
+use std::{collections::HashSet, sync::Mutex};
+
+/// Keys of the token accounts stored in the database.
+///
+/// Closed token accounts are reported with the system program as owner, the same as any other
+/// account without lamports, so only the closes of accounts in this set are deleted. Loaded from
+/// the database at startup and kept up to date as token accounts are upserted and closed.
+#[derive(Default, Debug)]
+pub struct TokenAccountKeys(Mutex<HashSet<Pubkey>>);
+
+impl TokenAccountKeys {
+    pub async fn load(pg_client: &Client) -> Result<Self, bpl_api_data::Error> {
+        let keys = token_account::select_ids(pg_client)
+            .await?
+            .iter()
+            .filter_map(|id| id.parse().ok())
+            .collect();
+        Ok(Self(Mutex::new(keys)))
+    }
+
+    fn insert(&self, key: &[u8]) {
+        if let Ok(key) = Pubkey::try_from(key) {
+            self.0.lock().unwrap().insert(key);
+        }
+    }
+
+    fn remove(&self, key: &[u8]) -> bool {
+        Pubkey::try_from(key).map_or(false, |key| self.0.lock().unwrap().remove(&key))
+    }
+}
 
 #[tracing::instrument(skip_all)]
 async fn process_mint<'a>(
@@ -25,6 +57,7 @@ async fn process_mint<'a>(
 #[tracing::instrument(skip_all)]
 async fn process_token_account<'a>(
     pg_client: &bpl_api_data::Client,
+    token_accounts: &TokenAccountKeys,
     key: &[u8],
     buf: &mut &[u8],
     slot: u64,
@@ -32,6 +65,7 @@ async fn process_token_account<'a>(
 ) {
     match TokenAccount::try_deserialize(buf) {
         Ok(ref account) => {
+            token_accounts.insert(key);
             token_account::upsert(pg_client, key, account, slot, write_version).await
         }
         Err(error) => {
@@ -40,7 +74,34 @@ async fn process_token_account<'a>(
     }
 }
 
-pub async fn process<'a>(pg_client: deadpool_postgres::Object, message: AccountMessageData<'a>) {
+/// Deletes closed token accounts.
+///
+/// Closed accounts have no lamports and, depending on the token program version, either zeroed
+/// data or no data and the system program as owner. Accounts that aren't tracked token accounts
+/// are skipped without querying the database.
+pub async fn process_closed_account<'a>(
+    pg_client: deadpool_postgres::Object,
+    token_accounts: &TokenAccountKeys,
+    message: AccountMessageData<'a>,
+) {
+    let key = message.account.pubkey.as_ref();
+    if !token_accounts.remove(key) {
+        return;
+    }
+    let slot = message.slot;
+    let write_version = message.account.write_version;
+    token_account::delete(&pg_client, key, slot, write_version).await
+}
+
+pub async fn process<'a>(
+    pg_client: deadpool_postgres::Object,
+    token_accounts: &TokenAccountKeys,
+    message: AccountMessageData<'a>,
+) {
+    if message.account.lamports == 0 {
+        return process_closed_account(pg_client, token_accounts, message).await;
+    }
+
     let key = message.account.pubkey.as_ref();
     let mut buf = message.account.data.as_ref();
     let slot = message.slot;
@@ -48,7 +109,60 @@ pub async fn process<'a>(pg_client: deadpool_postgres::Object, message: AccountM
     match buf.len() {
         Mint::LEN => process_mint(&pg_client, key, &mut buf, slot, write_version).await,
         TokenAccount::LEN => {
-            process_token_account(&pg_client, key, &mut buf, slot, write_version).await
+            process_token_account(
+                &pg_client,
+                token_accounts,
+                key,
+                &mut buf,
+                slot,
+                write_version,
+            )
+            .await
+        }
+        _ => (),
+    }
+}
+
+/// Processes Token-2022 accounts.
+///
+/// Accounts without extensions have the same layout as spl-token accounts. Accounts with
+/// extensions are padded to the token account length and followed by an account type byte and
+/// the extension data, so they are dispatched on the account type and only the base state, which
+/// matches the spl-token layout, is deserialized.
+pub async fn process_token_2022<'a>(
+    pg_client: deadpool_postgres::Object,
+    token_accounts: &TokenAccountKeys,
+    message: AccountMessageData<'a>,
+) {
+    if message.account.lamports == 0 {
+        return process_closed_account(pg_client, token_accounts, message).await;
+    }
+
+    let key = message.account.pubkey.as_ref();
+    let data = message.account.data.as_ref();
+    let slot = message.slot;
+    let write_version = message.account.write_version;
+    let mut buf = match data.len() {
+        Mint::LEN | TokenAccount::LEN => data,
+        len if len > TokenAccount::LEN => match data[TokenAccount::LEN] {
+            t if t == AccountType::Mint as u8 => &data[..Mint::LEN],
+            t if t == AccountType::Account as u8 => &data[..TokenAccount::LEN],
+            _ => return,
+        },
+        _ => return,
+    };
+    match buf.len() {
+        Mint::LEN => process_mint(&pg_client, key, &mut buf, slot, write_version).await,
+        TokenAccount::LEN => {
+            process_token_account(
+                &pg_client,
+                token_accounts,
+                key,
+                &mut buf,
+                slot,
+                write_version,
+            )
+            .await
         }
         _ => (),
     }
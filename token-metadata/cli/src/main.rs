@@ -1,6 +1,4 @@
-use anchor_lang::{
-    InstructionData, ToAccountMetas,
-};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
 
 use ed25519_dalek::Keypair as DalekKeypair;
 
@@ -9,11 +7,13 @@ use anchor_client::{
         commitment_config::CommitmentConfig,
         signature::{read_keypair_file, Keypair},
         signer::Signer,
-        system_program
     },
+    solana_client::rpc_client::RpcClient,
     Client, Cluster,
 };
-use bpl_token_metadata::{instruction, accounts, state::{AdminSettings, Campaign}, utils::{self, find_campaign_address, find_merchant_address}};
+use bpl_token_metadata::{state::{AdminSettings, Campaign, DailyLimits}, utils::{self, find_campaign_address, find_campaign_location_address, find_location_address, find_merchant_address}};
+use bpl_token_metadata_client::instructions::{create_admin_settings_instruction, create_campaign_instruction, set_campaign_location_limits_instruction};
+use bpl_api_tx::utils::allowlist::AllowlistTree;
 use bundlr_sdk::{tags::Tag};
 use clap::{Parser, Subcommand, ArgEnum};
 use tokio::time::sleep;
@@ -74,6 +74,25 @@ enum Commands {
         uri: String,
         #[clap(long, default_value_t = 500_000_000, value_parser)]
         lamports: u64,
+        #[clap(long, value_parser)]
+        max_mint: Option<u32>,
+        #[clap(long, value_parser)]
+        max_burn: Option<u32>,
+    },
+    #[clap(about = "Set daily mint and burn limits for a campaign location and each of its devices")]
+    SetCampaignLocationLimits {
+        #[clap(long, default_value = "Test Campaign")]
+        campaign_name: String,
+        #[clap(long, default_value = "Test Location")]
+        location_name: String,
+        #[clap(long, value_parser)]
+        location_max_mint: Option<u32>,
+        #[clap(long, value_parser)]
+        location_max_burn: Option<u32>,
+        #[clap(long, value_parser)]
+        device_max_mint: Option<u32>,
+        #[clap(long, value_parser)]
+        device_max_burn: Option<u32>,
     },
     #[clap(about = "Tesing requesting data from graphql api")]
     TestGql,
@@ -90,6 +109,11 @@ enum Commands {
     PurgeImgx {
         #[clap(index=1)]
         url: String
+    },
+    #[clap(about = "Build promo allowlist from csv of wallets and upload wallet list to arweave")]
+    CreateAllowlist {
+        #[clap(index=1, value_parser = valid_file_path)]
+        path: PathBuf
     }
 }
 
@@ -208,20 +232,17 @@ async fn main() -> anyhow::Result<()> {
 
             let tx = program
                 .request()
-                .accounts(accounts::CreateAdminSettings {
+                .instruction(create_admin_settings_instruction(
                     payer,
-                    admin_settings,
-                    system_program: system_program::ID,
-                })
-                .args(instruction::CreateAdminSettings {
-                    data: AdminSettings {
+                    AdminSettings {
                         platform: platform_keypair.pubkey(),
                         create_promo_lamports: create_promo_lamports.clone(),
                         burn_promo_token_lamports: burn_promo_token_lamports.clone(),
+                        vault_authority: platform_signer_keypair.pubkey(),
                     },
-                })
+                ))
                 .send()?;
-            let admin_settings_account: AdminSettings = program.account(admin_settings)?;
+            let admin_settings_account: AdminSettings = get_account(&program.rpc(), admin_settings)?;
             tracing::info!(
                 signature = tx.to_string(),
                 admin_settings_account = format!("{:?}", admin_settings_account)
@@ -231,7 +252,9 @@ async fn main() -> anyhow::Result<()> {
         Commands::CreateCampaign {
             name,
             uri,
-            lamports
+            lamports,
+            max_mint,
+            max_burn
         } => {
             let owner = merchant_keypair.pubkey();
             let payer = platform_signer_keypair.pubkey();
@@ -248,31 +271,25 @@ async fn main() -> anyhow::Result<()> {
             let campaign = find_campaign_address(&merchant, name).0;
             
             // this needs to be updated for actual location, just addresses for now
-            let data = Campaign {
-                    merchant,
-                    name: name.clone(),
-                    uri: uri.clone(),
-                    active: true,
-                };
-
-            let tx = program
-            .request()
-            .accounts(accounts::CreateCampaign {
+            let instructions = create_campaign_instruction(
                 payer,
                 owner,
-                merchant,
-                campaign,
-                memo_program: spl_memo::ID,
-                system_program: system_program::ID,
-            })
-            .args(instruction::CreateCampaign {
-                data,
-                lamports: lamports.clone(),
-                memo: None,
-            })
+                name.clone(),
+                uri.clone(),
+                lamports.clone(),
+                vec![],
+                max_mint.clone(),
+                max_burn.clone(),
+                true,
+                None,
+            );
+
+            let tx = instructions
+            .into_iter()
+            .fold(program.request(), |request, ix| request.instruction(ix))
             .send()?;
             
-            let campaign_account: Campaign = program.account(campaign)?;
+            let campaign_account: Campaign = get_account(&program.rpc(), campaign)?;
             tracing::info!(
                 signature = tx.to_string(),
                 campaign = format!("{:?}", campaign_account)
@@ -281,6 +298,57 @@ async fn main() -> anyhow::Result<()> {
             Ok(())
 
         }
+        Commands::SetCampaignLocationLimits {
+            campaign_name,
+            location_name,
+            location_max_mint,
+            location_max_burn,
+            device_max_mint,
+            device_max_burn
+        } => {
+            let owner = merchant_keypair.pubkey();
+            let rc_payer_keypair = Rc::new(merchant_keypair);
+            let client = Client::new_with_options(
+                cli.cluster,
+                rc_payer_keypair,
+                CommitmentConfig::confirmed(),
+            );
+
+            let program = client.program(bpl_token_metadata::id());
+
+            let merchant = find_merchant_address(&owner).0;
+            let campaign = find_campaign_address(&merchant, campaign_name).0;
+            let location = find_location_address(&owner, location_name).0;
+            let campaign_location = find_campaign_location_address(&campaign, &location).0;
+
+            let tx = program
+            .request()
+            .instruction(set_campaign_location_limits_instruction(
+                owner,
+                owner,
+                campaign,
+                location,
+                DailyLimits {
+                    max_mint: location_max_mint.clone(),
+                    max_burn: location_max_burn.clone(),
+                },
+                DailyLimits {
+                    max_mint: device_max_mint.clone(),
+                    max_burn: device_max_burn.clone(),
+                },
+                None,
+            ))
+            .send()?;
+
+            let campaign_location_account: bpl_token_metadata::state::CampaignLocation =
+                get_account(&program.rpc(), campaign_location)?;
+            tracing::info!(
+                signature = tx.to_string(),
+                campaign_location = format!("{:?}", campaign_location_account)
+            );
+
+            Ok(())
+        }
         Commands::UploadString => {
             let wallet = DalekKeypair::from_bytes(&platform_signer_keypair.to_bytes()).unwrap();
             
@@ -400,6 +468,43 @@ async fn main() -> anyhow::Result<()> {
 
             Ok(())
         }
+        Commands::CreateAllowlist { path } => {
+            let csv = std::fs::read_to_string(path)?;
+            let tree = AllowlistTree::from_csv(&csv)?;
+
+            let wallet = DalekKeypair::from_bytes(&platform_signer_keypair.to_bytes()).unwrap();
+            
+            let currency = bundlr_sdk::currency::solana::SolanaBuilder::new()
+            .wallet(&bs58::encode(wallet.to_bytes()).into_string())
+            .build()
+            .unwrap();
+
+            let bundlr = bundlr_sdk::BundlrBuilder::<bundlr_sdk::currency::solana::Solana>::new()
+            .currency(currency)
+            .url(url::Url::from_str(bundlr_sdk::consts::BUNDLR_DEFAULT_URL).unwrap())
+            .fetch_pub_info().await.unwrap()
+            .build()
+            .unwrap();
+
+            let mut tx = bundlr.create_transaction(
+                serde_json::to_vec(&tree.to_json()).unwrap(),
+                vec![
+                    Tag::new("User-Agent".into(), "bokoup".into()),
+                    Tag::new("Content-Type".into(), "application/json".into()),
+                ],
+            ).unwrap();
+
+            bundlr.sign_transaction(&mut tx).await?;
+            let response = bundlr.send_transaction(tx).await?;
+
+            tracing::info!(
+                uri = format!("https://arweave.net/{}", response["id"].as_str().unwrap()),
+                root = bs58::encode(tree.root()).into_string(),
+                wallets = tree.len()
+            );
+
+            Ok(())
+        }
 
     }
 }
@@ -410,6 +515,13 @@ async fn main() -> anyhow::Result<()> {
 // Validators
 // ====================
 
+// anchor-client is held at 0.27 until the workspace moves to solana 1.16, so accounts are
+// deserialized with the program's own anchor-lang traits rather than `Program::account`.
+fn get_account<T: AccountDeserialize>(rpc: &RpcClient, address: Pubkey) -> anyhow::Result<T> {
+    let data = rpc.get_account_data(&address)?;
+    Ok(T::try_deserialize(&mut data.as_slice())?)
+}
+
 fn valid_file_path(path_str: &str) -> Result<PathBuf, String> {
     match path_str.parse::<PathBuf>() {
         Ok(p) => {
@@ -0,0 +1,324 @@
+//! Helpers for fetching and decoding program accounts. Fetching is left to an [AccountFetcher]
+//! implementation so that the crate doesn't depend on any particular rpc client.
+
+use crate::{
+    error::ClientError,
+    instructions::{ReferralRewardAccounts, SponsorshipAccounts},
+};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use async_trait::async_trait;
+use bpl_token_metadata::{
+    state::{
+        AdminSettings, Campaign, CampaignLocation, CampaignMember, CustomerVault, Device, Location,
+        Membership, MembershipPass, Merchant, MintRequest, Promo, Referral, Sponsorship, StampCard,
+        VoucherClaim,
+    },
+    utils::{
+        find_admin_address, find_campaign_address, find_campaign_address_v1,
+        find_campaign_location_address, find_campaign_member_address, find_customer_vault_address,
+        find_device_address, find_device_address_v1, find_location_address,
+        find_location_address_v1, find_membership_address, find_membership_pass_address,
+        find_merchant_address, find_mint_request_address, find_promo_address,
+        find_referral_address, find_sponsorship_address, find_stamp_card_address,
+        find_voucher_claim_address,
+    },
+};
+
+/// Source of raw account data, e.g. an rpc client.
+#[async_trait]
+pub trait AccountFetcher {
+    /// Returns the data of the account at `address`, or `None` if it doesn't exist.
+    async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError>;
+}
+
+/// Decodes account data, checking the account discriminator.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    let mut data = data;
+    Ok(T::try_deserialize(&mut data)?)
+}
+
+/// Fetches and decodes the account at `address`, returning `None` if it doesn't exist.
+pub async fn fetch_account<T, F>(fetcher: &F, address: &Pubkey) -> Result<Option<T>, ClientError>
+where
+    T: AccountDeserialize,
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetcher
+        .get_account_data(address)
+        .await?
+        .map(|data| decode_account(&data))
+        .transpose()
+}
+
+/// Fetches and decodes the account at `address`, returning an error if it doesn't exist.
+pub async fn fetch_existing_account<T, F>(fetcher: &F, address: &Pubkey) -> Result<T, ClientError>
+where
+    T: AccountDeserialize,
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, address)
+        .await?
+        .ok_or(ClientError::AccountNotFound(*address))
+}
+
+pub async fn fetch_admin_settings<F>(fetcher: &F) -> Result<Option<AdminSettings>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_admin_address().0).await
+}
+
+pub async fn fetch_merchant<F>(fetcher: &F, owner: &Pubkey) -> Result<Option<Merchant>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_merchant_address(owner).0).await
+}
+
+/// Fetches the account at `address`, falling back to `legacy_address` if it doesn't exist. Used
+/// for accounts identified by name, which may have been created before names were hashed into
+/// their seeds.
+async fn fetch_named_account<F, T>(
+    fetcher: &F,
+    address: &Pubkey,
+    legacy_address: Option<Pubkey>,
+) -> Result<Option<T>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+    T: AccountDeserialize,
+{
+    if let Some(account) = fetch_account(fetcher, address).await? {
+        return Ok(Some(account));
+    }
+    match legacy_address {
+        Some(legacy_address) => fetch_account(fetcher, &legacy_address).await,
+        None => Ok(None),
+    }
+}
+
+pub async fn fetch_location<F>(
+    fetcher: &F,
+    merchant_owner: &Pubkey,
+    name: &str,
+) -> Result<Option<Location>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_named_account(
+        fetcher,
+        &find_location_address(merchant_owner, name).0,
+        find_location_address_v1(merchant_owner, name).map(|(address, _)| address),
+    )
+    .await
+}
+
+pub async fn fetch_device<F>(
+    fetcher: &F,
+    location: &Pubkey,
+    name: &str,
+) -> Result<Option<Device>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_named_account(
+        fetcher,
+        &find_device_address(location, name).0,
+        find_device_address_v1(location, name).map(|(address, _)| address),
+    )
+    .await
+}
+
+pub async fn fetch_campaign<F>(
+    fetcher: &F,
+    merchant: &Pubkey,
+    name: &str,
+) -> Result<Option<Campaign>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_named_account(
+        fetcher,
+        &find_campaign_address(merchant, name).0,
+        find_campaign_address_v1(merchant, name).map(|(address, _)| address),
+    )
+    .await
+}
+
+pub async fn fetch_campaign_location<F>(
+    fetcher: &F,
+    campaign: &Pubkey,
+    location: &Pubkey,
+) -> Result<Option<CampaignLocation>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(
+        fetcher,
+        &find_campaign_location_address(campaign, location).0,
+    )
+    .await
+}
+
+pub async fn fetch_campaign_member<F>(
+    fetcher: &F,
+    campaign: &Pubkey,
+    merchant: &Pubkey,
+) -> Result<Option<CampaignMember>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_campaign_member_address(campaign, merchant).0).await
+}
+
+pub async fn fetch_promo<F>(fetcher: &F, mint: &Pubkey) -> Result<Option<Promo>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_promo_address(mint).0).await
+}
+
+pub async fn fetch_stamp_card<F>(
+    fetcher: &F,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Option<StampCard>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_stamp_card_address(mint, owner).0).await
+}
+
+/// Returns the token owner's pending mint request for a promo, or `None` if there isn't one.
+pub async fn fetch_mint_request<F>(
+    fetcher: &F,
+    mint: &Pubkey,
+    token_owner: &Pubkey,
+) -> Result<Option<MintRequest>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_mint_request_address(mint, token_owner).0).await
+}
+
+/// Returns the customer vault for `identity_hash`, or `None` if nothing has been minted to it.
+pub async fn fetch_customer_vault<F>(
+    fetcher: &F,
+    identity_hash: &[u8; 32],
+) -> Result<Option<CustomerVault>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_customer_vault_address(identity_hash).0).await
+}
+
+/// Returns the membership with master edition `mint`, or `None` if it doesn't exist.
+pub async fn fetch_membership<F>(
+    fetcher: &F,
+    mint: &Pubkey,
+) -> Result<Option<Membership>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_membership_address(mint).0).await
+}
+
+/// Returns the membership pass with mint `mint`, or `None` if it doesn't exist.
+pub async fn fetch_membership_pass<F>(
+    fetcher: &F,
+    mint: &Pubkey,
+) -> Result<Option<MembershipPass>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_membership_pass_address(mint).0).await
+}
+
+/// Returns the claim record of a voucher, or `None` if the voucher hasn't been claimed.
+pub async fn fetch_voucher_claim<F>(
+    fetcher: &F,
+    promo: &Pubkey,
+    nonce: u64,
+) -> Result<Option<VoucherClaim>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_voucher_claim_address(promo, nonce).0).await
+}
+
+pub async fn fetch_referral<F>(
+    fetcher: &F,
+    mint: &Pubkey,
+    referred: &Pubkey,
+) -> Result<Option<Referral>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_referral_address(mint, referred).0).await
+}
+
+/// Returns the accounts to include when burning a token owner's promo token so that their
+/// referrer is rewarded, or `None` if the promo doesn't have referral rewards, the token owner
+/// wasn't referred or the referrer has already been rewarded.
+pub async fn fetch_referral_reward_accounts<F>(
+    fetcher: &F,
+    mint: &Pubkey,
+    token_owner: &Pubkey,
+) -> Result<Option<ReferralRewardAccounts>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    let reward = match fetch_promo(fetcher, mint).await?.and_then(|p| p.referral) {
+        Some(reward) => reward,
+        None => return Ok(None),
+    };
+
+    Ok(fetch_referral(fetcher, mint, token_owner)
+        .await?
+        .filter(|referral| !referral.rewarded && referral.referred == *token_owner)
+        .map(|referral| ReferralRewardAccounts {
+            token_owner: *token_owner,
+            referrer: referral.referrer,
+            reward_mint: reward.reward_mint,
+        }))
+}
+
+pub async fn fetch_sponsorship<F>(
+    fetcher: &F,
+    campaign: &Pubkey,
+    sponsor: &Pubkey,
+) -> Result<Option<Sponsorship>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    fetch_account(fetcher, &find_sponsorship_address(campaign, sponsor).0).await
+}
+
+/// Returns the accounts to include when redeeming a promo token at `location` so that the burn
+/// fee and merchant reimbursement are paid by the sponsor, or `None` if the sponsor doesn't
+/// sponsor the promo. The reimbursement goes to the owner of the location's merchant.
+pub async fn fetch_sponsorship_accounts<F>(
+    fetcher: &F,
+    campaign: &Pubkey,
+    sponsor: &Pubkey,
+    mint: &Pubkey,
+    location: &Pubkey,
+) -> Result<Option<SponsorshipAccounts>, ClientError>
+where
+    F: AccountFetcher + Sync + ?Sized,
+{
+    let covered = fetch_sponsorship(fetcher, campaign, sponsor)
+        .await?
+        .map_or(false, |sponsorship| sponsorship.covers(mint));
+    if !covered {
+        return Ok(None);
+    }
+
+    let location: Location = fetch_existing_account(fetcher, location).await?;
+    let merchant: Merchant = fetch_existing_account(fetcher, &location.merchant).await?;
+    Ok(Some(SponsorshipAccounts {
+        campaign: *campaign,
+        sponsor: *sponsor,
+        payee_merchant: location.merchant,
+        payee: merchant.owner,
+    }))
+}
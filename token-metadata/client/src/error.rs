@@ -0,0 +1,18 @@
+use anchor_lang::prelude::Pubkey;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("account not found: {0}")]
+    AccountNotFound(Pubkey),
+    #[error("account could not be decoded: {0}")]
+    AccountDecodeError(String),
+    #[error("account could not be fetched: {0}")]
+    AccountFetchError(String),
+}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(error: anchor_lang::error::Error) -> Self {
+        ClientError::AccountDecodeError(error.to_string())
+    }
+}
@@ -0,0 +1,1586 @@
+//! Instruction builders for each program instruction. Program derived addresses are derived
+//! from the arguments, so only the keys that can't be derived need to be passed in.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        ed25519_program,
+        instruction::{AccountMeta, Instruction},
+        system_instruction, sysvar,
+    },
+    system_program, AnchorSerialize, InstructionData, ToAccountMetas,
+};
+use bpl_token_metadata::{
+    accounts::{
+        ApproveMintRequest as approve_mint_request_accounts,
+        BurnDelegatedPromoToken as burn_delegated_promo_token_accounts,
+        CheckIn as check_in_accounts, ClaimCustomerVault as claim_customer_vault_accounts,
+        ClaimPromoVoucher as claim_promo_voucher_accounts,
+        ClawbackPromoToken as clawback_promo_token_accounts,
+        CloseSponsorship as close_sponsorship_accounts,
+        CreateAdminSettings as create_admin_settings_accounts,
+        CreateCampaign as create_campaign_accounts,
+        CreateCampaignLocation as create_campaign_location_accounts,
+        CreateCampaignLocations as create_campaign_locations_accounts,
+        CreateDevice as create_device_accounts, CreateLocation as create_location_accounts,
+        CreateMembership as create_membership_accounts, CreateMerchant as create_merchant_accounts,
+        CreateNonFungible as create_non_fungible_accounts, CreatePromo as create_promo_accounts,
+        CreateSponsorship as create_sponsorship_accounts,
+        DelegatePromoToken as delegate_promo_token_accounts,
+        GiftPromoToken as gift_promo_token_accounts,
+        IssueMembershipPass as issue_membership_pass_accounts,
+        JoinCampaign as join_campaign_accounts, MigrateAccount as migrate_account_accounts,
+        MintPromoToken as mint_promo_token_accounts,
+        MintPromoTokenToVault as mint_promo_token_to_vault_accounts,
+        RedeemPromoToken as redeem_promo_token_accounts,
+        RejectMintRequest as reject_mint_request_accounts,
+        RenewMembershipPass as renew_membership_pass_accounts,
+        RequestPromoMint as request_promo_mint_accounts,
+        SetCampaignLocationLimits as set_campaign_location_limits_accounts,
+        SignMemo as sign_memo_accounts, StampPromoCard as stamp_promo_card_accounts,
+        TransferCpi as transfer_cpi_accounts,
+    },
+    instruction::{
+        ApproveMintRequest as approve_mint_request_instruction,
+        BurnDelegatedPromoToken as burn_delegated_promo_token_instruction,
+        CheckIn as check_in_instruction, ClaimCustomerVault as claim_customer_vault_instruction,
+        ClaimPromoVoucher as claim_promo_voucher_instruction,
+        ClawbackPromoToken as clawback_promo_token_instruction,
+        CloseSponsorship as close_sponsorship_instruction,
+        CreateAdminSettings as create_admin_settings_instruction,
+        CreateCampaign as create_campaign_instruction,
+        CreateCampaignLocation as create_campaign_location_instruction,
+        CreateCampaignLocations as create_campaign_locations_instruction,
+        CreateDevice as create_device_instruction, CreateLocation as create_location_instruction,
+        CreateMembership as create_membership_instruction,
+        CreateMerchant as create_merchant_instruction,
+        CreateNonFungible as create_non_fungible_instruction,
+        CreatePromo as create_promo_instruction,
+        CreateSponsorship as create_sponsorship_instruction,
+        DelegatePromoToken as delegate_promo_token_instruction,
+        GiftPromoToken as gift_promo_token_instruction,
+        IssueMembershipPass as issue_membership_pass_instruction,
+        JoinCampaign as join_campaign_instruction, MigrateAccount as migrate_account_instruction,
+        MintPromoToken as mint_promo_token_instruction,
+        MintPromoTokenToVault as mint_promo_token_to_vault_instruction,
+        RedeemPromoToken as redeem_promo_token_instruction,
+        RejectMintRequest as reject_mint_request_instruction,
+        RenewMembershipPass as renew_membership_pass_instruction,
+        RequestPromoMint as request_promo_mint_instruction,
+        SetCampaignLocationLimits as set_campaign_location_limits_instruction,
+        SignMemo as sign_memo_instruction, StampPromoCard as stamp_promo_card_instruction,
+        TransferCpi as transfer_cpi_instruction,
+    },
+    state::{
+        AdminSettings, Campaign, DailyLimits, DataV2, Device, GiftPolicy, Location, Merchant,
+        Promo, PromoExtensions, PromoSchedule, PromoTerms, ReferralReward, Voucher,
+    },
+    utils::{
+        ed25519_instruction_data, find_admin_address, find_associated_token_address,
+        find_authority_address, find_campaign_address, find_campaign_location_address,
+        find_campaign_member_address, find_customer_vault_address, find_device_address,
+        find_device_daily_count_address, find_edition_address, find_edition_marker_address,
+        find_gift_address, find_location_address, find_location_daily_count_address,
+        find_membership_address, find_membership_pass_address, find_merchant_address,
+        find_metadata_address, find_mint_request_address, find_promo_address,
+        find_referral_address, find_sponsorship_address, find_stamp_card_address,
+        find_voucher_claim_address,
+    },
+};
+
+/// Accounts needed to mint the referral reward to the referrer of a token owner when their
+/// promo token is burned.
+#[derive(Clone, Copy, Debug)]
+pub struct ReferralRewardAccounts {
+    /// Owner of the promo token being burned
+    pub token_owner: Pubkey,
+    /// Referrer recorded when the token was minted to the token owner
+    pub referrer: Pubkey,
+    /// Mint of the reward promo
+    pub reward_mint: Pubkey,
+}
+
+/// Accounts needed to pay the burn fee and merchant reimbursement of a redemption from a
+/// sponsorship.
+#[derive(Clone, Copy, Debug)]
+pub struct SponsorshipAccounts {
+    /// Campaign the sponsorship is for
+    pub campaign: Pubkey,
+    /// Wallet that created and funds the sponsorship
+    pub sponsor: Pubkey,
+    /// Merchant of the location the token is redeemed at
+    pub payee_merchant: Pubkey,
+    /// Owner of the payee merchant, reimbursed for the redemption
+    pub payee: Pubkey,
+}
+
+/// Creates or updates the admin settings. Must be signed by the program upgrade authority.
+pub fn create_admin_settings_instruction(payer: Pubkey, data: AdminSettings) -> Instruction {
+    let admin_settings = find_admin_address().0;
+
+    let accounts = create_admin_settings_accounts {
+        payer,
+        admin_settings,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = create_admin_settings_instruction { data }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn create_merchant_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    name: String,
+    uri: String,
+    active: bool,
+    memo: Option<String>,
+) -> Instruction {
+    let merchant = find_merchant_address(&owner).0;
+
+    let data = Merchant {
+        owner,
+        name,
+        uri,
+        active,
+    };
+
+    let accounts = create_merchant_accounts {
+        payer,
+        owner,
+        merchant,
+        memo_program: spl_memo::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = create_merchant_instruction { data, memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn create_location_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    name: String,
+    uri: String,
+    active: bool,
+    memo: Option<String>,
+) -> Instruction {
+    let (merchant, _) = find_merchant_address(&owner);
+    let (location, _) = find_location_address(&owner, &name);
+
+    let data = Location {
+        merchant,
+        name,
+        uri,
+        active,
+    };
+
+    let accounts = create_location_accounts {
+        payer,
+        owner,
+        merchant,
+        location,
+        memo_program: spl_memo::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = create_location_instruction { data, memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn create_device_instruction(
+    payer: Pubkey,
+    merchant_owner: Pubkey,
+    location: Pubkey,
+    owner: Pubkey,
+    name: String,
+    uri: String,
+    active: bool,
+    memo: Option<String>,
+) -> Instruction {
+    let (merchant, _) = find_merchant_address(&merchant_owner);
+    let (device, _) = find_device_address(&location, &name);
+
+    let data = Device {
+        owner,
+        location,
+        name,
+        uri,
+        active,
+    };
+
+    let accounts = create_device_accounts {
+        payer,
+        merchant_owner,
+        merchant,
+        location,
+        device,
+        memo_program: spl_memo::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = create_device_instruction { data, memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Creates a campaign and links `locations` to it. The campaign is funded with `lamports` from
+/// the owner's wallet with a system transfer, so the payer doesn't fund it.
+pub fn create_campaign_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    name: String,
+    uri: String,
+    lamports: u64,
+    locations: Vec<Pubkey>,
+    max_mint: Option<u32>,
+    max_burn: Option<u32>,
+    active: bool,
+    memo: Option<String>,
+) -> Vec<Instruction> {
+    let merchant = find_merchant_address(&owner).0;
+    let campaign = find_campaign_address(&merchant, &name).0;
+
+    let data = Campaign {
+        merchant,
+        name,
+        uri,
+        active,
+        max_mint,
+        max_burn,
+        ..Default::default()
+    };
+
+    let accounts = create_campaign_accounts {
+        payer,
+        owner,
+        merchant,
+        campaign,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    // The program funds the campaign from the payer, so the owner's funding is transferred
+    // separately.
+    let data = create_campaign_instruction {
+        data,
+        lamports: 0,
+        memo,
+    }
+    .data();
+
+    let mut instructions: Vec<Instruction> = Vec::new();
+
+    instructions.push(Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    });
+
+    if lamports > 0 {
+        instructions.push(system_instruction::transfer(&owner, &campaign, lamports));
+    }
+
+    if !locations.is_empty() {
+        instructions.push(create_campaign_locations_instruction(
+            payer, owner, campaign, &locations, false, None,
+        ));
+    }
+
+    instructions
+}
+
+/// Links a location to a campaign. `member` is set when the location's merchant joined the
+/// campaign with [join_campaign_instruction] rather than creating it.
+pub fn create_campaign_location_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    campaign: Pubkey,
+    location: Pubkey,
+    member: bool,
+    memo: Option<String>,
+) -> Instruction {
+    let merchant = find_merchant_address(&owner).0;
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+    let campaign_member = member.then(|| find_campaign_member_address(&campaign, &merchant).0);
+
+    let accounts = create_campaign_location_accounts {
+        payer,
+        owner,
+        merchant,
+        campaign,
+        campaign_location,
+        location,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+        campaign_member,
+    }
+    .to_account_metas(Some(true));
+
+    let data = create_campaign_location_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Links many locations to a campaign in one instruction. Each location adds two accounts, so
+/// how many fit depends on the rest of the transaction. `member` is set as in
+/// [create_campaign_location_instruction].
+pub fn create_campaign_locations_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    campaign: Pubkey,
+    locations: &[Pubkey],
+    member: bool,
+    memo: Option<String>,
+) -> Instruction {
+    let merchant = find_merchant_address(&owner).0;
+    let campaign_member = member.then(|| find_campaign_member_address(&campaign, &merchant).0);
+
+    let mut accounts = create_campaign_locations_accounts {
+        payer,
+        owner,
+        merchant,
+        campaign,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+        campaign_member,
+    }
+    .to_account_metas(Some(true));
+
+    for location in locations {
+        accounts.push(AccountMeta::new_readonly(*location, false));
+        accounts.push(AccountMeta::new(
+            find_campaign_location_address(&campaign, location).0,
+            false,
+        ));
+    }
+
+    let data = create_campaign_locations_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Adds the merchant of `owner` to a campaign created by the merchant of `campaign_owner` and
+/// links the member's locations to the campaign. Must be signed by both owners.
+pub fn join_campaign_instruction(
+    payer: Pubkey,
+    campaign_owner: Pubkey,
+    campaign: Pubkey,
+    owner: Pubkey,
+    locations: Vec<Pubkey>,
+    memo: Option<String>,
+) -> Vec<Instruction> {
+    let campaign_merchant = find_merchant_address(&campaign_owner).0;
+    let merchant = find_merchant_address(&owner).0;
+    let campaign_member = find_campaign_member_address(&campaign, &merchant).0;
+
+    let accounts = join_campaign_accounts {
+        payer,
+        campaign_owner,
+        campaign_merchant,
+        campaign,
+        owner,
+        merchant,
+        campaign_member,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = join_campaign_instruction { memo }.data();
+
+    let mut instructions = vec![Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }];
+
+    if !locations.is_empty() {
+        instructions.push(create_campaign_locations_instruction(
+            payer, owner, campaign, &locations, true, None,
+        ));
+    }
+
+    instructions
+}
+
+/// Creates a sponsorship of the promos of `campaign` with the given mints, or all of them if
+/// empty, funded with `lamports` from the sponsor.
+pub fn create_sponsorship_instruction(
+    payer: Pubkey,
+    sponsor: Pubkey,
+    merchant: Pubkey,
+    campaign: Pubkey,
+    mints: Vec<Pubkey>,
+    reimbursement_lamports: u64,
+    lamports: u64,
+    memo: Option<String>,
+) -> Instruction {
+    let sponsorship = find_sponsorship_address(&campaign, &sponsor).0;
+
+    let mut accounts = create_sponsorship_accounts {
+        payer,
+        sponsor,
+        merchant,
+        campaign,
+        sponsorship,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+    // The program checks that each mint is a promo of the campaign.
+    accounts.extend(
+        mints
+            .iter()
+            .map(|mint| AccountMeta::new_readonly(find_promo_address(mint).0, false)),
+    );
+
+    let data = create_sponsorship_instruction {
+        mints,
+        reimbursement_lamports,
+        lamports,
+        memo,
+    }
+    .data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Closes the sponsor's sponsorship of `campaign`, returning its remaining funds to the sponsor.
+pub fn close_sponsorship_instruction(
+    sponsor: Pubkey,
+    campaign: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let accounts = close_sponsorship_accounts {
+        sponsor,
+        sponsorship: find_sponsorship_address(&campaign, &sponsor).0,
+        memo_program: spl_memo::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = close_sponsorship_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn set_campaign_location_limits_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    campaign: Pubkey,
+    location: Pubkey,
+    location_limits: DailyLimits,
+    device_limits: DailyLimits,
+    memo: Option<String>,
+) -> Instruction {
+    let merchant = find_merchant_address(&owner).0;
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+
+    let accounts = set_campaign_location_limits_accounts {
+        payer,
+        owner,
+        merchant,
+        campaign,
+        campaign_location,
+        memo_program: spl_memo::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = set_campaign_location_limits_instruction {
+        location_limits,
+        device_limits,
+        memo,
+    }
+    .data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn create_promo_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    campaign: Pubkey,
+    mint: Pubkey,
+    platform: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    max_mint: Option<u32>,
+    max_burn: Option<u32>,
+    active: bool,
+    schedule: Option<PromoSchedule>,
+    allowlist: Option<[u8; 32]>,
+    referral: Option<ReferralReward>,
+    stamp_threshold: Option<u32>,
+    terms: Option<PromoTerms>,
+    requires_approval: bool,
+    gift_policy: GiftPolicy,
+    is_mutable: bool,
+    token_program: Pubkey,
+    extensions: PromoExtensions,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let metadata = find_metadata_address(&mint).0;
+    let admin_settings = find_admin_address().0;
+    let merchant = find_merchant_address(&owner).0;
+
+    let accounts = create_promo_accounts {
+        payer,
+        owner,
+        merchant,
+        campaign,
+        mint,
+        metadata,
+        authority,
+        promo,
+        platform,
+        admin_settings,
+        metadata_program: mpl_token_metadata::ID,
+        token_program,
+        memo_program: spl_memo::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let promo_data = Promo {
+        campaign,
+        mint,
+        metadata,
+        mint_count: 0,
+        burn_count: 0,
+        max_mint,
+        max_burn,
+        active,
+        schedule,
+        allowlist,
+        referral,
+        stamp_threshold,
+        terms,
+        requires_approval,
+        gift_policy,
+    };
+
+    let metadata_data = DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    let data = create_promo_instruction {
+        promo_data,
+        metadata_data,
+        is_mutable,
+        extensions,
+        memo,
+    }
+    .data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn transfer_cpi_instruction(
+    payer: Pubkey,
+    device: Pubkey,
+    campaign: Pubkey,
+    platform: Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let merchant = find_merchant_address(&payer).0;
+    let admin_settings = find_admin_address().0;
+
+    let accounts = transfer_cpi_accounts {
+        payer,
+        merchant,
+        device,
+        campaign,
+        platform,
+        admin_settings,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = transfer_cpi_instruction { lamports }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn mint_promo_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    campaign: Pubkey,
+    token_owner: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    proof: Vec<[u8; 32]>,
+    referrer: Option<Pubkey>,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+    let location_count = find_location_daily_count_address(&campaign_location).0;
+    let device_count = find_device_daily_count_address(&campaign_location, &device).0;
+    let referral = referrer.map(|_| find_referral_address(&mint, &token_owner).0);
+
+    let accounts = mint_promo_token_accounts {
+        payer,
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        token_owner,
+        mint,
+        authority,
+        promo,
+        token_account,
+        location_count,
+        device_count,
+        token_program,
+        memo_program: spl_memo::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+        referral,
+    }
+    .to_account_metas(Some(true));
+
+    let data = mint_promo_token_instruction {
+        proof,
+        referrer,
+        memo,
+    }
+    .data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Mints a promo token into the customer vault for `identity_hash`, the salted hash of the
+/// customer's phone number or email.
+pub fn mint_promo_token_to_vault_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    campaign: Pubkey,
+    identity_hash: [u8; 32],
+    mint: Pubkey,
+    token_program: Pubkey,
+    proof: Vec<[u8; 32]>,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let customer_vault = find_customer_vault_address(&identity_hash).0;
+    let token_account = find_associated_token_address(&customer_vault, &mint, &token_program);
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+    let location_count = find_location_daily_count_address(&campaign_location).0;
+    let device_count = find_device_daily_count_address(&campaign_location, &device).0;
+
+    let accounts = mint_promo_token_to_vault_accounts {
+        payer,
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        customer_vault,
+        mint,
+        authority,
+        promo,
+        token_account,
+        location_count,
+        device_count,
+        token_program,
+        memo_program: spl_memo::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = mint_promo_token_to_vault_instruction {
+        identity_hash,
+        proof,
+        memo,
+    }
+    .data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Transfers the tokens of a promo from the customer vault for `identity_hash` to the token
+/// owner's wallet. Has to be signed by the vault authority in the admin settings.
+pub fn claim_customer_vault_instruction(
+    payer: Pubkey,
+    vault_authority: Pubkey,
+    identity_hash: [u8; 32],
+    token_owner: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let admin_settings = find_admin_address().0;
+    let authority = find_authority_address().0;
+    let customer_vault = find_customer_vault_address(&identity_hash).0;
+    let vault_token_account = find_associated_token_address(&customer_vault, &mint, &token_program);
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+
+    let accounts = claim_customer_vault_accounts {
+        payer,
+        vault_authority,
+        admin_settings,
+        customer_vault,
+        token_owner,
+        mint,
+        authority,
+        vault_token_account,
+        token_account,
+        token_program,
+        memo_program: spl_memo::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = claim_customer_vault_instruction {
+        identity_hash,
+        memo,
+    }
+    .data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn stamp_promo_card_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    campaign: Pubkey,
+    token_owner: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let stamp_card = find_stamp_card_address(&mint, &token_owner).0;
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+    let location_count = find_location_daily_count_address(&campaign_location).0;
+    let device_count = find_device_daily_count_address(&campaign_location, &device).0;
+
+    let accounts = stamp_promo_card_accounts {
+        payer,
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        token_owner,
+        mint,
+        authority,
+        promo,
+        stamp_card,
+        token_account,
+        location_count,
+        device_count,
+        token_program,
+        memo_program: spl_memo::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = stamp_promo_card_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Claims a promo token for a voucher signed by the device owner. Returns the Ed25519 program
+/// instruction verifying `signature` followed by the claim instruction, which must stay next to
+/// each other in the transaction.
+pub fn claim_promo_voucher_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    campaign: Pubkey,
+    token_owner: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    voucher: Voucher,
+    signature: [u8; 64],
+    proof: Vec<[u8; 32]>,
+    memo: Option<String>,
+) -> Vec<Instruction> {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let voucher_claim = find_voucher_claim_address(&promo, voucher.nonce).0;
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+    let location_count = find_location_daily_count_address(&campaign_location).0;
+    let device_count = find_device_daily_count_address(&campaign_location, &device).0;
+
+    let message = voucher.try_to_vec().unwrap();
+    let verify_ix = Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data: ed25519_instruction_data(&device_owner, &signature, &message),
+    };
+
+    let accounts = claim_promo_voucher_accounts {
+        payer,
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        token_owner,
+        mint,
+        authority,
+        promo,
+        voucher_claim,
+        token_account,
+        location_count,
+        device_count,
+        instructions: sysvar::instructions::id(),
+        token_program,
+        memo_program: spl_memo::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = claim_promo_voucher_instruction {
+        voucher,
+        proof,
+        memo,
+    }
+    .data();
+
+    vec![
+        verify_ix,
+        Instruction {
+            program_id: bpl_token_metadata::id(),
+            accounts,
+            data,
+        },
+    ]
+}
+
+/// Gifts a promo token from the sender to the recipient. `gift_policy` is the promo's policy,
+/// which decides whether the [Gift] accounts of the recipient and sender are included.
+///
+/// [Gift]: bpl_token_metadata::state::Gift
+pub fn gift_promo_token_instruction(
+    payer: Pubkey,
+    sender: Pubkey,
+    recipient: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    gift_policy: GiftPolicy,
+    memo: Option<String>,
+) -> Instruction {
+    let promo = find_promo_address(&mint).0;
+    let sender_token_account = find_associated_token_address(&sender, &mint, &token_program);
+    let recipient_token_account = find_associated_token_address(&recipient, &mint, &token_program);
+    let allowed_once = gift_policy == GiftPolicy::AllowedOnce;
+    let gift = allowed_once.then(|| find_gift_address(&mint, &recipient).0);
+    let sender_gift = allowed_once.then(|| find_gift_address(&mint, &sender).0);
+    let authority = find_authority_address().0;
+
+    let accounts = gift_promo_token_accounts {
+        payer,
+        sender,
+        recipient,
+        mint,
+        promo,
+        sender_token_account,
+        recipient_token_account,
+        token_program,
+        memo_program: spl_memo::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+        gift,
+        sender_gift,
+        authority,
+    }
+    .to_account_metas(Some(true));
+
+    let data = gift_promo_token_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Requests a promo token for a promo that requires approval. Signed by the token owner.
+pub fn request_promo_mint_instruction(
+    payer: Pubkey,
+    token_owner: Pubkey,
+    campaign: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let mint_request = find_mint_request_address(&mint, &token_owner).0;
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+
+    let accounts = request_promo_mint_accounts {
+        payer,
+        token_owner,
+        campaign,
+        mint,
+        authority,
+        promo,
+        mint_request,
+        token_account,
+        token_program,
+        memo_program: spl_memo::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = request_promo_mint_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Approves a mint request, minting the promo token to the token owner. `request_payer` is the
+/// payer recorded in the request, who gets its rent back.
+pub fn approve_mint_request_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    campaign: Pubkey,
+    token_owner: Pubkey,
+    mint: Pubkey,
+    request_payer: Pubkey,
+    token_program: Pubkey,
+    proof: Vec<[u8; 32]>,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let mint_request = find_mint_request_address(&mint, &token_owner).0;
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+    let location_count = find_location_daily_count_address(&campaign_location).0;
+    let device_count = find_device_daily_count_address(&campaign_location, &device).0;
+
+    let accounts = approve_mint_request_accounts {
+        payer,
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        token_owner,
+        mint,
+        authority,
+        promo,
+        mint_request,
+        request_payer,
+        token_account,
+        location_count,
+        device_count,
+        token_program,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = approve_mint_request_instruction { proof, memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Rejects a mint request. `request_payer` is the payer recorded in the request, who gets its
+/// rent back.
+pub fn reject_mint_request_instruction(
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    campaign: Pubkey,
+    token_owner: Pubkey,
+    mint: Pubkey,
+    request_payer: Pubkey,
+    token_program: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let mint_request = find_mint_request_address(&mint, &token_owner).0;
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+
+    let accounts = reject_mint_request_accounts {
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        token_owner,
+        mint,
+        authority,
+        promo,
+        mint_request,
+        request_payer,
+        token_account,
+        token_program,
+        memo_program: spl_memo::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = reject_mint_request_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn delegate_promo_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    campaign: Pubkey,
+    location: Pubkey,
+    token_owner: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let promo = find_promo_address(&mint).0;
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+
+    let accounts = delegate_promo_token_accounts {
+        payer,
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        token_owner,
+        mint,
+        promo,
+        token_account,
+        memo_program: spl_memo::ID,
+        token_program,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = delegate_promo_token_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn burn_delegated_promo_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    campaign: Pubkey,
+    token_account: Pubkey,
+    mint: Pubkey,
+    platform: Pubkey,
+    token_program: Pubkey,
+    referral: Option<ReferralRewardAccounts>,
+    sponsorship: Option<SponsorshipAccounts>,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let admin_settings = find_admin_address().0;
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+    let location_count = find_location_daily_count_address(&campaign_location).0;
+    let device_count = find_device_daily_count_address(&campaign_location, &device).0;
+
+    let accounts = burn_delegated_promo_token_accounts {
+        payer,
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        mint,
+        authority,
+        promo,
+        platform,
+        admin_settings,
+        token_account,
+        location_count,
+        device_count,
+        memo_program: spl_memo::ID,
+        token_program,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+        referral: referral.map(|r| find_referral_address(&mint, &r.token_owner).0),
+        referrer: referral.map(|r| r.referrer),
+        reward_mint: referral.map(|r| r.reward_mint),
+        reward_promo: referral.map(|r| find_promo_address(&r.reward_mint).0),
+        referrer_token_account: referral
+            .map(|r| find_associated_token_address(&r.referrer, &r.reward_mint, &token_program)),
+        sponsorship: sponsorship.map(|s| find_sponsorship_address(&s.campaign, &s.sponsor).0),
+        payee: sponsorship.map(|s| s.payee),
+        payee_location: sponsorship.map(|_| location),
+        payee_merchant: sponsorship.map(|s| s.payee_merchant),
+    }
+    .to_account_metas(Some(true));
+
+    let data = burn_delegated_promo_token_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn redeem_promo_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    campaign: Pubkey,
+    token_owner: Pubkey,
+    mint: Pubkey,
+    platform: Pubkey,
+    token_program: Pubkey,
+    sponsorship: Option<SponsorshipAccounts>,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+    let admin_settings = find_admin_address().0;
+    let campaign_location = find_campaign_location_address(&campaign, &location).0;
+    let location_count = find_location_daily_count_address(&campaign_location).0;
+    let device_count = find_device_daily_count_address(&campaign_location, &device).0;
+    let token_account = find_associated_token_address(&token_owner, &mint, &token_program);
+
+    let accounts = redeem_promo_token_accounts {
+        payer,
+        device_owner,
+        device,
+        campaign,
+        campaign_location,
+        token_owner,
+        mint,
+        authority,
+        promo,
+        platform,
+        admin_settings,
+        token_account,
+        location_count,
+        device_count,
+        token_program,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+        sponsorship: sponsorship.map(|s| find_sponsorship_address(&s.campaign, &s.sponsor).0),
+        payee: sponsorship.map(|s| s.payee),
+        payee_location: sponsorship.map(|_| location),
+        payee_merchant: sponsorship.map(|s| s.payee_merchant),
+    }
+    .to_account_metas(Some(true));
+
+    let data = redeem_promo_token_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn clawback_promo_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    campaign: Pubkey,
+    mint: Pubkey,
+    token_account: Pubkey,
+    token_program: Pubkey,
+    burn: bool,
+    reason: String,
+) -> Instruction {
+    let merchant = find_merchant_address(&owner).0;
+    let authority = find_authority_address().0;
+    let promo = find_promo_address(&mint).0;
+
+    let accounts = clawback_promo_token_accounts {
+        payer,
+        owner,
+        merchant,
+        campaign,
+        mint,
+        authority,
+        promo,
+        token_account,
+        token_program,
+        memo_program: spl_memo::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = clawback_promo_token_instruction { burn, reason }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn create_sign_memo_instruction(payer: Pubkey, memo: String, signer: Pubkey) -> Instruction {
+    let accounts = sign_memo_accounts {
+        payer,
+        signer,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = sign_memo_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Creates a membership for the merchant of `owner`. `mint` is a new keypair that signs the
+/// transaction and becomes the membership's master edition.
+pub fn create_membership_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    mint: Pubkey,
+    data: DataV2,
+    duration: i64,
+    max_supply: Option<u64>,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+
+    let accounts = create_membership_accounts {
+        payer,
+        owner,
+        merchant: find_merchant_address(&owner).0,
+        authority,
+        mint,
+        token_account: find_associated_token_address(&authority, &mint, &anchor_spl::token::ID),
+        metadata: find_metadata_address(&mint).0,
+        edition: find_edition_address(&mint).0,
+        membership: find_membership_address(&mint).0,
+        metadata_program: mpl_token_metadata::ID,
+        token_program: anchor_spl::token::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        memo_program: spl_memo::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = create_membership_instruction {
+        data,
+        duration,
+        max_supply,
+        memo,
+    }
+    .data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Issues a pass of the membership with master edition `master_mint` to `token_owner`.
+/// `pass_mint` is a new keypair that signs the transaction. `edition` is the edition number of
+/// the new pass, one more than the `pass_count` of the membership.
+pub fn issue_membership_pass_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    master_mint: Pubkey,
+    edition: u64,
+    token_owner: Pubkey,
+    pass_mint: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+
+    let accounts = issue_membership_pass_accounts {
+        payer,
+        owner,
+        merchant: find_merchant_address(&owner).0,
+        membership: find_membership_address(&master_mint).0,
+        authority,
+        master_mint,
+        master_token_account: find_associated_token_address(
+            &authority,
+            &master_mint,
+            &anchor_spl::token::ID,
+        ),
+        master_metadata: find_metadata_address(&master_mint).0,
+        master_edition: find_edition_address(&master_mint).0,
+        token_owner,
+        pass_mint,
+        token_account: find_associated_token_address(
+            &token_owner,
+            &pass_mint,
+            &anchor_spl::token::ID,
+        ),
+        pass_metadata: find_metadata_address(&pass_mint).0,
+        pass_edition: find_edition_address(&pass_mint).0,
+        edition_marker: find_edition_marker_address(&master_mint, edition).0,
+        membership_pass: find_membership_pass_address(&pass_mint).0,
+        metadata_program: mpl_token_metadata::ID,
+        token_program: anchor_spl::token::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        memo_program: spl_memo::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = issue_membership_pass_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Renews the membership pass with mint `pass_mint` for `periods` of the membership's duration.
+/// Signed by the owner of the membership's merchant.
+pub fn renew_membership_pass_instruction(
+    payer: Pubkey,
+    owner: Pubkey,
+    master_mint: Pubkey,
+    pass_mint: Pubkey,
+    periods: u16,
+    memo: Option<String>,
+) -> Instruction {
+    let accounts = renew_membership_pass_accounts {
+        payer,
+        owner,
+        merchant: find_merchant_address(&owner).0,
+        membership: find_membership_address(&master_mint).0,
+        membership_pass: find_membership_pass_address(&pass_mint).0,
+        memo_program: spl_memo::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = renew_membership_pass_instruction { periods, memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Checks in the holder of the membership pass with mint `pass_mint` at `location`. Signed by
+/// the device owner and the token owner.
+pub fn check_in_instruction(
+    payer: Pubkey,
+    device_owner: Pubkey,
+    device: Pubkey,
+    location: Pubkey,
+    master_mint: Pubkey,
+    pass_mint: Pubkey,
+    token_owner: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let accounts = check_in_accounts {
+        payer,
+        device_owner,
+        device,
+        location,
+        membership: find_membership_address(&master_mint).0,
+        membership_pass: find_membership_pass_address(&pass_mint).0,
+        token_owner,
+        token_account: find_associated_token_address(
+            &token_owner,
+            &pass_mint,
+            &anchor_spl::token::ID,
+        ),
+        memo_program: spl_memo::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = check_in_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Reallocates a promo, campaign or campaign location account created before fields were
+/// appended to it to its current size.
+pub fn migrate_account_instruction(
+    payer: Pubkey,
+    account: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    let accounts = migrate_account_accounts {
+        payer,
+        account,
+        memo_program: spl_memo::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = migrate_account_instruction { memo }.data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn create_non_fungible_instruction(
+    payer: Pubkey,
+    mint: Pubkey,
+    data: DataV2,
+    is_mutable: bool,
+    max_supply: Option<u64>,
+) -> Instruction {
+    let authority = find_authority_address().0;
+    let token_account = find_associated_token_address(&payer, &mint, &anchor_spl::token::ID);
+    let metadata_account = find_metadata_address(&mint).0;
+    let edition_account = mpl_token_metadata::pda::find_master_edition_account(&mint).0;
+
+    let accounts = create_non_fungible_accounts {
+        payer,
+        authority,
+        mint,
+        token_account,
+        metadata_account,
+        edition_account,
+        metadata_program: mpl_token_metadata::ID,
+        token_program: anchor_spl::token::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        rent: sysvar::rent::id(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(Some(true));
+
+    let data = create_non_fungible_instruction {
+        data,
+        is_mutable,
+        max_supply,
+    }
+    .data();
+
+    Instruction {
+        program_id: bpl_token_metadata::id(),
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,772 @@
+//! Client for the bokoup token metadata program.
+//!
+//! Builds instructions with their program derived addresses and fetches and decodes program
+//! accounts. Doesn't depend on `solana-client` so that it can be used wherever transactions are
+//! assembled, e.g. the transaction api, the cli and tests.
+
+pub mod accounts;
+pub mod error;
+pub mod instructions;
+
+pub use bpl_token_metadata;
+pub use error::ClientError;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use accounts::{
+        fetch_existing_account, fetch_location, fetch_promo, fetch_referral_reward_accounts,
+        fetch_sponsorship_accounts, AccountFetcher,
+    };
+    use anchor_lang::{
+        prelude::Pubkey, solana_program::system_instruction, AccountSerialize, AnchorSerialize,
+        Discriminator,
+    };
+    use async_trait::async_trait;
+    use bpl_token_metadata::{
+        state::{
+            CampaignLocation, DailyLimits, GiftPolicy, Location, Merchant, Promo, Referral,
+            ReferralReward, Sponsorship, Voucher,
+        },
+        utils::{
+            find_associated_token_address, find_authority_address, find_campaign_address,
+            find_campaign_location_address, find_campaign_member_address,
+            find_customer_vault_address, find_device_daily_count_address,
+            find_edition_marker_address, find_gift_address, find_location_address,
+            find_location_address_v1, find_location_daily_count_address, find_membership_address,
+            find_membership_pass_address, find_merchant_address, find_mint_request_address,
+            find_promo_address, find_referral_address, find_sponsorship_address,
+            find_voucher_claim_address, verify_ed25519_instruction, MAX_NAME_LENGTH,
+        },
+    };
+    use instructions::{
+        approve_mint_request_instruction, burn_delegated_promo_instruction, check_in_instruction,
+        claim_customer_vault_instruction, claim_promo_voucher_instruction,
+        create_campaign_instruction, create_campaign_location_instruction,
+        create_campaign_locations_instruction, gift_promo_token_instruction,
+        issue_membership_pass_instruction, join_campaign_instruction, migrate_account_instruction,
+        mint_promo_instruction, mint_promo_token_to_vault_instruction, redeem_promo_instruction,
+        reject_mint_request_instruction, request_promo_mint_instruction,
+    };
+    use std::collections::HashMap;
+
+    struct TestFetcher(HashMap<Pubkey, Vec<u8>>);
+
+    #[async_trait]
+    impl AccountFetcher for TestFetcher {
+        async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+            Ok(self.0.get(address).cloned())
+        }
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[tokio::test]
+    async fn test_fetch_promo() {
+        let mint = Pubkey::new_unique();
+        let promo = Promo {
+            campaign: Pubkey::new_unique(),
+            mint,
+            metadata: Pubkey::new_unique(),
+            mint_count: 1,
+            burn_count: 0,
+            max_mint: Some(10),
+            max_burn: None,
+            active: true,
+            schedule: None,
+            allowlist: None,
+            referral: None,
+            stamp_threshold: None,
+            terms: None,
+            requires_approval: false,
+            gift_policy: GiftPolicy::Disallowed,
+        };
+        let mut data = vec![];
+        promo.try_serialize(&mut data).unwrap();
+
+        let fetcher = TestFetcher(HashMap::from([(find_promo_address(&mint).0, data)]));
+
+        assert_eq!(fetch_promo(&fetcher, &mint).await.unwrap(), Some(promo));
+        assert_eq!(
+            fetch_promo(&fetcher, &Pubkey::new_unique()).await.unwrap(),
+            None
+        );
+        assert!(matches!(
+            fetch_existing_account::<Promo, _>(&fetcher, &Pubkey::new_unique()).await,
+            Err(ClientError::AccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_mint_promo_instruction() {
+        let [payer, device_owner, device, location, campaign, token_owner, mint] =
+            [(); 7].map(|_| Pubkey::new_unique());
+
+        let ix = mint_promo_instruction(
+            payer,
+            device_owner,
+            device,
+            location,
+            campaign,
+            token_owner,
+            mint,
+            anchor_spl::token::ID,
+            vec![],
+            None,
+            None,
+        );
+
+        let campaign_location = find_campaign_location_address(&campaign, &location).0;
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+
+        assert_eq!(ix.program_id, bpl_token_metadata::id());
+        assert_eq!(keys[4], campaign_location);
+        assert_eq!(keys[8], find_promo_address(&mint).0);
+        assert_eq!(
+            keys[9],
+            find_associated_token_address(&token_owner, &mint, &anchor_spl::token::ID)
+        );
+        assert_eq!(
+            keys[10],
+            find_location_daily_count_address(&campaign_location).0
+        );
+        assert_eq!(
+            keys[11],
+            find_device_daily_count_address(&campaign_location, &device).0
+        );
+    }
+
+    #[test]
+    fn test_claim_promo_voucher_instruction() {
+        let [payer, device_owner, device, location, campaign, token_owner, mint] =
+            [(); 7].map(|_| Pubkey::new_unique());
+        let promo = find_promo_address(&mint).0;
+        let voucher = Voucher {
+            promo,
+            nonce: 7,
+            expiry: 1_700_000_000,
+            recipient: Some(token_owner),
+        };
+
+        let ixs = claim_promo_voucher_instruction(
+            payer,
+            device_owner,
+            device,
+            location,
+            campaign,
+            token_owner,
+            mint,
+            anchor_spl::token::ID,
+            voucher.clone(),
+            [1; 64],
+            vec![],
+            None,
+        );
+
+        assert_eq!(ixs.len(), 2);
+        assert!(verify_ed25519_instruction(
+            &ixs[0],
+            &device_owner,
+            &voucher.try_to_vec().unwrap()
+        ));
+        assert!(!verify_ed25519_instruction(
+            &ixs[0],
+            &payer,
+            &voucher.try_to_vec().unwrap()
+        ));
+
+        let keys: Vec<Pubkey> = ixs[1].accounts.iter().map(|a| a.pubkey).collect();
+        assert_eq!(ixs[1].program_id, bpl_token_metadata::id());
+        assert_eq!(keys[8], promo);
+        assert_eq!(keys[9], find_voucher_claim_address(&promo, 7).0);
+    }
+
+    #[test]
+    fn test_mint_request_instructions() {
+        let [payer, device_owner, device, location, campaign, token_owner, mint] =
+            [(); 7].map(|_| Pubkey::new_unique());
+        let mint_request = find_mint_request_address(&mint, &token_owner).0;
+
+        let request_ix = request_promo_mint_instruction(
+            payer,
+            token_owner,
+            campaign,
+            mint,
+            anchor_spl::token::ID,
+            None,
+        );
+        let approve_ix = approve_mint_request_instruction(
+            device_owner,
+            device_owner,
+            device,
+            location,
+            campaign,
+            token_owner,
+            mint,
+            payer,
+            anchor_spl::token::ID,
+            vec![],
+            None,
+        );
+        let reject_ix = reject_mint_request_instruction(
+            device_owner,
+            device,
+            location,
+            campaign,
+            token_owner,
+            mint,
+            payer,
+            anchor_spl::token::ID,
+            None,
+        );
+
+        assert_eq!(request_ix.accounts[6].pubkey, mint_request);
+        assert!(request_ix.accounts[1].is_signer);
+        assert_eq!(approve_ix.accounts[9].pubkey, mint_request);
+        assert_eq!(approve_ix.accounts[10].pubkey, payer);
+        assert!(!approve_ix.accounts[5].is_signer);
+        assert_eq!(reject_ix.accounts[8].pubkey, mint_request);
+        assert_eq!(reject_ix.accounts[9].pubkey, payer);
+    }
+
+    #[test]
+    fn test_customer_vault_instructions() {
+        let [payer, device_owner, device, location, campaign, token_owner, mint] =
+            [(); 7].map(|_| Pubkey::new_unique());
+        let identity_hash = [7; 32];
+        let customer_vault = find_customer_vault_address(&identity_hash).0;
+        let vault_token_account =
+            find_associated_token_address(&customer_vault, &mint, &anchor_spl::token::ID);
+
+        let mint_ix = mint_promo_token_to_vault_instruction(
+            payer,
+            device_owner,
+            device,
+            location,
+            campaign,
+            identity_hash,
+            mint,
+            anchor_spl::token::ID,
+            vec![],
+            None,
+        );
+        let claim_ix = claim_customer_vault_instruction(
+            payer,
+            payer,
+            identity_hash,
+            token_owner,
+            mint,
+            anchor_spl::token::ID,
+            None,
+        );
+
+        assert_eq!(mint_ix.accounts[5].pubkey, customer_vault);
+        assert!(!mint_ix.accounts[5].is_signer);
+        assert_eq!(mint_ix.accounts[9].pubkey, vault_token_account);
+        assert_eq!(claim_ix.accounts[3].pubkey, customer_vault);
+        assert!(claim_ix.accounts[1].is_signer);
+        assert!(claim_ix.accounts[4].is_signer);
+        assert_eq!(claim_ix.accounts[7].pubkey, vault_token_account);
+    }
+
+    #[test]
+    fn test_gift_promo_token_instruction() {
+        let [payer, sender, recipient, mint] = [(); 4].map(|_| Pubkey::new_unique());
+
+        let ix = gift_promo_token_instruction(
+            payer,
+            sender,
+            recipient,
+            mint,
+            anchor_spl::token::ID,
+            GiftPolicy::Allowed,
+            None,
+        );
+        let once_ix = gift_promo_token_instruction(
+            payer,
+            sender,
+            recipient,
+            mint,
+            anchor_spl::token::ID,
+            GiftPolicy::AllowedOnce,
+            None,
+        );
+
+        assert!(ix.accounts[1].is_signer);
+        assert!(!ix.accounts[2].is_signer);
+        assert_eq!(
+            ix.accounts[6].pubkey,
+            find_associated_token_address(&recipient, &mint, &anchor_spl::token::ID)
+        );
+        // optional gift accounts are replaced by the program id when not needed
+        assert_eq!(ix.accounts[12].pubkey, bpl_token_metadata::id());
+        assert_eq!(ix.accounts[13].pubkey, bpl_token_metadata::id());
+        assert_eq!(
+            once_ix.accounts[12].pubkey,
+            find_gift_address(&mint, &recipient).0
+        );
+        assert_eq!(
+            once_ix.accounts[13].pubkey,
+            find_gift_address(&mint, &sender).0
+        );
+        assert!(ix.accounts[3].is_writable);
+        assert_eq!(ix.accounts[14].pubkey, find_authority_address().0);
+    }
+
+    #[test]
+    fn test_membership_instructions() {
+        let [payer, owner, master_mint, token_owner, pass_mint, device, location] =
+            [(); 7].map(|_| Pubkey::new_unique());
+
+        let issue_ix = issue_membership_pass_instruction(
+            payer,
+            owner,
+            master_mint,
+            249,
+            token_owner,
+            pass_mint,
+            None,
+        );
+        let check_in_ix = check_in_instruction(
+            payer,
+            owner,
+            device,
+            location,
+            master_mint,
+            pass_mint,
+            token_owner,
+            None,
+        );
+
+        // The master edition token is held by the program authority.
+        assert_eq!(
+            issue_ix.accounts[6].pubkey,
+            find_associated_token_address(
+                &find_authority_address().0,
+                &master_mint,
+                &anchor_spl::token::ID
+            )
+        );
+        assert!(!issue_ix.accounts[9].is_signer);
+        assert!(issue_ix.accounts[10].is_signer);
+        // Editions 248 to 495 share the second edition marker.
+        assert_eq!(
+            issue_ix.accounts[14].pubkey,
+            find_edition_marker_address(&master_mint, 248).0
+        );
+        assert_ne!(
+            issue_ix.accounts[14].pubkey,
+            find_edition_marker_address(&master_mint, 247).0
+        );
+        assert_eq!(
+            issue_ix.accounts[15].pubkey,
+            find_membership_pass_address(&pass_mint).0
+        );
+        assert_eq!(
+            check_in_ix.accounts[4].pubkey,
+            find_membership_address(&master_mint).0
+        );
+        assert!(check_in_ix.accounts[6].is_signer);
+    }
+
+    #[test]
+    fn test_create_campaign_instruction() {
+        let [payer, owner] = [(); 2].map(|_| Pubkey::new_unique());
+
+        let ixs = create_campaign_instruction(
+            payer,
+            owner,
+            "Test Campaign".to_string(),
+            "https://example.com".to_string(),
+            1_000_000,
+            vec![],
+            None,
+            None,
+            true,
+            None,
+        );
+
+        let campaign = find_campaign_address(&find_merchant_address(&owner).0, "Test Campaign").0;
+
+        // The owner only signs, so it can be a multisig or a PDA signing through cpi.
+        assert!(ixs[0].accounts[0].is_writable);
+        assert!(ixs[0].accounts[1].is_signer);
+        assert!(!ixs[0].accounts[1].is_writable);
+
+        // The owner's funding is a separate system transfer to the campaign.
+        assert_eq!(ixs.len(), 2);
+        assert_eq!(
+            ixs[1],
+            system_instruction::transfer(&owner, &campaign, 1_000_000)
+        );
+    }
+
+    #[test]
+    fn test_join_campaign_instruction() {
+        let [payer, campaign_owner, campaign, owner, location] =
+            [(); 5].map(|_| Pubkey::new_unique());
+
+        let ixs =
+            join_campaign_instruction(payer, campaign_owner, campaign, owner, vec![location], None);
+
+        let merchant = find_merchant_address(&owner).0;
+        let campaign_member = find_campaign_member_address(&campaign, &merchant).0;
+
+        assert_eq!(ixs.len(), 2);
+        assert_eq!(ixs[0].accounts[1].pubkey, campaign_owner);
+        assert!(ixs[0].accounts[1].is_signer);
+        assert_eq!(ixs[0].accounts[4].pubkey, owner);
+        assert!(ixs[0].accounts[4].is_signer);
+        assert_eq!(ixs[0].accounts[6].pubkey, campaign_member);
+        assert_eq!(ixs[1].accounts[2].pubkey, merchant);
+        assert_eq!(ixs[1].accounts[6].pubkey, campaign_member);
+        assert_eq!(ixs[1].accounts[7].pubkey, location);
+        assert_eq!(
+            ixs[1].accounts[8].pubkey,
+            find_campaign_location_address(&campaign, &location).0
+        );
+
+        // Optional campaign member account is left out for the merchant that created the campaign.
+        let ix =
+            create_campaign_location_instruction(payer, owner, campaign, location, false, None);
+        assert_eq!(ix.accounts[8].pubkey, bpl_token_metadata::id());
+    }
+
+    #[test]
+    fn test_create_campaign_locations_instruction() {
+        let [payer, owner, campaign] = [(); 3].map(|_| Pubkey::new_unique());
+        let locations: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        let ix =
+            create_campaign_locations_instruction(payer, owner, campaign, &locations, false, None);
+
+        // Fixed accounts, with the campaign member left out, followed by a pair per location.
+        assert_eq!(ix.accounts.len(), 7 + 2 * locations.len());
+        assert_eq!(ix.accounts[6].pubkey, bpl_token_metadata::id());
+        for (i, location) in locations.iter().enumerate() {
+            let location_meta = &ix.accounts[7 + 2 * i];
+            let campaign_location_meta = &ix.accounts[8 + 2 * i];
+            assert_eq!(location_meta.pubkey, *location);
+            assert!(!location_meta.is_writable);
+            assert_eq!(
+                campaign_location_meta.pubkey,
+                find_campaign_location_address(&campaign, location).0
+            );
+            assert!(campaign_location_meta.is_writable);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_account() {
+        let [payer, campaign, mint, metadata, location] = [(); 5].map(|_| Pubkey::new_unique());
+
+        let ix = migrate_account_instruction(payer, mint, None);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[1].pubkey, mint);
+        assert!(ix.accounts[1].is_writable);
+
+        // Accounts created before fields were appended only decode once they have been
+        // reallocated, after which the appended fields are zeroed and read as their defaults.
+        let mut promo_data = Promo::DISCRIMINATOR.to_vec();
+        (
+            campaign,
+            mint,
+            metadata,
+            2u32,
+            1u32,
+            Some(10u32),
+            None::<u32>,
+            true,
+        )
+            .serialize(&mut promo_data)
+            .unwrap();
+        let mut campaign_location_data = CampaignLocation::DISCRIMINATOR.to_vec();
+        (campaign, location)
+            .serialize(&mut campaign_location_data)
+            .unwrap();
+
+        let mut fetcher = TestFetcher(HashMap::from([
+            (metadata, promo_data.clone()),
+            (location, campaign_location_data.clone()),
+        ]));
+        assert!(fetch_existing_account::<Promo, _>(&fetcher, &metadata)
+            .await
+            .is_err());
+        assert!(
+            fetch_existing_account::<CampaignLocation, _>(&fetcher, &location)
+                .await
+                .is_err()
+        );
+
+        promo_data.resize(Promo::LEN, 0);
+        campaign_location_data.resize(CampaignLocation::LEN, 0);
+        fetcher.0.insert(metadata, promo_data);
+        fetcher.0.insert(location, campaign_location_data);
+
+        let promo = fetch_existing_account::<Promo, _>(&fetcher, &metadata)
+            .await
+            .unwrap();
+        assert_eq!(promo.mint_count, 2);
+        assert_eq!(promo.max_mint, Some(10));
+        assert!(promo.active);
+        assert_eq!(promo.schedule, None);
+        assert_eq!(promo.referral, None);
+        assert_eq!(promo.terms, None);
+        assert!(!promo.requires_approval);
+        assert_eq!(promo.gift_policy, GiftPolicy::Disallowed);
+
+        let campaign_location = fetch_existing_account::<CampaignLocation, _>(&fetcher, &location)
+            .await
+            .unwrap();
+        assert_eq!(campaign_location.location, location);
+        assert_eq!(campaign_location.location_limits, DailyLimits::default());
+        assert_eq!(campaign_location.device_limits, DailyLimits::default());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_location_by_name() {
+        let merchant_owner = Pubkey::new_unique();
+        let location = |name: &str| Location {
+            merchant: find_merchant_address(&merchant_owner).0,
+            name: name.to_string(),
+            uri: "https://arweave.net/location".to_string(),
+            active: true,
+        };
+
+        // created before names were hashed into seeds
+        let legacy_name = "Test Location";
+        let legacy = location(legacy_name);
+        let legacy_address = find_location_address_v1(&merchant_owner, legacy_name)
+            .unwrap()
+            .0;
+        assert_ne!(
+            legacy_address,
+            find_location_address(&merchant_owner, legacy_name).0
+        );
+
+        // longer than a seed and not ascii
+        let long_name = "東京駅八重洲北口店 テストロケーション";
+        assert!(long_name.len() > 32 && long_name.len() <= MAX_NAME_LENGTH);
+        assert_eq!(find_location_address_v1(&merchant_owner, long_name), None);
+        let long = location(long_name);
+
+        let fetcher = TestFetcher(HashMap::from([
+            (legacy_address, serialize(&legacy)),
+            (
+                find_location_address(&merchant_owner, long_name).0,
+                serialize(&long),
+            ),
+        ]));
+
+        assert_eq!(
+            fetch_location(&fetcher, &merchant_owner, legacy_name)
+                .await
+                .unwrap(),
+            Some(legacy)
+        );
+        assert_eq!(
+            fetch_location(&fetcher, &merchant_owner, long_name)
+                .await
+                .unwrap(),
+            Some(long)
+        );
+        assert_eq!(
+            fetch_location(&fetcher, &merchant_owner, "Unknown")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_referral_reward_accounts() {
+        let [mint, reward_mint, token_owner, referrer, token_account] =
+            [(); 5].map(|_| Pubkey::new_unique());
+        let promo = Promo {
+            campaign: Pubkey::new_unique(),
+            mint,
+            metadata: Pubkey::new_unique(),
+            mint_count: 1,
+            burn_count: 0,
+            max_mint: None,
+            max_burn: None,
+            active: true,
+            schedule: None,
+            allowlist: None,
+            referral: Some(ReferralReward {
+                reward_mint,
+                max_rewards: Some(100),
+                reward_count: 0,
+            }),
+            stamp_threshold: None,
+            terms: None,
+            requires_approval: false,
+            gift_policy: GiftPolicy::Disallowed,
+        };
+        let mut referral = Referral {
+            mint,
+            referrer,
+            referred: token_owner,
+            rewarded: false,
+        };
+
+        let mut fetcher = TestFetcher(HashMap::from([
+            (find_promo_address(&mint).0, serialize(&promo)),
+            (
+                find_referral_address(&mint, &token_owner).0,
+                serialize(&referral),
+            ),
+        ]));
+
+        let accounts = fetch_referral_reward_accounts(&fetcher, &mint, &token_owner)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(accounts.referrer, referrer);
+        assert_eq!(accounts.reward_mint, reward_mint);
+        assert!(
+            fetch_referral_reward_accounts(&fetcher, &mint, &Pubkey::new_unique())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let [payer, device_owner, device, location, campaign, platform] =
+            [(); 6].map(|_| Pubkey::new_unique());
+        let burn = |referral| {
+            burn_delegated_promo_instruction(
+                payer,
+                device_owner,
+                device,
+                location,
+                campaign,
+                token_account,
+                mint,
+                platform,
+                anchor_spl::token::ID,
+                referral,
+                None,
+                None,
+            )
+        };
+
+        let keys: Vec<Pubkey> = burn(Some(accounts))
+            .accounts
+            .iter()
+            .map(|a| a.pubkey)
+            .collect();
+        assert_eq!(
+            keys[keys.len() - 9..keys.len() - 4],
+            [
+                find_referral_address(&mint, &token_owner).0,
+                referrer,
+                reward_mint,
+                find_promo_address(&reward_mint).0,
+                find_associated_token_address(&referrer, &reward_mint, &anchor_spl::token::ID),
+            ]
+        );
+
+        // Optional accounts that aren't included are replaced by the program id.
+        let keys: Vec<Pubkey> = burn(None).accounts.iter().map(|a| a.pubkey).collect();
+        assert!(keys[keys.len() - 9..]
+            .iter()
+            .all(|key| *key == bpl_token_metadata::id()));
+
+        // No reward once the referrer has been rewarded.
+        referral.rewarded = true;
+        fetcher.0.insert(
+            find_referral_address(&mint, &token_owner).0,
+            serialize(&referral),
+        );
+        assert!(
+            fetch_referral_reward_accounts(&fetcher, &mint, &token_owner)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sponsorship_accounts() {
+        let [campaign, sponsor, location, payee_merchant, payee, mint, other_mint] =
+            [(); 7].map(|_| Pubkey::new_unique());
+        let sponsorship = Sponsorship {
+            sponsor,
+            campaign,
+            mints: vec![mint],
+            reimbursement_lamports: 1_000_000,
+            ..Default::default()
+        };
+        let fetcher = TestFetcher(HashMap::from([
+            (
+                find_sponsorship_address(&campaign, &sponsor).0,
+                serialize(&sponsorship),
+            ),
+            (
+                location,
+                serialize(&Location {
+                    merchant: payee_merchant,
+                    name: "location".to_string(),
+                    uri: "https://example.com".to_string(),
+                    active: true,
+                }),
+            ),
+            (
+                payee_merchant,
+                serialize(&Merchant {
+                    owner: payee,
+                    name: "merchant".to_string(),
+                    uri: "https://example.com".to_string(),
+                    active: true,
+                }),
+            ),
+        ]));
+
+        // The owner of the merchant of the redeeming location is reimbursed.
+        let accounts = fetch_sponsorship_accounts(&fetcher, &campaign, &sponsor, &mint, &location)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(accounts.payee_merchant, payee_merchant);
+        assert_eq!(accounts.payee, payee);
+
+        // Promos the sponsorship doesn't cover aren't paid for by the sponsor.
+        assert!(
+            fetch_sponsorship_accounts(&fetcher, &campaign, &sponsor, &other_mint, &location)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let [payer, device_owner, device, token_owner, platform] =
+            [(); 5].map(|_| Pubkey::new_unique());
+        let ix = redeem_promo_instruction(
+            payer,
+            device_owner,
+            device,
+            location,
+            campaign,
+            token_owner,
+            mint,
+            platform,
+            anchor_spl::token::ID,
+            Some(accounts),
+            None,
+        );
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+        assert_eq!(
+            keys[keys.len() - 4..],
+            [
+                find_sponsorship_address(&campaign, &sponsor).0,
+                payee,
+                location,
+                payee_merchant
+            ]
+        );
+        assert!(ix.accounts[ix.accounts.len() - 3].is_writable);
+    }
+}
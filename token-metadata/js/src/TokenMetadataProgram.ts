@@ -1,6 +1,6 @@
 import fetch from 'cross-fetch';
 import { PublicKey, Keypair } from '@solana/web3.js';
-import { Program, Provider, Wallet, Idl, AnchorProvider, BN } from '@project-serum/anchor';
+import { Program, Provider, Wallet, Idl, AnchorProvider, BN, utils } from '@project-serum/anchor';
 import {
   Metadata,
   MasterEditionV2,
@@ -11,6 +11,8 @@ import {
   getMint,
   Account as TokenAccount,
   Mint,
+  MintLayout,
+  MINT_SIZE,
 } from '@solana/spl-token';
 import idl from '../../../target/idl/bpl_token_metadata.json';
 import {
@@ -25,14 +27,27 @@ import {
   Location,
   Merchant,
   CampaignLocation,
+  PromoExtensions,
+  PromoOptions,
+  PromoSchedule,
+  ReferralReward,
+  PromoTerms,
+  GiftPolicy,
 } from '.';
 const camelcaseKeysDeep = require('camelcase-keys-deep');
 
+const NO_PROMO_EXTENSIONS: PromoExtensions = {
+  nonTransferable: false,
+  permanentDelegate: false,
+  metadataPointer: false,
+};
+
 export class TokenMetadataProgram {
   readonly PUBKEY: PublicKey;
 
   readonly SPL_ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID: PublicKey;
   readonly TOKEN_PROGRAM_ID: PublicKey;
+  readonly TOKEN_2022_PROGRAM_ID: PublicKey;
   readonly TOKEN_METADATA_PROGRAM_ID: PublicKey;
   readonly MEMO_PROGRAM_ID: PublicKey;
 
@@ -44,6 +59,7 @@ export class TokenMetadataProgram {
   readonly CAMPAIGN_PREFIX: string;
   readonly CAMPAIGN_LOCATION_PREFIX: string;
   readonly PROMO_PREFIX: string;
+  readonly REFERRAL_PREFIX: string;
   readonly METADATA_PREFIX: string;
   readonly EDITION_PREFIX: string;
 
@@ -56,6 +72,7 @@ export class TokenMetadataProgram {
       'ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL',
     );
     this.TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
+    this.TOKEN_2022_PROGRAM_ID = new PublicKey('TokenzQdBNbLqP5VEhdkAS6EPFLC1PJnBZvsQ7KXUjnKZ');
     this.TOKEN_METADATA_PROGRAM_ID = METADATA_PROGRAM_ID;
     this.MEMO_PROGRAM_ID = new PublicKey('MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr');
 
@@ -67,6 +84,7 @@ export class TokenMetadataProgram {
     this.CAMPAIGN_PREFIX = 'campaign';
     this.CAMPAIGN_LOCATION_PREFIX = 'campaign_location';
     this.PROMO_PREFIX = 'promo';
+    this.REFERRAL_PREFIX = 'referral';
     this.METADATA_PREFIX = 'metadata';
     this.EDITION_PREFIX = 'edition';
 
@@ -275,6 +293,8 @@ export class TokenMetadataProgram {
    * @param isMutable     Whether metadata is mutable
    * @param maxMint       Optional Max number of tokens to mint
    * @param maxRedeemable Optional max number of tokens that can used
+   * @param extensions    Token-2022 extensions, the mint is created with Token-2022 if any are set
+   * @param options       Optional promo fields, such as the schedule, terms or gift policy
    *
    * @return Address of promo account
    */
@@ -287,6 +307,8 @@ export class TokenMetadataProgram {
     maxBurn: number | null,
     platform: PublicKey,
     memo: string | null,
+    extensions: PromoExtensions = NO_PROMO_EXTENSIONS,
+    options: Partial<PromoOptions> = {},
   ): Promise<PublicKey> {
     const mint = Keypair.generate();
 
@@ -301,10 +323,23 @@ export class TokenMetadataProgram {
       burnCount: 0,
       maxMint,
       maxBurn,
+      active: true,
+      schedule: null,
+      allowlist: null,
+      referral: null,
+      stampThreshold: null,
+      terms: null,
+      requiresApproval: false,
+      giftPolicy: { disallowed: {} },
+      ...options,
     };
 
+    const tokenProgram = Object.values(extensions).some((enabled) => enabled)
+      ? this.TOKEN_2022_PROGRAM_ID
+      : this.TOKEN_PROGRAM_ID;
+
     await this.program.methods
-      .createPromo(promoData, metadataData, isMutable, memo)
+      .createPromo(promoData, metadataData, isMutable, extensions, memo)
       .accounts({
         payer: payer.publicKey,
         owner: this.payer.publicKey,
@@ -314,6 +349,7 @@ export class TokenMetadataProgram {
         metadata,
         platform,
         metadataProgram: this.TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram,
         memoProgram: this.MEMO_PROGRAM_ID,
       })
       .signers([mint, payer])
@@ -327,6 +363,8 @@ export class TokenMetadataProgram {
    *
    * @param mint        Promo mint
    * @param deviceOwner Keypair of device owner
+   * @param proof       Merkle proof that the token owner is on the promo's allowlist
+   * @param referrer    Wallet that referred the token owner to the promo
    *
    * @return Address of token account account
    */
@@ -337,11 +375,14 @@ export class TokenMetadataProgram {
     device: PublicKey,
     campaign: PublicKey,
     memo: string | null,
+    proof: number[][] = [],
+    referrer: PublicKey | null = null,
   ): Promise<PublicKey> {
     const tokenAccount = this.findAssociatedTokenAccountAddress(mint, this.payer.publicKey);
+    const referral = referrer ? this.findReferralAddress(mint, this.payer.publicKey) : null;
 
     await this.program.methods
-      .mintPromoToken(memo)
+      .mintPromoToken(proof, referrer, memo)
       .accounts({
         payer: payer.publicKey,
         deviceOwner: deviceOwner.publicKey,
@@ -351,6 +392,7 @@ export class TokenMetadataProgram {
         mint,
         tokenAccount,
         memoProgram: this.MEMO_PROGRAM_ID,
+        referral,
       })
       .signers([payer, deviceOwner, this.payer.payer])
       .rpc();
@@ -489,7 +531,22 @@ export class TokenMetadataProgram {
   }
 
   async getMintAccount(address: PublicKey): Promise<Mint> {
-    return await getMint(this.program.provider.connection, address);
+    const connection = this.program.provider.connection;
+    const accountInfo = await connection.getAccountInfo(address);
+    if (accountInfo === null || !accountInfo.owner.equals(this.TOKEN_2022_PROGRAM_ID)) {
+      return await getMint(connection, address);
+    }
+    // Token-2022 mints with extensions are longer than spl-token mints, but start with the
+    // same layout.
+    const rawMint = MintLayout.decode(accountInfo.data.slice(0, MINT_SIZE));
+    return {
+      address,
+      mintAuthority: rawMint.mintAuthorityOption ? rawMint.mintAuthority : null,
+      supply: rawMint.supply,
+      decimals: rawMint.decimals,
+      isInitialized: rawMint.isInitialized,
+      freezeAuthority: rawMint.freezeAuthorityOption ? rawMint.freezeAuthority : null,
+    };
   }
 
   async getMetadataAccount(address: PublicKey): Promise<Metadata> {
@@ -585,23 +642,28 @@ export class TokenMetadataProgram {
     )[0];
   }
 
+  /** Names are hashed into seeds so that they can be longer than 32 bytes. */
+  nameSeed(name: string): Buffer {
+    return Buffer.from(utils.sha256.hash(name), 'hex');
+  }
+
   findLocationAddress(merchant: PublicKey, name: string): PublicKey {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from(this.LOCATION_PREFIX), merchant.toBuffer(), Buffer.from(name)],
+      [Buffer.from(this.LOCATION_PREFIX), merchant.toBuffer(), this.nameSeed(name)],
       this.PUBKEY,
     )[0];
   }
 
   findDeviceAddress(location: PublicKey, name: string): PublicKey {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from(this.DEVICE_PREFIX), location.toBuffer(), Buffer.from(name)],
+      [Buffer.from(this.DEVICE_PREFIX), location.toBuffer(), this.nameSeed(name)],
       this.PUBKEY,
     )[0];
   }
 
   findCampaignAddress(merchant: PublicKey, name: string): PublicKey {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from(this.CAMPAIGN_PREFIX), merchant.toBuffer(), Buffer.from(name)],
+      [Buffer.from(this.CAMPAIGN_PREFIX), merchant.toBuffer(), this.nameSeed(name)],
       this.PUBKEY,
     )[0];
   }
@@ -613,6 +675,13 @@ export class TokenMetadataProgram {
     )[0];
   }
 
+  findReferralAddress(mint: PublicKey, referred: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(this.REFERRAL_PREFIX), mint.toBuffer(), referred.toBuffer()],
+      this.PUBKEY,
+    )[0];
+  }
+
   findMetadataAddress(mint: PublicKey): PublicKey {
     return PublicKey.findProgramAddressSync(
       [
@@ -684,6 +753,14 @@ export class PromoExtendedImpl implements PromoExtended {
   burnCount: number;
   maxMint: number | null;
   maxBurn: number | null;
+  active: boolean;
+  schedule: PromoSchedule | null;
+  allowlist: number[] | null;
+  referral: ReferralReward | null;
+  stampThreshold: number | null;
+  terms: PromoTerms | null;
+  requiresApproval: boolean;
+  giftPolicy: GiftPolicy;
   publicKey: PublicKey;
   mintAccount: Mint;
   metadataAccount: Metadata;
@@ -708,5 +785,13 @@ export class PromoExtendedImpl implements PromoExtended {
     this.burnCount = promoAccount.burnCount;
     this.maxMint = promoAccount.maxMint;
     this.maxBurn = promoAccount.maxBurn;
+    this.active = promoAccount.active;
+    this.schedule = promoAccount.schedule;
+    this.allowlist = promoAccount.allowlist;
+    this.referral = promoAccount.referral;
+    this.stampThreshold = promoAccount.stampThreshold;
+    this.terms = promoAccount.terms;
+    this.requiresApproval = promoAccount.requiresApproval;
+    this.giftPolicy = promoAccount.giftPolicy;
   }
 }
@@ -62,6 +62,58 @@ export type Promo = {
   burnCount: number;
   maxMint: number | null;
   maxBurn: number | null;
+  active: boolean;
+  schedule: PromoSchedule | null;
+  allowlist: number[] | null;
+  referral: ReferralReward | null;
+  stampThreshold: number | null;
+  terms: PromoTerms | null;
+  requiresApproval: boolean;
+  giftPolicy: GiftPolicy;
+};
+
+// Promo fields that can be set when creating a promo.
+export type PromoOptions = Pick<
+  Promo,
+  | 'schedule'
+  | 'allowlist'
+  | 'referral'
+  | 'stampThreshold'
+  | 'terms'
+  | 'requiresApproval'
+  | 'giftPolicy'
+>;
+
+export type TimeWindow = {
+  start: number;
+  end: number;
+};
+
+export type PromoSchedule = {
+  days: number;
+  windows: TimeWindow[];
+  utcOffset: number;
+  checkMint: boolean;
+};
+
+export type ReferralReward = {
+  rewardMint: PublicKey;
+  maxRewards: number | null;
+  rewardCount: number;
+};
+
+export type PromoTerms =
+  | { buyXProductGetYFree: { productId: string; buyXProduct: number; getYProduct: number } }
+  | { buyXCurrencyGetYPercent: { buyXCurrency: BN; getYPercent: number } }
+  | { amountOff: { buyXCurrency: BN; amountOff: BN } }
+  | { freeItem: { productId: string } };
+
+export type GiftPolicy = { disallowed: {} } | { allowed: {} } | { allowedOnce: {} };
+
+export type PromoExtensions = {
+  nonTransferable: boolean;
+  permanentDelegate: boolean;
+  metadataPointer: boolean;
 };
 
 export interface PromoExtended extends Promo {
@@ -13,12 +13,16 @@ import {
   AuctionHouseProgram,
   Merchant,
   CampaignLocation,
+  PromoExtensions,
+  PromoSchedule,
+  PromoTerms,
 } from '../src';
 import { PublicKey, Keypair, Transaction, Connection } from '@solana/web3.js';
 import chai = require('chai');
 import chaiAsPromised = require('chai-as-promised');
 chai.use(chaiAsPromised);
 const expect = chai.expect;
+import { createHash } from 'crypto';
 import * as dotenv from 'dotenv';
 import path from 'path';
 dotenv.config({ path: path.resolve(__dirname, '../../../.env') });
@@ -252,6 +256,69 @@ describe('promo', () => {
       .rpc();
   });
 
+  it('joins campaign', async () => {
+    const memberOwner = Keypair.generate();
+    const tokenMetadataProgramMemberOwner = new TokenMetadataProgram(
+      new anchor.AnchorProvider(connection, new anchor.Wallet(memberOwner), options),
+    );
+
+    let _ = '';
+    let memberMerchant: PublicKey;
+    [_, memberMerchant] = await tokenMetadataProgramMemberOwner.createMerchant(
+      {
+        owner: memberOwner.publicKey,
+        name: 'Test Member Merchant',
+        uri: 'https://member.example.com',
+        active: true,
+      },
+      platformSigner,
+      null,
+    );
+
+    const campaignMember = PublicKey.findProgramAddressSync(
+      [Buffer.from('campaign_member'), campaign.toBuffer(), memberMerchant.toBuffer()],
+      tokenMetadataProgram.PUBKEY,
+    )[0];
+
+    // The campaign's own merchant is already part of the campaign
+    await expect(
+      tokenMetadataProgramMerchantOwner.program.methods
+        .joinCampaign(null)
+        .accounts({
+          payer: platformSigner.publicKey,
+          campaignOwner: merchantOwner.publicKey,
+          campaignMerchant: merchant,
+          campaign,
+          owner: merchantOwner.publicKey,
+          merchant,
+          memoProgram: tokenMetadataProgram.MEMO_PROGRAM_ID,
+        })
+        .signers([platformSigner])
+        .rpc(),
+    ).to.be.rejectedWith('ConstraintRaw');
+
+    await tokenMetadataProgramMerchantOwner.program.methods
+      .joinCampaign('Joined campaign')
+      .accounts({
+        payer: platformSigner.publicKey,
+        campaignOwner: merchantOwner.publicKey,
+        campaignMerchant: merchant,
+        campaign,
+        owner: memberOwner.publicKey,
+        merchant: memberMerchant,
+        campaignMember,
+        memoProgram: tokenMetadataProgram.MEMO_PROGRAM_ID,
+      })
+      .signers([platformSigner, memberOwner])
+      .rpc();
+
+    const account = await tokenMetadataProgram.program.account.campaignMember.fetch(
+      campaignMember,
+    );
+    expect(account.campaign.toString()).to.equal(campaign.toString());
+    expect(account.merchant.toString()).to.equal(memberMerchant.toString());
+  });
+
   it('Creates two promos', async () => {
     const metadataData1: DataV2 = {
       name: 'Test Promo',
@@ -315,6 +382,204 @@ describe('promo', () => {
     }
   });
 
+  it('Creates a Token-2022 promo with extensions', async () => {
+    const metadataData: DataV2 = {
+      name: 'Test Promo 2022',
+      symbol: 'BTP22',
+      uri: 'https://arweave.net/frDiuZYzSVwYTwSUMR1YbggVkZqZfA7S9xsI3drPWBo',
+      sellerFeeBasisPoints: 0,
+      creators: null,
+      collection: null,
+      uses: null,
+    };
+
+    const extensions: PromoExtensions = {
+      nonTransferable: true,
+      permanentDelegate: true,
+      metadataPointer: true,
+    };
+
+    const promoMint = await tokenMetadataProgramMerchantOwner.createPromo(
+      platformSigner,
+      metadataData,
+      campaign,
+      true,
+      10,
+      5,
+      adminSettingsAccount.platform,
+      null,
+      extensions,
+      { stampThreshold: 3, giftPolicy: { allowedOnce: {} } },
+    );
+
+    const mintAccountInfo = await connection.getAccountInfo(promoMint);
+    expect(mintAccountInfo?.owner.toString()).to.equal(
+      tokenMetadataProgram.TOKEN_2022_PROGRAM_ID.toString(),
+      'Mint owner incorrect.',
+    );
+    expect(mintAccountInfo?.data.length).to.equal(274, 'Mint length incorrect.');
+
+    const promo2022 = await tokenMetadataProgram.getPromoExtended(promoMint);
+    expect(promo2022.mintAccount.mintAuthority?.toString()).to.equal(
+      tokenMetadataProgram.findAuthorityAddress().toString(),
+      'Mint authority incorrect.',
+    );
+    expect(promo2022.stampThreshold).to.equal(3, 'Stamp threshold incorrect.');
+    expect(promo2022.giftPolicy).to.deep.equal({ allowedOnce: {} }, 'Gift policy incorrect.');
+    expect(promo2022.requiresApproval).to.equal(false, 'Requires approval incorrect.');
+  });
+
+  it('Rejects gifting once without the non-transferable extension', async () => {
+    const metadataData: DataV2 = {
+      name: 'Test Promo Gift',
+      symbol: 'BTPG',
+      uri: 'https://arweave.net/frDiuZYzSVwYTwSUMR1YbggVkZqZfA7S9xsI3drPWBo',
+      sellerFeeBasisPoints: 0,
+      creators: null,
+      collection: null,
+      uses: null,
+    };
+
+    await expect(
+      tokenMetadataProgramMerchantOwner.createPromo(
+        platformSigner,
+        metadataData,
+        campaign,
+        true,
+        10,
+        5,
+        adminSettingsAccount.platform,
+        null,
+        { nonTransferable: false, permanentDelegate: true, metadataPointer: false },
+        { giftPolicy: { allowedOnce: {} } },
+      ),
+    ).to.be.rejectedWith('GiftOnceRequiresNonTransferable');
+  });
+
+  it('Creates a promo with a schedule', async () => {
+    const metadataData: DataV2 = {
+      name: 'Test Promo Happy Hour',
+      symbol: 'BTPH',
+      uri: 'https://arweave.net/frDiuZYzSVwYTwSUMR1YbggVkZqZfA7S9xsI3drPWBo',
+      sellerFeeBasisPoints: 0,
+      creators: null,
+      collection: null,
+      uses: null,
+    };
+
+    // Weekdays 11:30-2pm and 10pm-2am, at UTC-5
+    const schedule: PromoSchedule = {
+      days: 0b0011_1110,
+      windows: [
+        { start: 690, end: 840 },
+        { start: 1320, end: 120 },
+      ],
+      utcOffset: -300,
+      checkMint: false,
+    };
+
+    const scheduledMint = await tokenMetadataProgramMerchantOwner.createPromo(
+      platformSigner,
+      metadataData,
+      campaign,
+      true,
+      null,
+      null,
+      adminSettingsAccount.platform,
+      null,
+      undefined,
+      { schedule },
+    );
+
+    const scheduledPromo = await tokenMetadataProgram.getPromoExtended(scheduledMint);
+    expect(scheduledPromo.schedule).to.deep.equal(schedule, 'Schedule incorrect.');
+  });
+
+  it('Rejects a schedule with an empty window', async () => {
+    const metadataData: DataV2 = {
+      name: 'Test Promo Empty Window',
+      symbol: 'BTPE',
+      uri: 'https://arweave.net/frDiuZYzSVwYTwSUMR1YbggVkZqZfA7S9xsI3drPWBo',
+      sellerFeeBasisPoints: 0,
+      creators: null,
+      collection: null,
+      uses: null,
+    };
+
+    await expect(
+      tokenMetadataProgramMerchantOwner.createPromo(
+        platformSigner,
+        metadataData,
+        campaign,
+        true,
+        null,
+        null,
+        adminSettingsAccount.platform,
+        null,
+        undefined,
+        {
+          schedule: {
+            days: 0b0111_1111,
+            windows: [{ start: 600, end: 600 }],
+            utcOffset: 0,
+            checkMint: false,
+          },
+        },
+      ),
+    ).to.be.rejectedWith('InvalidSchedule');
+  });
+
+  it('Creates a promo with terms', async () => {
+    const metadataData: DataV2 = {
+      name: 'Test Promo Terms',
+      symbol: 'BTPT',
+      uri: 'https://arweave.net/frDiuZYzSVwYTwSUMR1YbggVkZqZfA7S9xsI3drPWBo',
+      sellerFeeBasisPoints: 0,
+      creators: null,
+      collection: null,
+      uses: null,
+    };
+
+    const terms: PromoTerms = {
+      buyXProductGetYFree: { productId: 'latte', buyXProduct: 2, getYProduct: 1 },
+    };
+
+    const termsMint = await tokenMetadataProgramMerchantOwner.createPromo(
+      platformSigner,
+      metadataData,
+      campaign,
+      true,
+      null,
+      null,
+      adminSettingsAccount.platform,
+      null,
+      undefined,
+      { terms },
+    );
+
+    const termsPromo = await tokenMetadataProgram.getPromoExtended(termsMint);
+    expect(termsPromo.terms).to.deep.equal(terms, 'Terms incorrect.');
+
+    await expect(
+      tokenMetadataProgramMerchantOwner.createPromo(
+        platformSigner,
+        { ...metadataData, name: 'Test Promo No Discount' },
+        campaign,
+        true,
+        null,
+        null,
+        adminSettingsAccount.platform,
+        null,
+        undefined,
+        {
+          terms: {
+            buyXCurrencyGetYPercent: { buyXCurrency: new anchor.BN(5000), getYPercent: 0 },
+          },
+        },
+      ),
+    ).to.be.rejectedWith('InvalidPromoTerms');
+  });
+
   // This has group member1 pay for the transaction, which they are able to do because
   // of their membership in the group that owns the promo.
   it('Mints a promo token', async () => {
@@ -344,6 +609,199 @@ describe('promo', () => {
     console.log('mintAccount: ', mintAccount);
   });
 
+  it('Mints an allowlisted promo token', async () => {
+    const hash = (...data: Buffer[]) => createHash('sha256').update(Buffer.concat(data)).digest();
+    const leaf = (wallet: PublicKey) => hash(Buffer.from([0]), wallet.toBuffer());
+
+    const tokenOwnerLeaf = leaf(tokenOwner);
+    const buyerLeaf = leaf(buyer.publicKey);
+    const [left, right] = [tokenOwnerLeaf, buyerLeaf].sort(Buffer.compare);
+    const root = hash(Buffer.from([1]), left, right);
+
+    const metadataData: DataV2 = {
+      name: 'Test Promo Allowlist',
+      symbol: 'BTPA',
+      uri: 'https://arweave.net/frDiuZYzSVwYTwSUMR1YbggVkZqZfA7S9xsI3drPWBo',
+      sellerFeeBasisPoints: 0,
+      creators: null,
+      collection: null,
+      uses: null,
+    };
+
+    const allowlistMint = await tokenMetadataProgramMerchantOwner.createPromo(
+      platformSigner,
+      metadataData,
+      campaign,
+      true,
+      null,
+      null,
+      adminSettingsAccount.platform,
+      null,
+      undefined,
+      { allowlist: Array.from(root) },
+    );
+
+    await expect(
+      tokenMetadataProgramMerchantOwner.mintPromoToken(
+        platformSigner,
+        allowlistMint,
+        deviceOwner,
+        device,
+        campaign,
+        null,
+        [Array.from(tokenOwnerLeaf)],
+      ),
+    ).to.be.rejectedWith('NotAllowlisted');
+
+    const tokenAccount = await tokenMetadataProgram.mintPromoToken(
+      platformSigner,
+      allowlistMint,
+      deviceOwner,
+      device,
+      campaign,
+      null,
+      [Array.from(buyerLeaf)],
+    );
+
+    const tokenAccountAccount = await tokenMetadataProgram.getTokenAccount(tokenAccount);
+    expect(Number(tokenAccountAccount.amount)).to.equal(1, 'Token account amount incorrect.');
+  });
+
+  it('Mints a referred promo token', async () => {
+    const promoMetadata = (name: string, symbol: string): DataV2 => ({
+      name,
+      symbol,
+      uri: 'https://arweave.net/frDiuZYzSVwYTwSUMR1YbggVkZqZfA7S9xsI3drPWBo',
+      sellerFeeBasisPoints: 0,
+      creators: null,
+      collection: null,
+      uses: null,
+    });
+
+    const rewardMint = await tokenMetadataProgramMerchantOwner.createPromo(
+      platformSigner,
+      promoMetadata('Test Promo Referral Reward', 'BTPRR'),
+      campaign,
+      true,
+      null,
+      null,
+      adminSettingsAccount.platform,
+      null,
+    );
+
+    const referredMint = await tokenMetadataProgramMerchantOwner.createPromo(
+      platformSigner,
+      promoMetadata('Test Promo Referral', 'BTPR'),
+      campaign,
+      true,
+      null,
+      null,
+      adminSettingsAccount.platform,
+      null,
+      undefined,
+      { referral: { rewardMint, maxRewards: 1, rewardCount: 0 } },
+    );
+
+    await expect(
+      tokenMetadataProgram.mintPromoToken(
+        platformSigner,
+        referredMint,
+        deviceOwner,
+        device,
+        campaign,
+        null,
+        [],
+        tokenOwner,
+      ),
+    ).to.be.rejectedWith('SelfReferral');
+
+    await tokenMetadataProgramMerchantOwner.mintPromoToken(
+      platformSigner,
+      referredMint,
+      deviceOwner,
+      device,
+      campaign,
+      null,
+      [],
+      tokenOwner,
+    );
+
+    const referral = await tokenMetadataProgram.program.account.referral.fetch(
+      tokenMetadataProgram.findReferralAddress(referredMint, merchantOwner.publicKey),
+    );
+    expect(referral.referrer.toString()).to.equal(tokenOwner.toString(), 'Referrer incorrect.');
+    expect(referral.referred.toString()).to.equal(
+      merchantOwner.publicKey.toString(),
+      'Referred incorrect.',
+    );
+    expect(referral.rewarded).to.equal(false, 'Rewarded incorrect.');
+  });
+
+  it('Stamps a promo card', async () => {
+    const metadataData: DataV2 = {
+      name: 'Test Promo Stamp Card',
+      symbol: 'BTPS',
+      uri: 'https://arweave.net/frDiuZYzSVwYTwSUMR1YbggVkZqZfA7S9xsI3drPWBo',
+      sellerFeeBasisPoints: 0,
+      creators: null,
+      collection: null,
+      uses: null,
+    };
+
+    const stampMint = await tokenMetadataProgramMerchantOwner.createPromo(
+      platformSigner,
+      metadataData,
+      campaign,
+      true,
+      null,
+      null,
+      adminSettingsAccount.platform,
+      null,
+      undefined,
+      { stampThreshold: 2 },
+    );
+
+    const stampCard = PublicKey.findProgramAddressSync(
+      [Buffer.from('stamp_card'), stampMint.toBuffer(), tokenOwner.toBuffer()],
+      tokenMetadataProgram.PUBKEY,
+    )[0];
+    const tokenAccount = tokenMetadataProgram.findAssociatedTokenAccountAddress(
+      stampMint,
+      tokenOwner,
+    );
+
+    const stamp = () =>
+      tokenMetadataProgram.program.methods
+        .stampPromoCard(null)
+        .accounts({
+          payer: platformSigner.publicKey,
+          deviceOwner: deviceOwner.publicKey,
+          device,
+          campaign,
+          tokenOwner,
+          mint: stampMint,
+          stampCard,
+          tokenAccount,
+          memoProgram: tokenMetadataProgram.MEMO_PROGRAM_ID,
+        })
+        .signers([platformSigner, deviceOwner])
+        .rpc();
+
+    await stamp();
+    let stampCardAccount = await tokenMetadataProgram.program.account.stampCard.fetch(stampCard);
+    expect(stampCardAccount.stamps).to.equal(1, 'Stamps incorrect.');
+    expect(stampCardAccount.rewards).to.equal(0, 'Rewards incorrect.');
+    let tokenAccountAccount = await tokenMetadataProgram.getTokenAccount(tokenAccount);
+    expect(Number(tokenAccountAccount.amount)).to.equal(0, 'Token account amount incorrect.');
+
+    await stamp();
+    stampCardAccount = await tokenMetadataProgram.program.account.stampCard.fetch(stampCard);
+    expect(stampCardAccount.stamps).to.equal(0, 'Stamps incorrect.');
+    expect(stampCardAccount.rewards).to.equal(1, 'Rewards incorrect.');
+    tokenAccountAccount = await tokenMetadataProgram.getTokenAccount(tokenAccount);
+    expect(Number(tokenAccountAccount.amount)).to.equal(1, 'Token account amount incorrect.');
+  });
+
   it('Delegates a promo token', async () => {
     // try different keys for memo
     const memo = {
@@ -0,0 +1,52 @@
+//! Typed helpers for calling the program from other programs, such as a partner point of sale
+//! program minting and burning promo tokens from its own instructions.
+//!
+//! Merchant, device and token owners can be PDAs of the calling program. The calling program
+//! passes the seeds of any of its PDAs that need to sign as `signer_seeds`, or an empty slice
+//! when all the signers signed the outer transaction.
+
+use crate::cpi;
+use anchor_lang::{error::ErrorCode, prelude::*};
+
+pub use crate::cpi::accounts::{BurnDelegatedPromoToken, MintPromoToken};
+
+/// Mints a promo token to the token owner, with the same checks as the `mint_promo_token`
+/// instruction. `program` is the account of this program.
+pub fn mint_promo_token<'a, 'b, 'c, 'info>(
+    program: AccountInfo<'info>,
+    accounts: MintPromoToken<'info>,
+    signer_seeds: &'a [&'b [&'c [u8]]],
+    proof: Vec<[u8; 32]>,
+    referrer: Option<Pubkey>,
+    memo: Option<String>,
+) -> Result<()> {
+    check_program(&program)?;
+    cpi::mint_promo_token(
+        CpiContext::new_with_signer(program, accounts, signer_seeds),
+        proof,
+        referrer,
+        memo,
+    )
+}
+
+/// Burns a promo token delegated to the device owner, with the same checks and fees as the
+/// `burn_delegated_promo_token` instruction. `program` is the account of this program.
+pub fn burn_delegated_promo_token<'a, 'b, 'c, 'info>(
+    program: AccountInfo<'info>,
+    accounts: BurnDelegatedPromoToken<'info>,
+    signer_seeds: &'a [&'b [&'c [u8]]],
+    memo: Option<String>,
+) -> Result<()> {
+    check_program(&program)?;
+    cpi::burn_delegated_promo_token(
+        CpiContext::new_with_signer(program, accounts, signer_seeds),
+        memo,
+    )
+}
+
+fn check_program(program: &AccountInfo) -> Result<()> {
+    if program.key() != crate::ID {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+    Ok(())
+}
@@ -14,4 +14,82 @@ pub enum ProgramError {
     InsufficientFunds,
     #[msg("Constraint not met")]
     ConstraintNotMet,
+    #[msg("Mint extensions require the Token-2022 program")]
+    ExtensionsRequireToken2022,
+    #[msg("Promo not available at this time")]
+    OutsideSchedule,
+    #[msg("Invalid promo schedule")]
+    InvalidSchedule,
+    #[msg("Token owner not on promo allowlist")]
+    NotAllowlisted,
+    #[msg("Campaign max mint exceeded")]
+    CampaignMaxMintExceeded,
+    #[msg("Campaign max burn exceeded")]
+    CampaignMaxBurnExceeded,
+    #[msg("Location daily max mint exceeded")]
+    LocationMaxMintExceeded,
+    #[msg("Location daily max burn exceeded")]
+    LocationMaxBurnExceeded,
+    #[msg("Device daily max mint exceeded")]
+    DeviceMaxMintExceeded,
+    #[msg("Device daily max burn exceeded")]
+    DeviceMaxBurnExceeded,
+    #[msg("Burning clawed back tokens requires the program authority as permanent delegate")]
+    PermanentDelegateRequired,
+    #[msg("Token account already frozen")]
+    TokenAccountFrozen,
+    #[msg("Promo does not have referral rewards")]
+    ReferralsNotEnabled,
+    #[msg("Token owner can't refer themselves")]
+    SelfReferral,
+    #[msg("Referral accounts missing or invalid")]
+    InvalidReferralAccounts,
+    #[msg("Referral reward must be a different promo")]
+    InvalidReferralReward,
+    #[msg("Promo is not a stamp card")]
+    StampCardNotEnabled,
+    #[msg("Too many sponsored promos")]
+    InvalidSponsorship,
+    #[msg("Sponsorship accounts missing or invalid")]
+    InvalidSponsorshipAccounts,
+    #[msg("Promo is not covered by the sponsorship")]
+    PromoNotSponsored,
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    #[msg("Voucher signature missing or invalid")]
+    InvalidVoucherSignature,
+    #[msg("Voucher is for a different recipient")]
+    VoucherRecipientMismatch,
+    #[msg("Invalid promo terms")]
+    InvalidPromoTerms,
+    #[msg("Promo doesn't require approval to mint")]
+    MintApprovalNotRequired,
+    #[msg("Promo tokens can't be gifted")]
+    GiftingNotAllowed,
+    #[msg("Promo tokens received as a gift can't be gifted again")]
+    AlreadyGifted,
+    #[msg("Token owner can't gift to themselves")]
+    SelfGift,
+    #[msg("Gift account missing or invalid")]
+    InvalidGiftAccounts,
+    #[msg("Customer vault has no tokens to claim")]
+    CustomerVaultEmpty,
+    #[msg("Membership duration and renewal periods should be greater than zero")]
+    InvalidMembershipDuration,
+    #[msg("Membership pass has expired")]
+    MembershipExpired,
+    #[msg("Campaign location accounts missing or invalid")]
+    InvalidCampaignLocationAccounts,
+    #[msg("Promo requires approval to mint")]
+    MintApprovalRequired,
+    #[msg("Campaign address doesn't match the merchant and campaign name")]
+    InvalidCampaignAddress,
+    #[msg("Sponsored mints should be unique promos of the campaign")]
+    InvalidSponsoredPromos,
+    #[msg("Only promo, campaign and campaign location accounts can be migrated")]
+    InvalidMigrationAccount,
+    #[msg("Non-transferable promo tokens can't be held in a customer vault")]
+    NonTransferableVault,
+    #[msg("Gifting once requires the non-transferable extension")]
+    GiftOnceRequiresNonTransferable,
 }
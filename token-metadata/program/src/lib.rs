@@ -1,3 +1,9 @@
+// Every instruction returns anchor's `Result`, whose error type is large by design.
+#![allow(clippy::result_large_err)]
+
+/// Helpers for calling the program from other programs.
+#[cfg(feature = "cpi")]
+pub mod cpi_helpers;
 pub mod error;
 /// Processors for each program instruction.
 pub mod processor;
@@ -9,14 +15,23 @@ use anchor_spl::{
     self,
     associated_token::AssociatedToken,
     token::{Mint, Token, TokenAccount},
+    token_interface::{
+        Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    },
 };
 use borsh::BorshDeserialize;
 use mpl_token_metadata::state::TokenMetadataAccount;
-use state::{AdminSettings, Campaign, CampaignLocation, DataV2, Device, Location, Merchant, Promo};
+use state::{
+    AdminSettings, Campaign, CampaignLocation, CampaignMember, CustomerVault, DailyCount,
+    DailyLimits, DataV2, Device, Gift, Location, Membership, MembershipPass, Merchant, MintRequest,
+    Promo, PromoExtensions, Referral, Sponsorship, StampCard, Voucher, VoucherClaim,
+};
 use utils::{
-    ADMIN_PREFIX, AUTHORITY_PREFIX, CAMPAIGN_LOCATION_PREFIX, CAMPAIGN_PREFIX, DEVICE_PREFIX,
-    LOCATION_PREFIX, MAX_NAME_LENGTH, MAX_URI_LENGTH, MERCHANT_PREFIX, PROMO_PREFIX,
-    UPGRADE_AUTHORITY,
+    name_seed, ADMIN_PREFIX, AUTHORITY_PREFIX, CAMPAIGN_LOCATION_PREFIX, CAMPAIGN_MEMBER_PREFIX,
+    CAMPAIGN_PREFIX, CUSTOMER_VAULT_PREFIX, DAILY_COUNT_PREFIX, DEVICE_PREFIX, GIFT_PREFIX,
+    LOCATION_PREFIX, MAX_NAME_LENGTH, MAX_URI_LENGTH, MEMBERSHIP_PASS_PREFIX, MEMBERSHIP_PREFIX,
+    MERCHANT_PREFIX, MINT_REQUEST_PREFIX, PROMO_PREFIX, REFERRAL_PREFIX, SPONSORSHIP_PREFIX,
+    STAMP_CARD_PREFIX, UPGRADE_AUTHORITY, VOUCHER_PREFIX,
 };
 
 declare_id!("HB53jiCac5VtNdokJeibrfd1QJsyWWFe56M1TQUSKQfY");
@@ -82,59 +97,276 @@ pub mod bpl_token_metadata {
         ctx.accounts.process(memo)
     }
 
+    /// Links many of the merchant's locations to a campaign. Each location is passed in
+    /// `remaining_accounts` followed by its campaign location account, which is created.
+    pub fn create_campaign_locations<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCampaignLocations<'info>>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts, memo)
+    }
+
+    /// Adds a merchant to a campaign created by another merchant, so the merchant can link their
+    /// locations to the campaign. Requires signatures from the owners of both merchants.
+    pub fn join_campaign(ctx: Context<JoinCampaign>, memo: Option<String>) -> Result<()> {
+        ctx.accounts.process(memo)
+    }
+
+    /// Creates a sponsorship through which the sponsor pays the burn fees of redemptions of the
+    /// campaign's promos listed in `mints`, or all of them if empty, and reimburses the merchant
+    /// of the redeeming location `reimbursement_lamports` for each. The sponsorship is funded
+    /// with `lamports` from the sponsor. The promo of each mint is passed in the remaining
+    /// accounts.
+    pub fn create_sponsorship<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateSponsorship<'info>>,
+        mints: Vec<Pubkey>,
+        reimbursement_lamports: u64,
+        lamports: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            ctx.remaining_accounts,
+            mints,
+            reimbursement_lamports,
+            lamports,
+            memo,
+        )
+    }
+
+    /// Closes a sponsorship, returning its remaining funds to the sponsor.
+    pub fn close_sponsorship(ctx: Context<CloseSponsorship>, memo: Option<String>) -> Result<()> {
+        ctx.accounts.process(memo)
+    }
+
+    /// Sets daily mint and burn limits for a campaign at a location, across all devices at the
+    /// location and for each device.
+    pub fn set_campaign_location_limits(
+        ctx: Context<SetCampaignLocationLimits>,
+        location_limits: DailyLimits,
+        device_limits: DailyLimits,
+        memo: Option<String>,
+    ) -> Result<()> {
+        ctx.accounts.process(location_limits, device_limits, memo)
+    }
+
     /// Creates Promo account and related mint and metadata accounts.
+    ///
+    /// The mint is created with the token program passed in as `token_program`, either spl-token
+    /// or Token-2022. `extensions` can only be set when using Token-2022.
     pub fn create_promo(
         ctx: Context<CreatePromo>,
         promo_data: Promo,
         metadata_data: DataV2,
         is_mutable: bool,
+        extensions: PromoExtensions,
         memo: Option<String>,
     ) -> Result<()> {
         let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
 
-        ctx.accounts
-            .process(promo_data, metadata_data, is_mutable, authority_seeds, memo)
+        ctx.accounts.process(
+            promo_data,
+            metadata_data,
+            is_mutable,
+            extensions,
+            authority_seeds,
+            memo,
+        )
     }
 
     /// Example of executing lamprts transfer from program derived account.
     pub fn transfer_cpi(ctx: Context<TransferCpi>, lamports: u64) -> Result<()> {
-        ctx.accounts.process(lamports, ctx.bumps["campaign"])
+        ctx.accounts.process(lamports)
     }
 
-    /// Mints a promo token.
-    pub fn mint_promo_token<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, MintPromoToken<'info>>,
+    /// Mints a promo token. `proof` is the merkle proof that the token owner is on the promo
+    /// allowlist and is empty if the promo doesn't have one. `referrer` is recorded as having
+    /// referred the token owner if the promo has referral rewards.
+    pub fn mint_promo_token(
+        ctx: Context<MintPromoToken>,
+        proof: Vec<[u8; 32]>,
+        referrer: Option<Pubkey>,
         memo: Option<String>,
     ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(proof, referrer, memo, authority_seeds)
+    }
+
+    /// Adds a stamp to the token owner's stamp card for a promo, minting a promo token to them
+    /// when the promo's stamp threshold is reached.
+    pub fn stamp_promo_card(ctx: Context<StampPromoCard>, memo: Option<String>) -> Result<()> {
         let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
         ctx.accounts.process(memo, authority_seeds)
     }
 
-    /// Delegates a promo token.
-    pub fn delegate_promo_token<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, DelegatePromoToken<'info>>,
+    /// Mints a promo token to the token owner for a voucher signed off chain by the owner of a
+    /// campaign device. The transaction must include an Ed25519 program instruction verifying the
+    /// signature over the serialized voucher immediately before this one. `proof` is the
+    /// allowlist merkle proof, as when minting.
+    pub fn claim_promo_voucher(
+        ctx: Context<ClaimPromoVoucher>,
+        voucher: Voucher,
+        proof: Vec<[u8; 32]>,
         memo: Option<String>,
     ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(voucher, proof, memo, authority_seeds)
+    }
+
+    /// Requests a promo token for a promo that requires approval, creating a pending
+    /// [MintRequest] and the token owner's token account.
+    pub fn request_promo_mint(ctx: Context<RequestPromoMint>, memo: Option<String>) -> Result<()> {
         ctx.accounts.process(memo)
     }
 
-    /// Burns a delegated promo token.
-    pub fn burn_delegated_promo_token<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, BurnDelegatedPromoToken<'info>>,
+    /// Approves a pending mint request, minting the promo token to the token owner. `proof` is
+    /// the allowlist merkle proof, as when minting.
+    pub fn approve_mint_request(
+        ctx: Context<ApproveMintRequest>,
+        proof: Vec<[u8; 32]>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(proof, memo, authority_seeds)
+    }
+
+    /// Rejects a pending mint request, closing it and the token owner's token account if it's
+    /// empty.
+    pub fn reject_mint_request(
+        ctx: Context<RejectMintRequest>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(memo, authority_seeds)
+    }
+
+    /// Gifts a promo token from the sender to the recipient, as allowed by the promo's gift
+    /// policy. Tokens of non-transferable promos are burned from the sender and minted to the
+    /// recipient by the program authority. Sender and recipient are recorded in a memo.
+    pub fn gift_promo_token(ctx: Context<GiftPromoToken>, memo: Option<String>) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(memo, authority_seeds)
+    }
+
+    /// Mints a promo token into a [CustomerVault] for a customer without a wallet, identified by
+    /// the salted hash of their phone number or email. `proof` is the allowlist merkle proof for
+    /// the vault address, as when minting.
+    pub fn mint_promo_token_to_vault(
+        ctx: Context<MintPromoTokenToVault>,
+        identity_hash: [u8; 32],
+        proof: Vec<[u8; 32]>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts
+            .process(identity_hash, proof, memo, authority_seeds)
+    }
+
+    /// Transfers the tokens of a promo held in a [CustomerVault] to the customer's wallet once
+    /// the platform has verified the customer controls the phone number or email.
+    pub fn claim_customer_vault(
+        ctx: Context<ClaimCustomerVault>,
+        identity_hash: [u8; 32],
+        memo: Option<String>,
+    ) -> Result<()> {
+        let vault_bump = [ctx.bumps[CUSTOMER_VAULT_PREFIX]];
+        let vault_seeds = [
+            CUSTOMER_VAULT_PREFIX.as_bytes(),
+            identity_hash.as_ref(),
+            &vault_bump,
+        ];
+        ctx.accounts.process(memo, vault_seeds)
+    }
+
+    /// Delegates a promo token.
+    pub fn delegate_promo_token(
+        ctx: Context<DelegatePromoToken>,
         memo: Option<String>,
     ) -> Result<()> {
         ctx.accounts.process(memo)
     }
 
+    /// Burns a delegated promo token, minting the referral reward to the referrer of the token
+    /// owner if the referral accounts are included.
+    pub fn burn_delegated_promo_token(
+        ctx: Context<BurnDelegatedPromoToken>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(memo, authority_seeds)
+    }
+
+    /// Redeems a promo token by burning it directly from the token owner's account.
+    pub fn redeem_promo_token(ctx: Context<RedeemPromoToken>, memo: Option<String>) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(memo, authority_seeds)
+    }
+
+    /// Claws back promo tokens issued by mistake or fraudulently, either burning them with the
+    /// program authority as permanent delegate or freezing the token account. Only the merchant
+    /// owner can claw back tokens and the reason is recorded in a memo.
+    pub fn clawback_promo_token(
+        ctx: Context<ClawbackPromoToken>,
+        burn: bool,
+        reason: String,
+    ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(burn, reason, authority_seeds)
+    }
+
     /// Signs a memo.
     ///
     /// This could have just been done outside of the program, but doing it inside the program
     /// makes it easier to get the resulting transaction filtered by our indexer based on
     /// program address.
-    pub fn sign_memo<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, SignMemo<'info>>,
-        memo: String,
+    pub fn sign_memo(ctx: Context<SignMemo>, memo: String) -> Result<()> {
+        ctx.accounts.process(memo)
+    }
+
+    /// Creates a [Membership] template for the merchant of the owner, with a master edition the
+    /// membership passes are printed from. `duration` is the number of seconds a pass is valid
+    /// for and `max_supply` limits the number of passes that can be issued.
+    pub fn create_membership(
+        ctx: Context<CreateMembership>,
+        data: DataV2,
+        duration: i64,
+        max_supply: Option<u64>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts
+            .process(data, duration, max_supply, memo, authority_seeds)
+    }
+
+    /// Issues a pass of a [Membership] to a customer by printing a new edition of the
+    /// membership's master edition, valid for the membership's duration.
+    pub fn issue_membership_pass(
+        ctx: Context<IssueMembershipPass>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let authority_seeds = [AUTHORITY_PREFIX.as_bytes(), &[ctx.bumps[AUTHORITY_PREFIX]]];
+        ctx.accounts.process(memo, authority_seeds)
+    }
+
+    /// Renews a [MembershipPass] by extending its expiry by `periods` of the membership's
+    /// duration, counted from now if the pass has already expired.
+    pub fn renew_membership_pass(
+        ctx: Context<RenewMembershipPass>,
+        periods: u16,
+        memo: Option<String>,
     ) -> Result<()> {
+        ctx.accounts.process(periods, memo)
+    }
+
+    /// Records a visit by the holder of an unexpired [MembershipPass] at a location of the
+    /// membership's merchant.
+    pub fn check_in(ctx: Context<CheckIn>, memo: Option<String>) -> Result<()> {
+        ctx.accounts.process(memo)
+    }
+
+    /// Reallocates a [Promo], [Campaign] or [CampaignLocation] created before fields were
+    /// appended to it to its current size, with the payer funding the rent. The appended fields
+    /// read as their defaults. Accounts that are already the current size are left as they are.
+    pub fn migrate_account(ctx: Context<MigrateAccount>, memo: Option<String>) -> Result<()> {
         ctx.accounts.process(memo)
     }
 
@@ -162,7 +394,7 @@ pub mod bpl_token_metadata {
 // TODO: uncomment prorgram data check when deploying to devnet
 #[derive(Accounts)]
 pub struct CreateAdminSettings<'info> {
-    #[account(mut, constraint = &payer.key().to_string() == UPGRADE_AUTHORITY)]
+    #[account(mut, constraint = payer.key().to_string() == UPGRADE_AUTHORITY)]
     pub payer: Signer<'info>,
     #[account(init_if_needed, seeds = [ADMIN_PREFIX.as_bytes()], bump, payer = payer, space = AdminSettings::LEN)]
     pub admin_settings: Account<'info, AdminSettings>,
@@ -175,12 +407,13 @@ pub struct CreateAdminSettings<'info> {
 
 /// Accounts related to creating [Merchant].
 ///
+/// The owner can be any signer, including a multisig or a PDA of another program signing through
+/// cpi. Owners only sign and never pay for accounts, which are all paid for by the payer.
 #[derive(Accounts, Clone)]
 #[instruction(merchant_data: Merchant)]
 pub struct CreateMerchant<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut)]
     pub owner: Signer<'info>,
     /// CHECK: pubkey checked via seeds
     #[account(
@@ -205,7 +438,6 @@ pub struct CreateMerchant<'info> {
 pub struct CreateLocation<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut)]
     pub owner: Signer<'info>,
     /// CHECK: pubkey checked via seeds
     #[account(
@@ -218,7 +450,7 @@ pub struct CreateLocation<'info> {
         constraint = data.merchant == merchant.key(),
         constraint = data.name.len() <= MAX_NAME_LENGTH,
         constraint = data.uri.len() <= MAX_URI_LENGTH,
-        seeds = [LOCATION_PREFIX.as_bytes(), merchant.key().as_ref(), data.name.as_bytes()], bump,
+        seeds = [LOCATION_PREFIX.as_bytes(), merchant.key().as_ref(), name_seed(&data.name).as_ref()], bump,
         payer = payer,
         space = Location::LEN
     )]
@@ -235,7 +467,6 @@ pub struct CreateLocation<'info> {
 pub struct CreateDevice<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut)]
     pub merchant_owner: Signer<'info>,
     #[account(
         constraint = merchant.owner == merchant_owner.key(),
@@ -249,7 +480,7 @@ pub struct CreateDevice<'info> {
         constraint = data.location == location.key(),
         constraint = data.name.len() <= MAX_NAME_LENGTH,
         constraint = data.uri.len() <= MAX_URI_LENGTH,
-        seeds = [DEVICE_PREFIX.as_bytes(), location.key().as_ref(), data.name.as_bytes()], bump,
+        seeds = [DEVICE_PREFIX.as_bytes(), location.key().as_ref(), name_seed(&data.name).as_ref()], bump,
         payer = payer,
         space = Device::LEN
     )]
@@ -261,12 +492,13 @@ pub struct CreateDevice<'info> {
 
 /// Accounts related to creating a [Campaign].
 ///
+/// The campaign is funded with `lamports` from the payer, from which the burn fees of its promos
+/// are paid. Merchants can also fund the campaign with a system transfer to it at any time.
 #[derive(Accounts)]
 #[instruction(data: Campaign)]
 pub struct CreateCampaign<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut)]
     pub owner: Signer<'info>,
     #[account(constraint = owner.key() == merchant.owner)]
     pub merchant: Account<'info, Merchant>,
@@ -274,7 +506,7 @@ pub struct CreateCampaign<'info> {
         init,
         constraint = data.merchant == merchant.key(),
         constraint = data.name.len() <= MAX_NAME_LENGTH,
-        seeds = [CAMPAIGN_PREFIX.as_bytes(), merchant.key().as_ref(), data.name.as_bytes()], bump,
+        seeds = [CAMPAIGN_PREFIX.as_bytes(), merchant.key().as_ref(), name_seed(&data.name).as_ref()], bump,
         payer = payer,
         space = Campaign::LEN
     )]
@@ -285,15 +517,16 @@ pub struct CreateCampaign<'info> {
 
 /// Accounts related to creating a [CampaignLocation].
 ///
+/// The merchant is either the merchant that created the campaign or a [CampaignMember] of it,
+/// in which case the campaign member account is included.
 #[derive(Accounts)]
 pub struct CreateCampaignLocation<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut)]
     pub owner: Signer<'info>,
     #[account(constraint = owner.key() == merchant.owner)]
     pub merchant: Account<'info, Merchant>,
-    #[account(constraint = campaign.merchant == merchant.key())]
+    #[account(constraint = campaign.merchant == merchant.key() || campaign_member.is_some())]
     pub campaign: Account<'info, Campaign>,
     #[account(constraint = location.merchant == merchant.key())]
     pub location: Account<'info, Location>,
@@ -306,6 +539,117 @@ pub struct CreateCampaignLocation<'info> {
     pub campaign_location: Account<'info, CampaignLocation>,
     pub memo_program: Program<'info, SplMemo>,
     pub system_program: Program<'info, System>,
+    #[account(seeds = [CAMPAIGN_MEMBER_PREFIX.as_bytes(), campaign.key().as_ref(), merchant.key().as_ref()], bump)]
+    pub campaign_member: Option<Account<'info, CampaignMember>>,
+}
+
+/// Accounts related to linking many locations to a campaign. The locations and their campaign
+/// location accounts are passed as pairs in `remaining_accounts`.
+///
+/// The merchant is either the merchant that created the campaign or a [CampaignMember] of it,
+/// in which case the campaign member account is included.
+#[derive(Accounts)]
+pub struct CreateCampaignLocations<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() == merchant.owner)]
+    pub merchant: Account<'info, Merchant>,
+    #[account(constraint = campaign.merchant == merchant.key() || campaign_member.is_some())]
+    pub campaign: Account<'info, Campaign>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub system_program: Program<'info, System>,
+    #[account(seeds = [CAMPAIGN_MEMBER_PREFIX.as_bytes(), campaign.key().as_ref(), merchant.key().as_ref()], bump)]
+    pub campaign_member: Option<Account<'info, CampaignMember>>,
+}
+
+/// Accounts related to creating a [CampaignMember].
+///
+/// The owner of the merchant that created the campaign approves the member by signing along
+/// with the owner of the member merchant, since burn fees for redemptions at the member's
+/// locations are paid from the campaign.
+#[derive(Accounts)]
+pub struct JoinCampaign<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub campaign_owner: Signer<'info>,
+    #[account(constraint = campaign_owner.key() == campaign_merchant.owner)]
+    pub campaign_merchant: Account<'info, Merchant>,
+    #[account(constraint = campaign.merchant == campaign_merchant.key())]
+    pub campaign: Account<'info, Campaign>,
+    pub owner: Signer<'info>,
+    #[account(
+        constraint = owner.key() == merchant.owner,
+        constraint = merchant.key() != campaign_merchant.key(),
+    )]
+    pub merchant: Account<'info, Merchant>,
+    #[account(
+        init,
+        seeds = [CAMPAIGN_MEMBER_PREFIX.as_bytes(), campaign.key().as_ref(), merchant.key().as_ref()], bump,
+        payer = payer,
+        space = CampaignMember::LEN
+    )]
+    pub campaign_member: Account<'info, CampaignMember>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to creating a [Sponsorship].
+///
+/// Any wallet can sponsor a campaign. Reimbursements are paid to the owner of the merchant of
+/// the location each sponsored token is redeemed at.
+#[derive(Accounts)]
+pub struct CreateSponsorship<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    pub merchant: Account<'info, Merchant>,
+    #[account(constraint = campaign.merchant == merchant.key())]
+    pub campaign: Account<'info, Campaign>,
+    #[account(
+        init,
+        seeds = [SPONSORSHIP_PREFIX.as_bytes(), campaign.key().as_ref(), sponsor.key().as_ref()], bump,
+        payer = payer,
+        space = Sponsorship::LEN
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to closing a [Sponsorship].
+///
+/// Only the sponsor can close the sponsorship, which returns its remaining lamports, including
+/// its rent, to the sponsor.
+#[derive(Accounts)]
+pub struct CloseSponsorship<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(mut, close = sponsor,
+        seeds = [SPONSORSHIP_PREFIX.as_bytes(), sponsorship.campaign.as_ref(), sponsor.key().as_ref()], bump,
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    pub memo_program: Program<'info, SplMemo>,
+}
+
+/// Accounts related to setting the [DailyLimits] of a [CampaignLocation].
+///
+/// Only the merchant owner can set limits.
+#[derive(Accounts)]
+pub struct SetCampaignLocationLimits<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() == merchant.owner)]
+    pub merchant: Account<'info, Merchant>,
+    #[account(constraint = campaign.merchant == merchant.key())]
+    pub campaign: Account<'info, Campaign>,
+    #[account(mut,
+        seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), campaign_location.location.as_ref()], bump,
+    )]
+    pub campaign_location: Account<'info, CampaignLocation>,
+    pub memo_program: Program<'info, SplMemo>,
 }
 
 /// Accounts related to creating a [Promo].
@@ -328,12 +672,14 @@ pub struct CreateCampaignLocation<'info> {
 /// is remitted from the [Group] specified in the `owner` property of the [Promo] is transferred
 /// from the [Group] lamports to the account specified in the `platform` property of the [AdminSettings]
 /// account.
+///
+/// The mint is owned by whichever token program is passed in, spl-token or Token-2022, and the
+/// remaining promo instructions take the same program as an interface account.
 #[derive(Accounts, Clone)]
 #[instruction(promo_data: Promo, metadata_data: DataV2)]
 pub struct CreatePromo<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut)]
     pub owner: Signer<'info>,
     #[account(mut, constraint = owner.key() == merchant.owner)]
     pub merchant: Account<'info, Merchant>,
@@ -342,8 +688,9 @@ pub struct CreatePromo<'info> {
         constraint = campaign.key() == promo_data.campaign,
     )]
     pub campaign: Account<'info, Campaign>,
-    #[account(init, payer = payer, mint::decimals = 0, mint::authority = authority, mint::freeze_authority = authority)]
-    pub mint: Account<'info, Mint>,
+    /// Created in the processor so Token-2022 extensions can be initialized before the mint.
+    #[account(mut)]
+    pub mint: Signer<'info>,
     /// CHECK: Created via cpi
     #[account(mut)]
     pub metadata: UncheckedAccount<'info>,
@@ -363,7 +710,7 @@ pub struct CreatePromo<'info> {
     #[account(seeds = [ADMIN_PREFIX.as_bytes()], bump)]
     pub admin_settings: Box<Account<'info, AdminSettings>>,
     pub metadata_program: Program<'info, TokenMetadata>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub memo_program: Program<'info, SplMemo>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
@@ -398,16 +745,20 @@ pub struct TransferCpi<'info> {
 /// bokoup always gets a recipient's consent before minting them any tokens).
 ///
 /// Creates a token account for the recipient if one does not already exist. Authority over the
-/// token account is retained with the token owner. (All tokens are currently freely transferrable
-/// by token owners. Future versions may retain authority with the program to facilitate execution
-/// to enforce transfer restrictions).
+/// token account is retained with the token owner, except that the program authority is set as
+/// close authority so the account can be closed when its last token is burned. Tokens are freely
+/// transferrable by token owners unless the promo mint was created with the Token-2022
+/// non-transferable extension.
+///
+/// If the promo has referral rewards and a referrer is included, a [Referral] is created for the
+/// token owner recording who referred them, unless one already exists.
 ///
 /// No platform fees result from minting a token.
 #[derive(Accounts, Clone)]
 pub struct MintPromoToken<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut, constraint = device.owner == device_owner.key())]
+    #[account(constraint = device.owner == device_owner.key())]
     pub device_owner: Signer<'info>,
     #[account(mut, constraint = device.location == campaign_location.location)]
     pub device: Account<'info, Device>,
@@ -420,89 +771,114 @@ pub struct MintPromoToken<'info> {
     )]
     #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
     pub campaign_location: Account<'info, CampaignLocation>,
-    #[account(mut)]
     pub token_owner: Signer<'info>,
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
     /// CHECK: pubkey checked via seeds
     #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
     pub authority: UncheckedAccount<'info>,
     #[account(mut, seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
     pub promo: Account<'info, Promo>,
-    #[account(init_if_needed, payer = payer, associated_token::mint = mint, associated_token::authority = token_owner)]
-    pub token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    #[account(init_if_needed, payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = token_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref()], bump,
+    )]
+    pub location_count: Box<Account<'info, DailyCount>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref(), device.key().as_ref()], bump,
+    )]
+    pub device_count: Box<Account<'info, DailyCount>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub memo_program: Program<'info, SplMemo>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
+    /// Only needed when a referrer is included, and rejected otherwise.
+    #[account(init_if_needed, payer = payer, space = Referral::LEN,
+        seeds = [REFERRAL_PREFIX.as_bytes(), mint.key().as_ref(), token_owner.key().as_ref()], bump,
+    )]
+    pub referral: Option<Box<Account<'info, Referral>>>,
 }
 
-/// Accounts related to the delegation of a promo token.
-///
-/// Delegates a token to a device owner.
-///
-/// Requires a signature from the owner of a device with a location included in the campaign
-/// as well as from the recipient (as a matter of responsible token issuance,
-/// bokoup always gets a recipient's consent before minting them any tokens).
+/// Accounts related to stamping a stamp card.
 ///
-/// Requires signature from token owner as the authority of the token account.
+/// Requires a signature from the owner of a device with a location included in the campaign,
+/// as a record of the purchase, as well as from the token owner. The [StampCard] and the token
+/// account are created the first time the token owner's card is stamped.
 ///
-/// No platform fees result from delegating a token.
+/// When the card reaches the promo's `stamp_threshold`, a promo token is minted to the token
+/// owner, subject to the same caps and daily limits as minting, and the card starts over.
 #[derive(Accounts, Clone)]
-pub struct DelegatePromoToken<'info> {
+pub struct StampPromoCard<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    /// CHECK: checked via device constraints
-    pub device_owner: UncheckedAccount<'info>,
+    pub device_owner: Signer<'info>,
     #[account(
         constraint = device.owner == device_owner.key(),
         constraint = device.location == campaign_location.location
     )]
-    pub device: Account<'info, Device>,
+    pub device: Box<Account<'info, Device>>,
     #[account(mut,
         constraint = campaign.key() == promo.campaign,
         constraint = campaign.key() == campaign_location.campaign,
     )]
     pub campaign: Box<Account<'info, Campaign>>,
     #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
-    pub campaign_location: Account<'info, CampaignLocation>,
-    #[account(mut)]
+    pub campaign_location: Box<Account<'info, CampaignLocation>>,
     pub token_owner: Signer<'info>,
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
     #[account(mut, seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
-    pub promo: Account<'info, Promo>,
-    #[account(mut,
-        constraint = token_owner.key() == token_account.owner,
-        constraint = mint.key() == token_account.mint
+    pub promo: Box<Account<'info, Promo>>,
+    #[account(init_if_needed, payer = payer, space = StampCard::LEN,
+        seeds = [STAMP_CARD_PREFIX.as_bytes(), mint.key().as_ref(), token_owner.key().as_ref()], bump,
     )]
-    pub token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub stamp_card: Box<Account<'info, StampCard>>,
+    #[account(init_if_needed, payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = token_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref()], bump,
+    )]
+    pub location_count: Box<Account<'info, DailyCount>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref(), device.key().as_ref()], bump,
+    )]
+    pub device_count: Box<Account<'info, DailyCount>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub memo_program: Program<'info, SplMemo>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
 }
 
-/// Accounts related to the burning of a delegated promo token.
+/// Accounts related to claiming a promo token with a [Voucher].
 ///
-/// Checks to make sure signer is a member of group specified in owner property of
-/// promo in order to execute transaction to transfer lamports from group to platform
-/// to pay `burn_promo_token_lamports`.
+/// Requires a signature from the token owner only. The device owner signs the voucher off chain
+/// instead, which is checked against the Ed25519 program instruction preceding this one. A
+/// [VoucherClaim] is created for the voucher nonce so the voucher can't be claimed twice.
 ///
-/// The fee specified in the `burn_promo_token_lamports` property of the [AdminSettings] account
-/// is transferred from the [Group] specified in the `owner` property of the [Promo] from the
-/// lamports of the [Group] account to the account specified in the `platform` property of the [AdminSettings]
-/// account.
+/// The promo token is subject to the same caps, daily limits and allowlist as minting.
 #[derive(Accounts, Clone)]
-pub struct BurnDelegatedPromoToken<'info> {
+#[instruction(voucher: Voucher)]
+pub struct ClaimPromoVoucher<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut)]
-    pub device_owner: Signer<'info>,
-    #[account(
-        constraint = device.owner == device_owner.key(),
-        constraint = device.location == campaign_location.location
-    )]
+    /// CHECK: checked against device owner, signature verified by the Ed25519 program
+    #[account(constraint = device.owner == device_owner.key())]
+    pub device_owner: UncheckedAccount<'info>,
+    #[account(constraint = device.location == campaign_location.location)]
     pub device: Box<Account<'info, Device>>,
     #[account(mut,
         constraint = campaign.key() == promo.campaign,
@@ -510,35 +886,727 @@ pub struct BurnDelegatedPromoToken<'info> {
     )]
     pub campaign: Box<Account<'info, Campaign>>,
     #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
-    pub campaign_location: Account<'info, CampaignLocation>,
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-    /// CHECK: pubkey checked via spl token program instruction
+    pub campaign_location: Box<Account<'info, CampaignLocation>>,
+    pub token_owner: Signer<'info>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
     #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
     pub authority: UncheckedAccount<'info>,
-    #[account(mut,
-        seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump,
-        constraint = promo.campaign == campaign.key()
+    #[account(mut, seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump,
+        constraint = promo.key() == voucher.promo,
     )]
-    pub promo: Account<'info, Promo>,
-    /// CHECK: pubkey checked via constraint
-    #[account(mut, constraint = platform.key() == admin_settings.platform)]
-    pub platform: UncheckedAccount<'info>,
-    #[account(seeds = [ADMIN_PREFIX.as_bytes()], bump)]
-    pub admin_settings: Account<'info, AdminSettings>,
-    #[account(mut,
-        constraint = token_account.mint == mint.key(),
-        constraint = token_account.delegate.unwrap() == device_owner.key(),
-        constraint = token_account.delegated_amount > 0,
+    pub promo: Box<Account<'info, Promo>>,
+    #[account(init, payer = payer, space = VoucherClaim::LEN,
+        seeds = [VOUCHER_PREFIX.as_bytes(), promo.key().as_ref(), voucher.nonce.to_le_bytes().as_ref()], bump,
     )]
-    pub token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub voucher_claim: Box<Account<'info, VoucherClaim>>,
+    #[account(init_if_needed, payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = token_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref()], bump,
+    )]
+    pub location_count: Box<Account<'info, DailyCount>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref(), device.key().as_ref()], bump,
+    )]
+    pub device_count: Box<Account<'info, DailyCount>>,
+    /// CHECK: address checked
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to requesting a promo token that requires approval.
+///
+/// Requires a signature from the token owner only. Creates the token account and sets the
+/// program as its close authority, as when minting, so approving the request doesn't need the
+/// token owner to sign again.
+#[derive(Accounts, Clone)]
+pub struct RequestPromoMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_owner: Signer<'info>,
+    #[account(constraint = campaign.key() == promo.campaign)]
+    pub campaign: Box<Account<'info, Campaign>>,
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
+    pub promo: Box<Account<'info, Promo>>,
+    #[account(init, payer = payer, space = MintRequest::LEN,
+        seeds = [MINT_REQUEST_PREFIX.as_bytes(), mint.key().as_ref(), token_owner.key().as_ref()], bump,
+    )]
+    pub mint_request: Box<Account<'info, MintRequest>>,
+    #[account(init_if_needed, payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = token_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to approving a [MintRequest].
+///
+/// Requires a signature from the owner of a device with a location included in the campaign.
+/// The promo token is subject to the same caps, daily limits and allowlist as minting. The
+/// request is closed and its rent returned to the payer of the request.
+#[derive(Accounts, Clone)]
+pub struct ApproveMintRequest<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(constraint = device.owner == device_owner.key())]
+    pub device_owner: Signer<'info>,
+    #[account(constraint = device.location == campaign_location.location)]
+    pub device: Box<Account<'info, Device>>,
+    #[account(mut,
+        constraint = campaign.key() == promo.campaign,
+        constraint = campaign.key() == campaign_location.campaign,
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
+    pub campaign_location: Box<Account<'info, CampaignLocation>>,
+    /// CHECK: pubkey checked via mint request seeds
+    pub token_owner: UncheckedAccount<'info>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut, seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
+    pub promo: Box<Account<'info, Promo>>,
+    #[account(mut, close = request_payer,
+        seeds = [MINT_REQUEST_PREFIX.as_bytes(), mint.key().as_ref(), token_owner.key().as_ref()], bump,
+        constraint = mint_request.payer == request_payer.key(),
+    )]
+    pub mint_request: Box<Account<'info, MintRequest>>,
+    /// CHECK: checked against mint request payer
+    #[account(mut)]
+    pub request_payer: UncheckedAccount<'info>,
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref()], bump,
+    )]
+    pub location_count: Box<Account<'info, DailyCount>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref(), device.key().as_ref()], bump,
+    )]
+    pub device_count: Box<Account<'info, DailyCount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to rejecting a [MintRequest].
+///
+/// Requires a signature from the owner of a device with a location included in the campaign.
+/// The request is closed and its rent, along with the rent of the token account if it's empty,
+/// is returned to the payer of the request.
+#[derive(Accounts, Clone)]
+pub struct RejectMintRequest<'info> {
+    #[account(constraint = device.owner == device_owner.key())]
+    pub device_owner: Signer<'info>,
+    #[account(constraint = device.location == campaign_location.location)]
+    pub device: Box<Account<'info, Device>>,
+    #[account(constraint = campaign.key() == promo.campaign,
+        constraint = campaign.key() == campaign_location.campaign,
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
+    pub campaign_location: Box<Account<'info, CampaignLocation>>,
+    /// CHECK: pubkey checked via mint request seeds
+    pub token_owner: UncheckedAccount<'info>,
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
+    pub promo: Box<Account<'info, Promo>>,
+    #[account(mut, close = request_payer,
+        seeds = [MINT_REQUEST_PREFIX.as_bytes(), mint.key().as_ref(), token_owner.key().as_ref()], bump,
+        constraint = mint_request.payer == request_payer.key(),
+    )]
+    pub mint_request: Box<Account<'info, MintRequest>>,
+    /// CHECK: checked against mint request payer
+    #[account(mut)]
+    pub request_payer: UncheckedAccount<'info>,
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+}
+
+/// Accounts related to gifting a promo token to another customer.
+///
+/// Requires a signature from the sender as the authority of their token account. The
+/// recipient's token account is created if it doesn't exist yet. The [Gift] accounts of the
+/// sender and recipient are only needed for promos with a gift policy of `AllowedOnce`.
+#[derive(Accounts, Clone)]
+pub struct GiftPromoToken<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub sender: Signer<'info>,
+    /// CHECK: any wallet can receive a gift
+    pub recipient: UncheckedAccount<'info>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    #[account(seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
+    pub promo: Box<Account<'info, Promo>>,
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+        associated_token::token_program = token_program,
+    )]
+    pub sender_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    /// Records the gift to the recipient. Only needed when the promo's gift policy is
+    /// `AllowedOnce`, and rejected otherwise.
+    #[account(init_if_needed, payer = payer, space = Gift::LEN,
+        seeds = [GIFT_PREFIX.as_bytes(), mint.key().as_ref(), recipient.key().as_ref()], bump,
+    )]
+    pub gift: Option<Box<Account<'info, Gift>>>,
+    /// CHECK: pubkey checked via seeds, only exists if the sender has received a gift of the
+    /// promo. Needed with the recipient's gift account.
+    #[account(seeds = [GIFT_PREFIX.as_bytes(), mint.key().as_ref(), sender.key().as_ref()], bump)]
+    pub sender_gift: Option<UncheckedAccount<'info>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+/// Accounts related to minting a promo token into a [CustomerVault].
+///
+/// Requires a signature from the owner of a device with a location included in the campaign.
+/// The vault and its token account are created the first time a token of the promo is minted
+/// to the customer. The promo token is subject to the same caps, daily limits and allowlist as
+/// minting, with the vault address checked against the allowlist.
+#[derive(Accounts, Clone)]
+#[instruction(identity_hash: [u8; 32])]
+pub struct MintPromoTokenToVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(constraint = device.owner == device_owner.key())]
+    pub device_owner: Signer<'info>,
+    #[account(constraint = device.location == campaign_location.location)]
+    pub device: Box<Account<'info, Device>>,
+    #[account(mut,
+        constraint = campaign.key() == promo.campaign,
+        constraint = campaign.key() == campaign_location.campaign,
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
+    pub campaign_location: Box<Account<'info, CampaignLocation>>,
+    #[account(init_if_needed, payer = payer, space = CustomerVault::LEN,
+        seeds = [CUSTOMER_VAULT_PREFIX.as_bytes(), identity_hash.as_ref()], bump,
+    )]
+    pub customer_vault: Box<Account<'info, CustomerVault>>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut, seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
+    pub promo: Box<Account<'info, Promo>>,
+    #[account(init_if_needed, payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = customer_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref()], bump,
+    )]
+    pub location_count: Box<Account<'info, DailyCount>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref(), device.key().as_ref()], bump,
+    )]
+    pub device_count: Box<Account<'info, DailyCount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to claiming the tokens of a promo from a [CustomerVault].
+///
+/// Requires a signature from the vault authority in [AdminSettings], which the platform only
+/// gives after the customer has verified their phone number or email with a one time password,
+/// as well as from the customer's wallet. The vault's token account is closed once emptied and
+/// its rent returned to the payer.
+#[derive(Accounts, Clone)]
+#[instruction(identity_hash: [u8; 32])]
+pub struct ClaimCustomerVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(constraint = vault_authority.key() == admin_settings.vault_authority)]
+    pub vault_authority: Signer<'info>,
+    #[account(seeds = [ADMIN_PREFIX.as_bytes()], bump)]
+    pub admin_settings: Box<Account<'info, AdminSettings>>,
+    #[account(mut, seeds = [CUSTOMER_VAULT_PREFIX.as_bytes(), identity_hash.as_ref()], bump)]
+    pub customer_vault: Box<Account<'info, CustomerVault>>,
+    pub token_owner: Signer<'info>,
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = customer_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = token_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to the delegation of a promo token.
+///
+/// Delegates a token to a device owner.
+///
+/// Requires a signature from the owner of a device with a location included in the campaign
+/// as well as from the recipient (as a matter of responsible token issuance,
+/// bokoup always gets a recipient's consent before minting them any tokens).
+///
+/// Requires signature from token owner as the authority of the token account.
+///
+/// No platform fees result from delegating a token.
+#[derive(Accounts, Clone)]
+pub struct DelegatePromoToken<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: checked via device constraints
+    pub device_owner: UncheckedAccount<'info>,
+    #[account(
+        constraint = device.owner == device_owner.key(),
+        constraint = device.location == campaign_location.location
+    )]
+    pub device: Account<'info, Device>,
+    #[account(mut,
+        constraint = campaign.key() == promo.campaign,
+        constraint = campaign.key() == campaign_location.campaign,
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
+    pub campaign_location: Account<'info, CampaignLocation>,
+    pub token_owner: Signer<'info>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    #[account(mut, seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
+    pub promo: Account<'info, Promo>,
+    #[account(mut,
+        constraint = token_owner.key() == token_account.owner,
+        constraint = mint.key() == token_account.mint
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to the burning of a delegated promo token.
+///
+/// Checks to make sure signer is a member of group specified in owner property of
+/// promo in order to execute transaction to transfer lamports from group to platform
+/// to pay `burn_promo_token_lamports`.
+///
+/// The fee specified in the `burn_promo_token_lamports` property of the [AdminSettings] account
+/// is transferred from the [Group] specified in the `owner` property of the [Promo] from the
+/// lamports of the [Group] account to the account specified in the `platform` property of the [AdminSettings]
+/// account.
+///
+/// Once the last token in the token account is burned, the account is closed and its rent
+/// returned to the payer.
+///
+/// If the token owner was referred to the promo and the referrer hasn't been rewarded yet, a
+/// token of the reward promo is minted to the referrer, as long as the referral reward, reward
+/// promo and campaign caps haven't been reached.
+///
+/// If a [Sponsorship] of the promo is included, the burn fee and the reimbursement of the merchant
+/// of the device's location are paid from it instead while it has the funds.
+#[derive(Accounts, Clone)]
+pub struct BurnDelegatedPromoToken<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub device_owner: Signer<'info>,
+    #[account(
+        constraint = device.owner == device_owner.key(),
+        constraint = device.location == campaign_location.location
+    )]
+    pub device: Box<Account<'info, Device>>,
+    #[account(mut,
+        constraint = campaign.key() == promo.campaign,
+        constraint = campaign.key() == campaign_location.campaign,
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
+    pub campaign_location: Account<'info, CampaignLocation>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via spl token program instruction
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut,
+        seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump,
+        constraint = promo.campaign == campaign.key()
+    )]
+    pub promo: Account<'info, Promo>,
+    /// CHECK: pubkey checked via constraint
+    #[account(mut, constraint = platform.key() == admin_settings.platform)]
+    pub platform: UncheckedAccount<'info>,
+    #[account(seeds = [ADMIN_PREFIX.as_bytes()], bump)]
+    pub admin_settings: Account<'info, AdminSettings>,
+    #[account(mut,
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.delegate.unwrap() == device_owner.key(),
+        constraint = token_account.delegated_amount > 0,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref()], bump,
+    )]
+    pub location_count: Box<Account<'info, DailyCount>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref(), device.key().as_ref()], bump,
+    )]
+    pub device_count: Box<Account<'info, DailyCount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    // Referral accounts, only needed when the token owner was referred to the promo. The
+    // referrer, reward promo and referrer token account are checked against the referral and
+    // the promo referral reward in the processor.
+    #[account(mut,
+        seeds = [REFERRAL_PREFIX.as_bytes(), mint.key().as_ref(), token_account.owner.as_ref()], bump,
+    )]
+    pub referral: Option<Box<Account<'info, Referral>>>,
+    /// CHECK: checked against referral in processor
+    pub referrer: Option<UncheckedAccount<'info>>,
+    #[account(mut, mint::token_program = token_program)]
+    pub reward_mint: Option<Box<InterfaceAccount<'info, MintInterface>>>,
+    #[account(mut, constraint = reward_promo.campaign == campaign.key())]
+    pub reward_promo: Option<Box<Account<'info, Promo>>>,
+    /// CHECK: created if needed and checked in processor
+    #[account(mut)]
+    pub referrer_token_account: Option<UncheckedAccount<'info>>,
+    // Sponsorship accounts, only needed when the promo is sponsored. The payee is the owner of
+    // the merchant of the device's location, checked when paying the burn fee.
+    #[account(mut, constraint = sponsorship.campaign == campaign.key())]
+    pub sponsorship: Option<Box<Account<'info, Sponsorship>>>,
+    /// CHECK: checked against the payee merchant in processor
+    #[account(mut)]
+    pub payee: Option<UncheckedAccount<'info>>,
+    pub payee_location: Option<Box<Account<'info, Location>>>,
+    pub payee_merchant: Option<Box<Account<'info, Merchant>>>,
+}
+
+/// Accounts related to redeeming a promo token.
+///
+/// Burns a token directly from the token owner's account without a prior delegation so that
+/// redemption can be completed in a single transaction, e.g. at an e-commerce checkout. Requires
+/// signatures from both the token owner and the owner of a device with a location included in
+/// the campaign.
+///
+/// The fee specified in the `burn_promo_token_lamports` property of the [AdminSettings] account
+/// is transferred from the lamports of the [Campaign] to the account specified in the `platform`
+/// property of the [AdminSettings] account, the same as when burning a delegated token, or from
+/// a [Sponsorship] if one is included. The token account is also closed once its last token is
+/// burned.
+#[derive(Accounts, Clone)]
+pub struct RedeemPromoToken<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub device_owner: Signer<'info>,
+    #[account(
+        constraint = device.owner == device_owner.key(),
+        constraint = device.location == campaign_location.location
+    )]
+    pub device: Box<Account<'info, Device>>,
+    #[account(mut,
+        constraint = campaign.key() == promo.campaign,
+        constraint = campaign.key() == campaign_location.campaign,
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    #[account(seeds = [CAMPAIGN_LOCATION_PREFIX.as_bytes(), campaign.key().as_ref(), device.location.as_ref()], bump)]
+    pub campaign_location: Account<'info, CampaignLocation>,
+    pub token_owner: Signer<'info>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut, seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
+    pub promo: Account<'info, Promo>,
+    /// CHECK: pubkey checked via constraint
+    #[account(mut, constraint = platform.key() == admin_settings.platform)]
+    pub platform: UncheckedAccount<'info>,
+    #[account(seeds = [ADMIN_PREFIX.as_bytes()], bump)]
+    pub admin_settings: Account<'info, AdminSettings>,
+    #[account(mut,
+        constraint = token_account.owner == token_owner.key(),
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.amount > 0,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref()], bump,
+    )]
+    pub location_count: Box<Account<'info, DailyCount>>,
+    #[account(init_if_needed, payer = payer, space = DailyCount::LEN,
+        seeds = [DAILY_COUNT_PREFIX.as_bytes(), campaign_location.key().as_ref(), device.key().as_ref()], bump,
+    )]
+    pub device_count: Box<Account<'info, DailyCount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub system_program: Program<'info, System>,
+    // Sponsorship accounts, only needed when the promo is sponsored. The payee is the owner of
+    // the merchant of the device's location, checked when paying the burn fee.
+    #[account(mut, constraint = sponsorship.campaign == campaign.key())]
+    pub sponsorship: Option<Box<Account<'info, Sponsorship>>>,
+    /// CHECK: checked against the payee merchant in processor
+    #[account(mut)]
+    pub payee: Option<UncheckedAccount<'info>>,
+    pub payee_location: Option<Box<Account<'info, Location>>>,
+    pub payee_merchant: Option<Box<Account<'info, Merchant>>>,
+}
+
+/// Accounts related to clawing back promo tokens.
+///
+/// Checks to make sure the signer is the owner of the merchant that owns the campaign of the
+/// promo. Burning requires the mint to have been created with the permanent delegate extension,
+/// while freezing uses the freeze authority the program retains on every promo mint. The clawed
+/// back tokens are subtracted from the `mint_count` of the [Promo] and [Campaign] so they don't
+/// count against `max_mint`.
+#[derive(Accounts, Clone)]
+pub struct ClawbackPromoToken<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() == merchant.owner)]
+    pub merchant: Account<'info, Merchant>,
+    #[account(mut,
+        constraint = campaign.merchant == merchant.key(),
+        constraint = campaign.key() == promo.campaign,
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut, seeds = [PROMO_PREFIX.as_bytes(), mint.key().as_ref()], bump)]
+    pub promo: Account<'info, Promo>,
+    #[account(mut, constraint = token_account.mint == mint.key())]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, SplMemo>,
+}
+
+/// Accounts related to creating a [Membership].
+///
+/// Only the merchant owner can create memberships. The master edition mint is created by the
+/// program and its token is held by the program authority, so passes can only be printed with
+/// the `issue_membership_pass` instruction.
+#[derive(Accounts, Clone)]
+pub struct CreateMembership<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() == merchant.owner)]
+    pub merchant: Box<Account<'info, Merchant>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(init, payer = payer, mint::decimals = 0, mint::authority = authority, mint::freeze_authority = authority)]
+    pub mint: Account<'info, Mint>,
+    #[account(init, payer = payer, associated_token::mint = mint, associated_token::authority = authority)]
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK: checked via cpi
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: checked via cpi
+    #[account(mut)]
+    pub edition: UncheckedAccount<'info>,
+    #[account(init, payer = payer, space = Membership::LEN,
+        seeds = [MEMBERSHIP_PREFIX.as_bytes(), mint.key().as_ref()], bump,
+    )]
+    pub membership: Box<Account<'info, Membership>>,
+    pub metadata_program: Program<'info, TokenMetadata>,
+    pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub memo_program: Program<'info, SplMemo>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
 }
 
+/// Accounts related to issuing a [MembershipPass].
+///
+/// Only the merchant owner can issue passes. The pass mint is a new keypair that signs the
+/// transaction, and becomes a print edition of the membership's master edition held in the
+/// token owner's associated token account.
+#[derive(Accounts, Clone)]
+pub struct IssueMembershipPass<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() == merchant.owner)]
+    pub merchant: Box<Account<'info, Merchant>>,
+    #[account(mut,
+        constraint = membership.merchant == merchant.key(),
+        seeds = [MEMBERSHIP_PREFIX.as_bytes(), master_mint.key().as_ref()], bump,
+    )]
+    pub membership: Box<Account<'info, Membership>>,
+    /// CHECK: pubkey checked via seeds
+    #[account(seeds = [AUTHORITY_PREFIX.as_bytes()], bump)]
+    pub authority: UncheckedAccount<'info>,
+    pub master_mint: Box<Account<'info, Mint>>,
+    #[account(associated_token::mint = master_mint, associated_token::authority = authority)]
+    pub master_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: checked via cpi
+    pub master_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked via cpi
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+    /// CHECK: any wallet can be issued a pass
+    pub token_owner: UncheckedAccount<'info>,
+    #[account(init, payer = payer, mint::decimals = 0, mint::authority = authority, mint::freeze_authority = authority)]
+    pub pass_mint: Box<Account<'info, Mint>>,
+    #[account(init, payer = payer, associated_token::mint = pass_mint, associated_token::authority = token_owner)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: checked via cpi
+    #[account(mut)]
+    pub pass_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked via cpi
+    #[account(mut)]
+    pub pass_edition: UncheckedAccount<'info>,
+    /// CHECK: checked via cpi
+    #[account(mut)]
+    pub edition_marker: UncheckedAccount<'info>,
+    #[account(init, payer = payer, space = MembershipPass::LEN,
+        seeds = [MEMBERSHIP_PASS_PREFIX.as_bytes(), pass_mint.key().as_ref()], bump,
+    )]
+    pub membership_pass: Box<Account<'info, MembershipPass>>,
+    pub metadata_program: Program<'info, TokenMetadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts related to renewing a [MembershipPass].
+///
+/// Only the merchant owner can renew passes, e.g. once the customer has paid for the renewal.
+#[derive(Accounts, Clone)]
+pub struct RenewMembershipPass<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() == merchant.owner)]
+    pub merchant: Box<Account<'info, Merchant>>,
+    #[account(constraint = membership.merchant == merchant.key())]
+    pub membership: Box<Account<'info, Membership>>,
+    #[account(mut,
+        constraint = membership_pass.membership == membership.key(),
+        seeds = [MEMBERSHIP_PASS_PREFIX.as_bytes(), membership_pass.mint.as_ref()], bump,
+    )]
+    pub membership_pass: Box<Account<'info, MembershipPass>>,
+    pub memo_program: Program<'info, SplMemo>,
+}
+
+/// Accounts related to checking in with a [MembershipPass].
+///
+/// Requires signatures from the owner of a device at one of the membership merchant's locations
+/// and from the holder of the pass, who doesn't need to be the customer it was issued to.
+#[derive(Accounts, Clone)]
+pub struct CheckIn<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub device_owner: Signer<'info>,
+    #[account(
+        constraint = device.owner == device_owner.key(),
+        constraint = device.location == location.key(),
+    )]
+    pub device: Box<Account<'info, Device>>,
+    #[account(constraint = location.merchant == membership.merchant)]
+    pub location: Box<Account<'info, Location>>,
+    pub membership: Box<Account<'info, Membership>>,
+    #[account(mut,
+        constraint = membership_pass.membership == membership.key(),
+        seeds = [MEMBERSHIP_PASS_PREFIX.as_bytes(), membership_pass.mint.as_ref()], bump,
+    )]
+    pub membership_pass: Box<Account<'info, MembershipPass>>,
+    pub token_owner: Signer<'info>,
+    #[account(
+        constraint = token_account.mint == membership_pass.mint,
+        constraint = token_account.owner == token_owner.key(),
+        constraint = token_account.amount == 1,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub memo_program: Program<'info, SplMemo>,
+}
+
+/// Accounts related to migrating a program account to its current size.
+///
+/// Any payer can migrate an account, since it only grows the account and zeroes the new bytes.
+#[derive(Accounts, Clone)]
+pub struct MigrateAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: discriminator checked in processor
+    #[account(mut, owner = crate::id())]
+    pub account: UncheckedAccount<'info>,
+    pub memo_program: Program<'info, SplMemo>,
+    pub system_program: Program<'info, System>,
+}
+
 /// Account related to creation of non-fungibles - not yet implemented.
 #[derive(Accounts, Clone)]
 pub struct CreateNonFungible<'info> {
@@ -571,8 +1639,9 @@ pub struct CreateMetaData<'info> {
     pub payer: Signer<'info>,
     /// CHECK: checked via cpi
     pub metadata_account: UncheckedAccount<'info>,
+    /// CHECK: checked via cpi
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: AccountInfo<'info>,
     /// CHECK: checked via cpi
     #[account(mut)]
     pub mint_authority: UncheckedAccount<'info>,
@@ -598,7 +1667,7 @@ impl<'info> From<CreatePromo<'info>> for CreateMetaData<'info> {
         CreateMetaData {
             payer: item.payer,
             metadata_account: item.metadata,
-            mint: item.mint,
+            mint: item.mint.to_account_info(),
             mint_authority: item.authority.clone(),
             metadata_authority: item.authority,
             metadata_program: item.metadata_program,
@@ -608,12 +1677,45 @@ impl<'info> From<CreatePromo<'info>> for CreateMetaData<'info> {
     }
 }
 
+impl<'info> From<CreateMembership<'info>> for CreateMetaData<'info> {
+    fn from(item: CreateMembership<'info>) -> Self {
+        CreateMetaData {
+            payer: item.payer,
+            metadata_account: item.metadata,
+            mint: item.mint.to_account_info(),
+            mint_authority: item.authority.clone(),
+            metadata_authority: item.authority,
+            metadata_program: item.metadata_program,
+            rent: item.rent,
+            system_program: item.system_program,
+        }
+    }
+}
+
+impl<'info> From<CreateMembership<'info>> for CreateNonFungible<'info> {
+    fn from(item: CreateMembership<'info>) -> Self {
+        CreateNonFungible {
+            payer: item.payer,
+            authority: item.authority,
+            mint: item.mint,
+            token_account: item.token_account,
+            metadata_account: item.metadata,
+            edition_account: item.edition,
+            metadata_program: item.metadata_program,
+            token_program: item.token_program,
+            associated_token_program: item.associated_token_program,
+            rent: item.rent,
+            system_program: item.system_program,
+        }
+    }
+}
+
 impl<'info> From<CreateNonFungible<'info>> for CreateMetaData<'info> {
     fn from(item: CreateNonFungible<'info>) -> Self {
         CreateMetaData {
             payer: item.payer,
             metadata_account: item.metadata_account,
-            mint: item.mint,
+            mint: item.mint.to_account_info(),
             mint_authority: item.authority.clone(),
             metadata_authority: item.authority,
             metadata_program: item.metadata_program,
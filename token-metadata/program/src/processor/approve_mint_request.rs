@@ -0,0 +1,54 @@
+use crate::utils::{check_allowlist, check_mint_limits, create_memo};
+use crate::ApproveMintRequest;
+use anchor_lang::prelude::*;
+
+impl<'info> ApproveMintRequest<'info> {
+    pub fn process(
+        &mut self,
+        proof: Vec<[u8; 32]>,
+        memo: Option<String>,
+        authority_seeds: [&[u8]; 2],
+    ) -> Result<()> {
+        msg!("Approve mint request");
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        check_mint_limits(
+            &self.promo,
+            &self.campaign,
+            &self.campaign_location,
+            &mut self.location_count,
+            &mut self.device_count,
+            unix_timestamp,
+        )?;
+
+        check_allowlist(&self.promo, &proof, &self.token_owner.key())?;
+
+        // The close authority was set to the program when the mint was requested.
+        let mint_to_ctx = anchor_spl::token_interface::MintTo {
+            mint: self.mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                mint_to_ctx,
+                &[&authority_seeds],
+            ),
+            1,
+        )?;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.device_owner.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        self.promo.mint_count += 1;
+        self.campaign.mint_count += 1;
+        self.location_count.mint_count += 1;
+        self.device_count.mint_count += 1;
+
+        Ok(())
+    }
+}
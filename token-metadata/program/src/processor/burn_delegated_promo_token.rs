@@ -1,65 +1,162 @@
-use crate::utils::create_memo;
+use crate::utils::{
+    check_burn_limits, create_memo, find_associated_token_address, pay_burn_fee, SponsorshipPayee,
+};
 use crate::{error::ProgramError, BurnDelegatedPromoToken};
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
 
 impl<'info> BurnDelegatedPromoToken<'info> {
-    pub fn process(&mut self, memo: Option<String>) -> Result<()> {
+    pub fn process(&mut self, memo: Option<String>, authority_seeds: [&[u8]; 2]) -> Result<()> {
         msg!("Burn delegated promo token");
 
-        // Check to see if burn_count is still below max_burn.
-        if let Some(max_burn) = self.promo.max_burn {
-            if self.promo.burn_count >= max_burn {
-                return Err(ProgramError::MaxBurnExceeded.into());
-            }
-        }
-
-        if self.admin_settings.burn_promo_token_lamports > 0 {
-            let campaign = self.campaign.to_account_info();
-            let platform = self.platform.to_account_info();
-            let amount = self.admin_settings.burn_promo_token_lamports;
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        check_burn_limits(
+            &self.promo,
+            &self.campaign,
+            &self.campaign_location,
+            &mut self.location_count,
+            &mut self.device_count,
+            unix_timestamp,
+        )?;
 
-            **campaign.try_borrow_mut_lamports()? =
-                campaign.lamports().checked_sub(amount).unwrap();
-            **platform.try_borrow_mut_lamports()? =
-                platform.lamports().checked_add(amount).unwrap();
-        }
+        let payee = match (&self.payee, &self.payee_location, &self.payee_merchant) {
+            (Some(owner), Some(location), Some(merchant)) => Some(SponsorshipPayee {
+                location,
+                merchant,
+                owner,
+            }),
+            (None, None, None) => None,
+            _ => return Err(ProgramError::InvalidSponsorshipAccounts.into()),
+        };
+        pay_burn_fee(
+            self.admin_settings.burn_promo_token_lamports,
+            &self.mint.key(),
+            &self.device.location,
+            self.campaign.to_account_info(),
+            self.platform.to_account_info(),
+            self.sponsorship.as_deref_mut(),
+            payee,
+        )?;
 
-        // device owner is the payer
-        let burn_ctx = anchor_spl::token::Burn {
+        // device owner is the delegate
+        let burn_ctx = anchor_spl::token_interface::Burn {
             mint: self.mint.to_account_info(),
             from: self.token_account.to_account_info(),
             authority: self.device_owner.to_account_info(),
         };
 
-        anchor_spl::token::burn(
+        anchor_spl::token_interface::burn(
             CpiContext::new(self.token_program.to_account_info(), burn_ctx),
             1,
         )?;
 
-        // TODO: update indexer to delete token accounts if the close account instruction
-        // is included.
-        // if self.token_account.amount == 1 {
-        //     let close_ctx = anchor_spl::token::CloseAccount {
-        //         account: self.token_account.to_account_info(),
-        //         destination: self.group.to_account_info(),
-        //         authority: self.authority.to_account_info(),
-        //     };
-        //     anchor_spl::token::close_account(CpiContext::new_with_signer(
-        //         self.token_program.to_account_info(),
-        //         close_ctx,
-        //         &[&authority_seeds],
-        //     ))?;
-        // }
+        // Close the token account once the last token has been burned and return the rent to
+        // the payer, who paid it when the token was minted.
+        self.token_account.reload()?;
+        if self.token_account.amount == 0
+            && self.token_account.close_authority == COption::Some(self.authority.key())
+        {
+            let close_ctx = anchor_spl::token_interface::CloseAccount {
+                account: self.token_account.to_account_info(),
+                destination: self.payer.to_account_info(),
+                authority: self.authority.to_account_info(),
+            };
+            anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                close_ctx,
+                &[&authority_seeds],
+            ))?;
+        }
 
         if let Some(memo) = memo {
             let account_infos = vec![
                 self.payer.to_account_info(),
                 self.device_owner.to_account_info(),
             ];
-            create_memo(memo.to_string(), account_infos)?;
+            create_memo(memo, account_infos)?;
         }
 
         self.promo.burn_count += 1;
+        self.campaign.burn_count += 1;
+        self.location_count.burn_count += 1;
+        self.device_count.burn_count += 1;
+
+        self.reward_referrer(authority_seeds)
+    }
+
+    /// Mints a token of the reward promo to the referrer of the token owner, if the token owner
+    /// was referred and the referrer hasn't already been rewarded for them. Rewards over the
+    /// referral, reward promo or campaign caps are skipped rather than failing the burn.
+    fn reward_referrer(&mut self, authority_seeds: [&[u8]; 2]) -> Result<()> {
+        let referral = match self.referral.as_mut() {
+            Some(referral) => referral,
+            None => return Ok(()),
+        };
+        let reward = match self.promo.referral.as_mut() {
+            Some(reward) => reward,
+            None => return Ok(()),
+        };
+        if referral.rewarded || referral.referred == Pubkey::default() {
+            return Ok(());
+        }
+
+        let (referrer, reward_mint, reward_promo, referrer_token_account) = match (
+            self.referrer.as_ref(),
+            self.reward_mint.as_ref(),
+            self.reward_promo.as_mut(),
+            self.referrer_token_account.as_ref(),
+        ) {
+            (Some(a), Some(b), Some(c), Some(d)) => (a, b, c, d),
+            _ => return Err(ProgramError::InvalidReferralAccounts.into()),
+        };
+        if referrer.key() != referral.referrer
+            || reward_mint.key() != reward.reward_mint
+            || reward_promo.mint != reward_mint.key()
+            || referrer_token_account.key()
+                != find_associated_token_address(
+                    &referrer.key(),
+                    &reward_mint.key(),
+                    &self.token_program.key(),
+                )
+        {
+            return Err(ProgramError::InvalidReferralAccounts.into());
+        }
+
+        if !reward.is_available(reward_promo, &self.campaign) {
+            msg!("Referral reward not available");
+            return Ok(());
+        }
+
+        let create_ctx = anchor_spl::associated_token::Create {
+            payer: self.payer.to_account_info(),
+            associated_token: referrer_token_account.to_account_info(),
+            authority: referrer.to_account_info(),
+            mint: reward_mint.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+        anchor_spl::associated_token::create_idempotent(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            create_ctx,
+        ))?;
+
+        let mint_to_ctx = anchor_spl::token_interface::MintTo {
+            mint: reward_mint.to_account_info(),
+            to: referrer_token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                mint_to_ctx,
+                &[&authority_seeds],
+            ),
+            1,
+        )?;
+
+        referral.rewarded = true;
+        reward.reward_count += 1;
+        reward_promo.mint_count += 1;
+        self.campaign.mint_count += 1;
 
         Ok(())
     }
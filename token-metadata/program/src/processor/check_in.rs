@@ -0,0 +1,26 @@
+use crate::{error::ProgramError, utils::create_memo, CheckIn};
+use anchor_lang::prelude::*;
+
+impl<'info> CheckIn<'info> {
+    pub fn process(&mut self, memo: Option<String>) -> Result<()> {
+        msg!("Check in");
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        if unix_timestamp > self.membership_pass.expires_at {
+            return Err(ProgramError::MembershipExpired.into());
+        }
+
+        self.membership_pass.check_ins += 1;
+        self.membership_pass.last_check_in_at = unix_timestamp;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![
+                self.token_owner.to_account_info(),
+                self.device_owner.to_account_info(),
+            ];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    error::ProgramError,
+    utils::{create_memo, set_close_authority},
+    ClaimCustomerVault,
+};
+use anchor_lang::prelude::*;
+
+impl<'info> ClaimCustomerVault<'info> {
+    pub fn process(&mut self, memo: Option<String>, vault_seeds: [&[u8]; 3]) -> Result<()> {
+        msg!("Claim customer vault");
+
+        let amount = self.vault_token_account.amount;
+        if amount == 0 {
+            return Err(ProgramError::CustomerVaultEmpty.into());
+        }
+
+        // Set the close authority to the program so it can close the token account when it
+        // burns the last token in it, the same as when minting.
+        set_close_authority(
+            self.token_program.to_account_info(),
+            &self.token_account,
+            self.token_owner.to_account_info(),
+            &self.authority.key(),
+        )?;
+
+        let transfer_ctx = anchor_spl::token_interface::TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.customer_vault.to_account_info(),
+        };
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                transfer_ctx,
+                &[&vault_seeds],
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        let close_ctx = anchor_spl::token_interface::CloseAccount {
+            account: self.vault_token_account.to_account_info(),
+            destination: self.payer.to_account_info(),
+            authority: self.customer_vault.to_account_info(),
+        };
+
+        anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_ctx,
+            &[&vault_seeds],
+        ))?;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.token_owner.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        self.customer_vault.claimed_by = Some(self.token_owner.key());
+
+        Ok(())
+    }
+}
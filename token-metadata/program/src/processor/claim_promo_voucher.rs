@@ -0,0 +1,108 @@
+use crate::state::Voucher;
+use crate::utils::{
+    check_allowlist, check_mint_limits, create_memo, set_close_authority,
+    verify_ed25519_instruction,
+};
+use crate::{error::ProgramError, ClaimPromoVoucher};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+impl<'info> ClaimPromoVoucher<'info> {
+    pub fn process(
+        &mut self,
+        voucher: Voucher,
+        proof: Vec<[u8; 32]>,
+        memo: Option<String>,
+        authority_seeds: [&[u8]; 2],
+    ) -> Result<()> {
+        msg!("Claim promo voucher");
+
+        // Promos that require approval are only minted by approving a mint request.
+        if self.promo.requires_approval {
+            return Err(ProgramError::MintApprovalRequired.into());
+        }
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        if unix_timestamp > voucher.expiry {
+            return Err(ProgramError::VoucherExpired.into());
+        }
+
+        if let Some(recipient) = voucher.recipient {
+            if recipient != self.token_owner.key() {
+                return Err(ProgramError::VoucherRecipientMismatch.into());
+            }
+        }
+
+        // Check that the instruction before this one verified the device owner's signature over
+        // the voucher.
+        let instructions = self.instructions.to_account_info();
+        let current_index = load_current_index_checked(&instructions)? as usize;
+        let ed25519_ix = current_index
+            .checked_sub(1)
+            .map(|index| load_instruction_at_checked(index, &instructions))
+            .transpose()?
+            .ok_or(ProgramError::InvalidVoucherSignature)?;
+        if !verify_ed25519_instruction(
+            &ed25519_ix,
+            &self.device_owner.key(),
+            &voucher.try_to_vec()?,
+        ) {
+            return Err(ProgramError::InvalidVoucherSignature.into());
+        }
+
+        check_mint_limits(
+            &self.promo,
+            &self.campaign,
+            &self.campaign_location,
+            &mut self.location_count,
+            &mut self.device_count,
+            unix_timestamp,
+        )?;
+
+        check_allowlist(&self.promo, &proof, &self.token_owner.key())?;
+
+        // Set the close authority to the program so it can close the token account when it
+        // burns the last token in it, as when minting.
+        set_close_authority(
+            self.token_program.to_account_info(),
+            &self.token_account,
+            self.token_owner.to_account_info(),
+            &self.authority.key(),
+        )?;
+
+        let mint_to_ctx = anchor_spl::token_interface::MintTo {
+            mint: self.mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                mint_to_ctx,
+                &[&authority_seeds],
+            ),
+            1,
+        )?;
+
+        self.voucher_claim.promo = voucher.promo;
+        self.voucher_claim.nonce = voucher.nonce;
+        self.voucher_claim.device = self.device.key();
+        self.voucher_claim.claimed_by = self.token_owner.key();
+        self.voucher_claim.claimed_at = unix_timestamp;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.token_owner.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        self.promo.mint_count += 1;
+        self.campaign.mint_count += 1;
+        self.location_count.mint_count += 1;
+        self.device_count.mint_count += 1;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use crate::utils::create_memo;
+use crate::{error::ProgramError, ClawbackPromoToken};
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::token_2022::{
+    self,
+    spl_token_2022::{
+        self,
+        extension::{
+            permanent_delegate::PermanentDelegate, BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+};
+
+impl<'info> ClawbackPromoToken<'info> {
+    pub fn process(
+        &mut self,
+        burn: bool,
+        reason: String,
+        authority_seeds: [&[u8]; 2],
+    ) -> Result<()> {
+        msg!("Clawback promo token");
+
+        let amount = self.token_account.amount;
+        let frozen = self.token_account.is_frozen();
+
+        if burn {
+            if !self.is_permanent_delegate()? {
+                return Err(ProgramError::PermanentDelegateRequired.into());
+            }
+
+            // Tokens previously clawed back by freezing have to be thawed before they can be
+            // burned.
+            if frozen {
+                let thaw_ctx = anchor_spl::token_interface::ThawAccount {
+                    account: self.token_account.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                };
+                anchor_spl::token_interface::thaw_account(CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    thaw_ctx,
+                    &[&authority_seeds],
+                ))?;
+            }
+
+            if amount > 0 {
+                let burn_ctx = anchor_spl::token_interface::Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.token_account.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                };
+                anchor_spl::token_interface::burn(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        burn_ctx,
+                        &[&authority_seeds],
+                    ),
+                    amount,
+                )?;
+            }
+
+            // Close the emptied token account if the program was set as close authority when
+            // the token was minted.
+            if self.token_account.close_authority == COption::Some(self.authority.key()) {
+                let close_ctx = anchor_spl::token_interface::CloseAccount {
+                    account: self.token_account.to_account_info(),
+                    destination: self.payer.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                };
+                anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    close_ctx,
+                    &[&authority_seeds],
+                ))?;
+            }
+        } else {
+            if frozen {
+                return Err(ProgramError::TokenAccountFrozen.into());
+            }
+
+            let freeze_ctx = anchor_spl::token_interface::FreezeAccount {
+                account: self.token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.authority.to_account_info(),
+            };
+            anchor_spl::token_interface::freeze_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                freeze_ctx,
+                &[&authority_seeds],
+            ))?;
+        }
+
+        let account_infos = vec![self.payer.to_account_info(), self.owner.to_account_info()];
+        create_memo(reason, account_infos)?;
+
+        // Frozen tokens were already subtracted when they were clawed back.
+        if !frozen {
+            let amount = u32::try_from(amount).unwrap_or(u32::MAX);
+            self.promo.mint_count = self.promo.mint_count.saturating_sub(amount);
+            self.campaign.mint_count = self.campaign.mint_count.saturating_sub(amount);
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the program authority is the permanent delegate of a Token-2022 mint.
+    fn is_permanent_delegate(&self) -> Result<bool> {
+        if self.token_program.key() != token_2022::ID {
+            return Ok(false);
+        }
+
+        let mint = self.mint.to_account_info();
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        Ok(match state.get_extension::<PermanentDelegate>() {
+            Ok(extension) => {
+                Option::<Pubkey>::from(extension.delegate) == Some(self.authority.key())
+            }
+            Err(_) => false,
+        })
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{utils::create_memo, CloseSponsorship};
+use anchor_lang::prelude::*;
+
+impl<'info> CloseSponsorship<'info> {
+    pub fn process(&mut self, memo: Option<String>) -> Result<()> {
+        msg!("Close sponsorship");
+
+        // The account and its remaining lamports are returned to the sponsor by the close
+        // constraint once the instruction succeeds.
+        if let Some(memo) = memo {
+            let account_infos = vec![self.sponsor.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
@@ -9,14 +9,20 @@ impl<'info> CreateCampaign<'info> {
     pub fn process(&mut self, data: Campaign, lamports: u64, memo: Option<String>) -> Result<()> {
         msg!("Create campaign");
 
-        *self.campaign = data;
+        // Counts always start at zero.
+        *self.campaign = Campaign {
+            mint_count: 0,
+            burn_count: 0,
+            ..data
+        };
 
+        // Funded by the payer rather than the owner, which may be a PDA signing through cpi.
         if lamports > 0 {
             transfer_sol(
                 CpiContext::new(
                     self.system_program.to_account_info(),
                     TransferSol {
-                        payer: self.owner.to_account_info(),
+                        payer: self.payer.to_account_info(),
                         to: self.campaign.to_account_info(),
                     },
                 ),
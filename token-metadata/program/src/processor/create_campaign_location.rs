@@ -8,6 +8,7 @@ impl<'info> CreateCampaignLocation<'info> {
         *self.campaign_location = CampaignLocation {
             campaign: self.campaign.key(),
             location: self.location.key(),
+            ..Default::default()
         };
 
         if let Some(memo) = memo {
@@ -0,0 +1,80 @@
+use crate::{
+    error::ProgramError,
+    state::{CampaignLocation, Location},
+    utils::{create_memo, CAMPAIGN_LOCATION_PREFIX},
+    CreateCampaignLocations,
+};
+use anchor_lang::{prelude::*, system_program};
+
+impl<'info> CreateCampaignLocations<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        memo: Option<String>,
+    ) -> Result<()> {
+        msg!("Create campaign locations");
+
+        if remaining_accounts.is_empty() || remaining_accounts.len() % 2 != 0 {
+            return Err(ProgramError::InvalidCampaignLocationAccounts.into());
+        }
+
+        let campaign = self.campaign.key();
+        let lamports = Rent::get()?.minimum_balance(CampaignLocation::LEN);
+
+        for accounts in remaining_accounts.chunks(2) {
+            let (location_info, campaign_location_info) = (&accounts[0], &accounts[1]);
+
+            let location = Account::<Location>::try_from(location_info)?;
+            if location.merchant != self.merchant.key() {
+                return Err(ProgramError::InvalidCampaignLocationAccounts.into());
+            }
+
+            let (address, bump) = Pubkey::find_program_address(
+                &[
+                    CAMPAIGN_LOCATION_PREFIX.as_bytes(),
+                    campaign.as_ref(),
+                    location_info.key.as_ref(),
+                ],
+                &crate::ID,
+            );
+            if campaign_location_info.key() != address || !campaign_location_info.is_writable {
+                return Err(ProgramError::InvalidCampaignLocationAccounts.into());
+            }
+
+            // Fails if the location is already linked to the campaign.
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::CreateAccount {
+                        from: self.payer.to_account_info(),
+                        to: campaign_location_info.clone(),
+                    },
+                    &[&[
+                        CAMPAIGN_LOCATION_PREFIX.as_bytes(),
+                        campaign.as_ref(),
+                        location_info.key.as_ref(),
+                        &[bump],
+                    ]],
+                ),
+                lamports,
+                CampaignLocation::LEN as u64,
+                &crate::ID,
+            )?;
+
+            let campaign_location = CampaignLocation {
+                campaign,
+                location: location_info.key(),
+                ..Default::default()
+            };
+            campaign_location
+                .try_serialize(&mut &mut campaign_location_info.try_borrow_mut_data()?[..])?;
+        }
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.owner.to_account_info(), self.payer.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
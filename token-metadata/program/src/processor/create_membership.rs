@@ -0,0 +1,75 @@
+use crate::{
+    error::ProgramError,
+    state::{DataV2, Membership},
+    utils::{create_master_edition_v3, create_memo, create_metadata_accounts_v2},
+    CreateMembership, CreateMetaData, CreateNonFungible,
+};
+use anchor_lang::prelude::*;
+
+impl<'info> CreateMembership<'info> {
+    pub fn process(
+        &mut self,
+        data: DataV2,
+        duration: i64,
+        max_supply: Option<u64>,
+        memo: Option<String>,
+        authority_seeds: [&[u8]; 2],
+    ) -> Result<()> {
+        msg!("Create membership");
+
+        if duration <= 0 {
+            return Err(ProgramError::InvalidMembershipDuration.into());
+        }
+
+        **self.membership = Membership {
+            merchant: self.merchant.key(),
+            mint: self.mint.key(),
+            duration,
+            pass_count: 0,
+        };
+
+        // The master edition token is held by the program authority so that only the program
+        // can print passes.
+        let mint_to_ctx = anchor_spl::token::MintTo {
+            mint: self.mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        anchor_spl::token::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                mint_to_ctx,
+                &[&authority_seeds],
+            ),
+            1,
+        )?;
+
+        create_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                self.metadata_program.to_account_info(),
+                CreateMetaData::from(self.clone()),
+                &[&authority_seeds],
+            ),
+            false,
+            true,
+            data.into(),
+        )?;
+
+        create_master_edition_v3(
+            CpiContext::new_with_signer(
+                self.metadata_program.to_account_info(),
+                CreateNonFungible::from(self.clone()),
+                &[&authority_seeds],
+            ),
+            max_supply,
+        )?;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.owner.to_account_info(), self.payer.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
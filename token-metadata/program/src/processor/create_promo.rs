@@ -1,9 +1,14 @@
 use crate::{
-    state::{DataV2, Promo},
-    utils::{create_memo, create_metadata_accounts_v2},
+    error::ProgramError as BplError,
+    state::{DataV2, GiftPolicy, Promo, PromoExtensions},
+    utils::{create_memo, create_metadata_accounts_v2, initialize_metadata_pointer},
     CreateMetaData, CreatePromo,
 };
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::program_pack::Pack, system_program};
+use anchor_spl::{
+    token::spl_token,
+    token_2022::{self, spl_token_2022},
+};
 
 impl<'info> CreatePromo<'info> {
     pub fn process(
@@ -11,13 +16,42 @@ impl<'info> CreatePromo<'info> {
         promo_data: Promo,
         metadata_data: DataV2,
         is_mutable: bool,
+        extensions: PromoExtensions,
         authority_seeds: [&[u8]; 2],
         memo: Option<String>,
     ) -> Result<()> {
         msg!("Create promo");
 
+        if let Some(schedule) = &promo_data.schedule {
+            if !schedule.is_valid() {
+                return Err(BplError::InvalidSchedule.into());
+            }
+        }
+
+        if let Some(terms) = &promo_data.terms {
+            if !terms.is_valid() {
+                return Err(BplError::InvalidPromoTerms.into());
+            }
+        }
+
+        // Tokens that can be transferred directly could be moved on after being received as a
+        // gift, so gifting once is only enforceable for non-transferable promos.
+        if promo_data.gift_policy == GiftPolicy::AllowedOnce && !extensions.non_transferable {
+            return Err(BplError::GiftOnceRequiresNonTransferable.into());
+        }
+
+        if promo_data.stamp_threshold == Some(0) {
+            return Err(BplError::ConstraintNotMet.into());
+        }
+
+        if let Some(referral) = &promo_data.referral {
+            if referral.reward_mint == self.mint.key() {
+                return Err(BplError::InvalidReferralReward.into());
+            }
+        }
+
         // Error if not enough lamports
-        if self.campaign.to_account_info().lamports.borrow().clone()
+        if **self.campaign.to_account_info().lamports.borrow()
             < self.admin_settings.create_promo_lamports
         {
             return Err(ProgramError::InsufficientFunds.into());
@@ -34,6 +68,8 @@ impl<'info> CreatePromo<'info> {
                 platform.lamports().checked_add(amount).unwrap();
         }
 
+        self.create_mint(extensions)?;
+
         create_metadata_accounts_v2(
             CpiContext::new_with_signer(
                 self.metadata_program.to_account_info(),
@@ -53,4 +89,70 @@ impl<'info> CreatePromo<'info> {
         **self.promo = promo_data;
         Ok(())
     }
+
+    /// Creates the mint account owned by the selected token program, initializes any Token-2022
+    /// extensions and then the mint itself, with the program authority as mint and freeze
+    /// authority.
+    fn create_mint(&self, extensions: PromoExtensions) -> Result<()> {
+        let token_program = self.token_program.key();
+        let mint = self.mint.to_account_info();
+        let authority = self.authority.key();
+
+        let space = if token_program == token_2022::ID {
+            extensions.mint_len()
+        } else if extensions.is_empty() {
+            spl_token::state::Mint::LEN
+        } else {
+            return Err(BplError::ExtensionsRequireToken2022.into());
+        };
+
+        system_program::create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: self.payer.to_account_info(),
+                    to: mint.clone(),
+                },
+            ),
+            self.rent.minimum_balance(space),
+            space as u64,
+            &token_program,
+        )?;
+
+        if extensions.non_transferable {
+            let ix = spl_token_2022::instruction::initialize_non_transferable_mint(
+                &token_program,
+                &mint.key(),
+            )?;
+            anchor_lang::solana_program::program::invoke(&ix, &[mint.clone()])?;
+        }
+
+        if extensions.permanent_delegate {
+            let ix = spl_token_2022::instruction::initialize_permanent_delegate(
+                &token_program,
+                &mint.key(),
+                &authority,
+            )?;
+            anchor_lang::solana_program::program::invoke(&ix, &[mint.clone()])?;
+        }
+
+        if extensions.metadata_pointer {
+            initialize_metadata_pointer(
+                self.token_program.to_account_info(),
+                mint.clone(),
+                Some(authority),
+                Some(self.metadata.key()),
+            )?;
+        }
+
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_2022::InitializeMint2 { mint },
+            ),
+            0,
+            &authority,
+            Some(&authority),
+        )
+    }
 }
@@ -0,0 +1,66 @@
+use crate::{
+    error::ProgramError,
+    state::{Promo, Sponsorship, MAX_SPONSORED_PROMOS},
+    utils::{create_memo, transfer_sol},
+    CreateSponsorship, TransferSol,
+};
+use anchor_lang::prelude::*;
+
+impl<'info> CreateSponsorship<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        mints: Vec<Pubkey>,
+        reimbursement_lamports: u64,
+        lamports: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        msg!("Create sponsorship");
+
+        if mints.len() > MAX_SPONSORED_PROMOS {
+            return Err(ProgramError::InvalidSponsorship.into());
+        }
+
+        // The promo of each mint is passed in the remaining accounts, in the same order.
+        if remaining_accounts.len() != mints.len() {
+            return Err(ProgramError::InvalidSponsoredPromos.into());
+        }
+        for (i, (mint, promo_info)) in mints.iter().zip(remaining_accounts).enumerate() {
+            let promo = Account::<Promo>::try_from(promo_info)?;
+            if promo.mint != *mint
+                || promo.campaign != self.campaign.key()
+                || mints[..i].contains(mint)
+            {
+                return Err(ProgramError::InvalidSponsoredPromos.into());
+            }
+        }
+
+        *self.sponsorship = Sponsorship {
+            sponsor: self.sponsor.key(),
+            campaign: self.campaign.key(),
+            mints,
+            reimbursement_lamports,
+            ..Default::default()
+        };
+
+        if lamports > 0 {
+            transfer_sol(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    TransferSol {
+                        payer: self.sponsor.to_account_info(),
+                        to: self.sponsorship.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.sponsor.to_account_info(), self.payer.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
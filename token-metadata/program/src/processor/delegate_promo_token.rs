@@ -6,13 +6,13 @@ impl<'info> DelegatePromoToken<'info> {
     pub fn process(&mut self, memo: Option<String>) -> Result<()> {
         msg!("Delegate promo token");
 
-        let delegate_ctx = anchor_spl::token::Approve {
+        let delegate_ctx = anchor_spl::token_interface::Approve {
             to: self.token_account.to_account_info(),
             delegate: self.device_owner.to_account_info(),
             authority: self.token_owner.to_account_info(),
         };
 
-        anchor_spl::token::approve(
+        anchor_spl::token_interface::approve(
             CpiContext::new(self.token_program.to_account_info(), delegate_ctx),
             1,
         )?;
@@ -22,7 +22,7 @@ impl<'info> DelegatePromoToken<'info> {
                 self.payer.to_account_info(),
                 self.token_owner.to_account_info(),
             ];
-            create_memo(memo.to_string(), account_infos)?;
+            create_memo(memo, account_infos)?;
         }
 
         Ok(())
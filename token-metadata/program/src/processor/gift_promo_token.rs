@@ -0,0 +1,100 @@
+use crate::{
+    error::ProgramError,
+    state::GiftPolicy,
+    utils::{create_memo, is_non_transferable},
+    GiftPromoToken,
+};
+use anchor_lang::prelude::*;
+
+impl<'info> GiftPromoToken<'info> {
+    pub fn process(&mut self, memo: Option<String>, authority_seeds: [&[u8]; 2]) -> Result<()> {
+        msg!("Gift promo token");
+
+        if self.sender.key() == self.recipient.key() {
+            return Err(ProgramError::SelfGift.into());
+        }
+
+        match self.promo.gift_policy {
+            GiftPolicy::Disallowed => return Err(ProgramError::GiftingNotAllowed.into()),
+            // Gift accounts are created on the payer's lamports, so they're only accepted when
+            // they record the gift.
+            GiftPolicy::Allowed => {
+                if self.gift.is_some() || self.sender_gift.is_some() {
+                    return Err(ProgramError::InvalidGiftAccounts.into());
+                }
+            }
+            GiftPolicy::AllowedOnce => {
+                // The sender's gift account only exists if they've received a gift of the promo.
+                let sender_gift = self
+                    .sender_gift
+                    .as_ref()
+                    .ok_or(ProgramError::InvalidGiftAccounts)?;
+                if !sender_gift.data_is_empty() {
+                    return Err(ProgramError::AlreadyGifted.into());
+                }
+                let gift = self
+                    .gift
+                    .as_mut()
+                    .ok_or(ProgramError::InvalidGiftAccounts)?;
+                gift.mint = self.mint.key();
+                gift.sender = self.sender.key();
+                gift.recipient = self.recipient.key();
+                gift.gifted_at = Clock::get()?.unix_timestamp;
+            }
+        }
+
+        if is_non_transferable(&self.mint.to_account_info())? {
+            // Non-transferable tokens can't be moved by their owner, so the sender's token is
+            // burned and the program authority mints one to the recipient instead.
+            let burn_ctx = anchor_spl::token_interface::Burn {
+                mint: self.mint.to_account_info(),
+                from: self.sender_token_account.to_account_info(),
+                authority: self.sender.to_account_info(),
+            };
+            anchor_spl::token_interface::burn(
+                CpiContext::new(self.token_program.to_account_info(), burn_ctx),
+                1,
+            )?;
+
+            let mint_to_ctx = anchor_spl::token_interface::MintTo {
+                mint: self.mint.to_account_info(),
+                to: self.recipient_token_account.to_account_info(),
+                authority: self.authority.to_account_info(),
+            };
+            anchor_spl::token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    mint_to_ctx,
+                    &[&authority_seeds],
+                ),
+                1,
+            )?;
+        } else {
+            let transfer_ctx = anchor_spl::token_interface::TransferChecked {
+                from: self.sender_token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.recipient_token_account.to_account_info(),
+                authority: self.sender.to_account_info(),
+            };
+
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new(self.token_program.to_account_info(), transfer_ctx),
+                1,
+                self.mint.decimals,
+            )?;
+        }
+
+        let gift_memo = format!(
+            r#"{{"gift":{{"sender":"{}","recipient":"{}"}}}}"#,
+            self.sender.key(),
+            self.recipient.key()
+        );
+        create_memo(gift_memo, vec![self.sender.to_account_info()])?;
+
+        if let Some(memo) = memo {
+            create_memo(memo, vec![self.sender.to_account_info()])?;
+        }
+
+        Ok(())
+    }
+}
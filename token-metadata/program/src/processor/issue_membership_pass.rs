@@ -0,0 +1,59 @@
+use crate::{
+    state::MembershipPass,
+    utils::{create_memo, mint_new_edition_from_master_edition_via_token},
+    IssueMembershipPass,
+};
+use anchor_lang::prelude::*;
+
+impl<'info> IssueMembershipPass<'info> {
+    pub fn process(&mut self, memo: Option<String>, authority_seeds: [&[u8]; 2]) -> Result<()> {
+        msg!("Issue membership pass");
+
+        let edition = self.membership.pass_count + 1;
+        let issued_at = Clock::get()?.unix_timestamp;
+
+        // Token metadata requires the print's mint to have a supply of one before the edition
+        // is printed, after which the mint and freeze authorities move to the edition.
+        let mint_to_ctx = anchor_spl::token::MintTo {
+            mint: self.pass_mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        anchor_spl::token::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                mint_to_ctx,
+                &[&authority_seeds],
+            ),
+            1,
+        )?;
+
+        mint_new_edition_from_master_edition_via_token(
+            CpiContext::new_with_signer(
+                self.metadata_program.to_account_info(),
+                self.clone(),
+                &[&authority_seeds],
+            ),
+            edition,
+        )?;
+
+        **self.membership_pass = MembershipPass {
+            membership: self.membership.key(),
+            mint: self.pass_mint.key(),
+            edition,
+            issued_to: self.token_owner.key(),
+            issued_at,
+            expires_at: issued_at + self.membership.duration,
+            ..Default::default()
+        };
+        self.membership.pass_count = edition;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.owner.to_account_info(), self.payer.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
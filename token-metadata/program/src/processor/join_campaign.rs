@@ -0,0 +1,23 @@
+use crate::{state::CampaignMember, utils::create_memo, JoinCampaign};
+use anchor_lang::prelude::*;
+
+impl<'info> JoinCampaign<'info> {
+    pub fn process(&mut self, memo: Option<String>) -> Result<()> {
+        msg!("Join campaign");
+
+        *self.campaign_member = CampaignMember {
+            campaign: self.campaign.key(),
+            merchant: self.merchant.key(),
+        };
+
+        if let Some(memo) = memo {
+            let account_infos = vec![
+                self.campaign_owner.to_account_info(),
+                self.owner.to_account_info(),
+            ];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{
+    error::ProgramError,
+    state::{Campaign, CampaignLocation, Promo},
+    utils::{create_memo, transfer_sol},
+    MigrateAccount, TransferSol,
+};
+use anchor_lang::{prelude::*, Discriminator};
+
+impl<'info> MigrateAccount<'info> {
+    pub fn process(&mut self, memo: Option<String>) -> Result<()> {
+        msg!("Migrate account");
+
+        let account_info = self.account.to_account_info();
+        let len = {
+            let data = account_info.try_borrow_data()?;
+            [
+                (Promo::DISCRIMINATOR, Promo::LEN),
+                (Campaign::DISCRIMINATOR, Campaign::LEN),
+                (CampaignLocation::DISCRIMINATOR, CampaignLocation::LEN),
+            ]
+            .into_iter()
+            .find(|(discriminator, _)| data.starts_with(discriminator))
+            .map(|(_, len)| len)
+            .ok_or(ProgramError::InvalidMigrationAccount)?
+        };
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.payer.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        if account_info.data_len() >= len {
+            return Ok(());
+        }
+
+        let lamports = Rent::get()?
+            .minimum_balance(len)
+            .saturating_sub(account_info.lamports());
+        if lamports > 0 {
+            transfer_sol(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    TransferSol {
+                        payer: self.payer.to_account_info(),
+                        to: account_info.clone(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+
+        // Fields appended since the account was created are zeroed, which deserializes as their
+        // defaults: `None`, `false`, zero counts and the first variant of enums.
+        account_info.realloc(len, true)?;
+
+        Ok(())
+    }
+}
@@ -1,38 +1,75 @@
-use crate::utils::create_memo;
+use crate::utils::{check_allowlist, check_mint_limits, create_memo, set_close_authority};
 use crate::{error::ProgramError, MintPromoToken};
 use anchor_lang::prelude::*;
 
 impl<'info> MintPromoToken<'info> {
-    pub fn process(&mut self, memo: Option<String>, authority_seeds: [&[u8]; 2]) -> Result<()> {
+    pub fn process(
+        &mut self,
+        proof: Vec<[u8; 32]>,
+        referrer: Option<Pubkey>,
+        memo: Option<String>,
+        authority_seeds: [&[u8]; 2],
+    ) -> Result<()> {
         msg!("Mint promo token");
 
-        // Check to see if mint_count is still below max_mint.
-        if let Some(max_mint) = self.promo.max_mint {
-            if self.promo.mint_count >= max_mint {
-                return Err(ProgramError::MaxMintExceeded.into());
+        // Promos that require approval are only minted by approving a mint request.
+        if self.promo.requires_approval {
+            return Err(ProgramError::MintApprovalRequired.into());
+        }
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        check_mint_limits(
+            &self.promo,
+            &self.campaign,
+            &self.campaign_location,
+            &mut self.location_count,
+            &mut self.device_count,
+            unix_timestamp,
+        )?;
+
+        check_allowlist(&self.promo, &proof, &self.token_owner.key())?;
+
+        // Record who referred the token owner, keeping the first referrer if they were already
+        // referred to the promo.
+        if let Some(referrer) = referrer {
+            if self.promo.referral.is_none() {
+                return Err(ProgramError::ReferralsNotEnabled.into());
             }
+            if referrer == self.token_owner.key() {
+                return Err(ProgramError::SelfReferral.into());
+            }
+            let referral = self
+                .referral
+                .as_mut()
+                .ok_or(ProgramError::InvalidReferralAccounts)?;
+            if referral.referred == Pubkey::default() {
+                referral.mint = self.mint.key();
+                referral.referrer = referrer;
+                referral.referred = self.token_owner.key();
+            }
+        } else if self.referral.is_some() {
+            // The referral account is created on the payer's lamports, so it's only accepted
+            // when there is a referrer to record in it.
+            return Err(ProgramError::InvalidReferralAccounts.into());
         }
 
         // Set the close authority to the program so it can close token
-        // accounts when it burns the last token in them.
-        // let set_authority_ctx = anchor_spl::token::SetAuthority {
-        //     current_authority: self.token_owner.to_account_info(),
-        //     account_or_mint: self.token_account.to_account_info(),
-        // };
-
-        // anchor_spl::token::set_authority(
-        //     CpiContext::new(self.token_program.to_account_info(), set_authority_ctx),
-        //     anchor_spl::token::spl_token::instruction::AuthorityType::CloseAccount,
-        //     Some(self.authority.key()),
-        // )?;
-
-        let mint_to_ctx = anchor_spl::token::MintTo {
+        // accounts when it burns the last token in them. Only needed the first
+        // time a token is minted into the account.
+        set_close_authority(
+            self.token_program.to_account_info(),
+            &self.token_account,
+            self.token_owner.to_account_info(),
+            &self.authority.key(),
+        )?;
+
+        let mint_to_ctx = anchor_spl::token_interface::MintTo {
             mint: self.mint.to_account_info(),
             to: self.token_account.to_account_info(),
             authority: self.authority.to_account_info(),
         };
 
-        anchor_spl::token::mint_to(
+        anchor_spl::token_interface::mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 mint_to_ctx,
@@ -46,10 +83,13 @@ impl<'info> MintPromoToken<'info> {
                 self.token_owner.to_account_info(),
                 self.device_owner.to_account_info(),
             ];
-            create_memo(memo.to_string(), account_infos)?;
+            create_memo(memo, account_infos)?;
         }
 
         self.promo.mint_count += 1;
+        self.campaign.mint_count += 1;
+        self.location_count.mint_count += 1;
+        self.device_count.mint_count += 1;
 
         Ok(())
     }
@@ -0,0 +1,71 @@
+use crate::utils::{check_allowlist, check_mint_limits, create_memo, is_non_transferable};
+use crate::{error::ProgramError, MintPromoTokenToVault};
+use anchor_lang::prelude::*;
+
+impl<'info> MintPromoTokenToVault<'info> {
+    pub fn process(
+        &mut self,
+        identity_hash: [u8; 32],
+        proof: Vec<[u8; 32]>,
+        memo: Option<String>,
+        authority_seeds: [&[u8]; 2],
+    ) -> Result<()> {
+        msg!("Mint promo token to vault");
+
+        // Promos that require approval are only minted by approving a mint request.
+        if self.promo.requires_approval {
+            return Err(ProgramError::MintApprovalRequired.into());
+        }
+
+        // Claims transfer tokens out of the vault, so tokens that can't be transferred would be
+        // stuck there.
+        if is_non_transferable(&self.mint.to_account_info())? {
+            return Err(ProgramError::NonTransferableVault.into());
+        }
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        check_mint_limits(
+            &self.promo,
+            &self.campaign,
+            &self.campaign_location,
+            &mut self.location_count,
+            &mut self.device_count,
+            unix_timestamp,
+        )?;
+
+        check_allowlist(&self.promo, &proof, &self.customer_vault.key())?;
+
+        // Only set the first time a token is minted to the vault.
+        if self.customer_vault.created_at == 0 {
+            self.customer_vault.identity_hash = identity_hash;
+            self.customer_vault.created_at = unix_timestamp;
+        }
+
+        let mint_to_ctx = anchor_spl::token_interface::MintTo {
+            mint: self.mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                mint_to_ctx,
+                &[&authority_seeds],
+            ),
+            1,
+        )?;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.device_owner.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        self.promo.mint_count += 1;
+        self.campaign.mint_count += 1;
+        self.location_count.mint_count += 1;
+        self.device_count.mint_count += 1;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::utils::{check_burn_limits, create_memo, pay_burn_fee, SponsorshipPayee};
+use crate::{error::ProgramError, RedeemPromoToken};
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+
+impl<'info> RedeemPromoToken<'info> {
+    pub fn process(&mut self, memo: Option<String>, authority_seeds: [&[u8]; 2]) -> Result<()> {
+        msg!("Redeem promo token");
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        check_burn_limits(
+            &self.promo,
+            &self.campaign,
+            &self.campaign_location,
+            &mut self.location_count,
+            &mut self.device_count,
+            unix_timestamp,
+        )?;
+
+        let payee = match (&self.payee, &self.payee_location, &self.payee_merchant) {
+            (Some(owner), Some(location), Some(merchant)) => Some(SponsorshipPayee {
+                location,
+                merchant,
+                owner,
+            }),
+            (None, None, None) => None,
+            _ => return Err(ProgramError::InvalidSponsorshipAccounts.into()),
+        };
+        pay_burn_fee(
+            self.admin_settings.burn_promo_token_lamports,
+            &self.mint.key(),
+            &self.device.location,
+            self.campaign.to_account_info(),
+            self.platform.to_account_info(),
+            self.sponsorship.as_deref_mut(),
+            payee,
+        )?;
+
+        // token owner burns directly, no delegation required
+        let burn_ctx = anchor_spl::token_interface::Burn {
+            mint: self.mint.to_account_info(),
+            from: self.token_account.to_account_info(),
+            authority: self.token_owner.to_account_info(),
+        };
+
+        anchor_spl::token_interface::burn(
+            CpiContext::new(self.token_program.to_account_info(), burn_ctx),
+            1,
+        )?;
+
+        // Close the token account once the last token has been burned and return the rent to
+        // the payer, who paid it when the token was minted.
+        self.token_account.reload()?;
+        if self.token_account.amount == 0
+            && self.token_account.close_authority == COption::Some(self.authority.key())
+        {
+            let close_ctx = anchor_spl::token_interface::CloseAccount {
+                account: self.token_account.to_account_info(),
+                destination: self.payer.to_account_info(),
+                authority: self.authority.to_account_info(),
+            };
+            anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                close_ctx,
+                &[&authority_seeds],
+            ))?;
+        }
+
+        if let Some(memo) = memo {
+            let account_infos = vec![
+                self.token_owner.to_account_info(),
+                self.device_owner.to_account_info(),
+            ];
+            create_memo(memo, account_infos)?;
+        }
+
+        self.promo.burn_count += 1;
+        self.campaign.burn_count += 1;
+        self.location_count.burn_count += 1;
+        self.device_count.burn_count += 1;
+
+        Ok(())
+    }
+}
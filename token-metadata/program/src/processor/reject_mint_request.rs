@@ -0,0 +1,33 @@
+use crate::utils::create_memo;
+use crate::RejectMintRequest;
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+
+impl<'info> RejectMintRequest<'info> {
+    pub fn process(&mut self, memo: Option<String>, authority_seeds: [&[u8]; 2]) -> Result<()> {
+        msg!("Reject mint request");
+
+        // Close the token account created with the request if nothing else has been minted
+        // into it, returning the rent to the payer of the request.
+        if self.token_account.amount == 0
+            && self.token_account.close_authority == COption::Some(self.authority.key())
+        {
+            let close_ctx = anchor_spl::token_interface::CloseAccount {
+                account: self.token_account.to_account_info(),
+                destination: self.request_payer.to_account_info(),
+                authority: self.authority.to_account_info(),
+            };
+            anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                close_ctx,
+                &[&authority_seeds],
+            ))?;
+        }
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.device_owner.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
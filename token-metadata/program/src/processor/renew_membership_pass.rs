@@ -0,0 +1,30 @@
+use crate::{error::ProgramError, utils::create_memo, RenewMembershipPass};
+use anchor_lang::prelude::*;
+
+impl<'info> RenewMembershipPass<'info> {
+    pub fn process(&mut self, periods: u16, memo: Option<String>) -> Result<()> {
+        msg!("Renew membership pass");
+
+        if periods == 0 {
+            return Err(ProgramError::InvalidMembershipDuration.into());
+        }
+
+        // Renewals of an expired pass start from now rather than the old expiry.
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+        let start = self.membership_pass.expires_at.max(unix_timestamp);
+        self.membership_pass.expires_at = self
+            .membership
+            .duration
+            .checked_mul(periods as i64)
+            .and_then(|extension| start.checked_add(extension))
+            .ok_or(ProgramError::InvalidMembershipDuration)?;
+        self.membership_pass.renewals += 1;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.owner.to_account_info(), self.payer.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use crate::utils::{create_memo, set_close_authority};
+use crate::{error::ProgramError, RequestPromoMint};
+use anchor_lang::prelude::*;
+
+impl<'info> RequestPromoMint<'info> {
+    pub fn process(&mut self, memo: Option<String>) -> Result<()> {
+        msg!("Request promo mint");
+
+        if !self.promo.requires_approval {
+            return Err(ProgramError::MintApprovalNotRequired.into());
+        }
+
+        // Set the close authority to the program now, while the token owner is signing, so the
+        // token can be minted on approval and the account closed on rejection.
+        set_close_authority(
+            self.token_program.to_account_info(),
+            &self.token_account,
+            self.token_owner.to_account_info(),
+            &self.authority.key(),
+        )?;
+
+        self.mint_request.mint = self.mint.key();
+        self.mint_request.token_owner = self.token_owner.key();
+        self.mint_request.campaign = self.campaign.key();
+        self.mint_request.payer = self.payer.key();
+        self.mint_request.requested_at = Clock::get()?.unix_timestamp;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.token_owner.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{state::DailyLimits, utils::create_memo, SetCampaignLocationLimits};
+use anchor_lang::prelude::*;
+
+impl<'info> SetCampaignLocationLimits<'info> {
+    pub fn process(
+        &mut self,
+        location_limits: DailyLimits,
+        device_limits: DailyLimits,
+        memo: Option<String>,
+    ) -> Result<()> {
+        msg!("Set campaign location limits");
+
+        self.campaign_location.location_limits = location_limits;
+        self.campaign_location.device_limits = device_limits;
+
+        if let Some(memo) = memo {
+            let account_infos = vec![self.owner.to_account_info(), self.payer.to_account_info()];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+}
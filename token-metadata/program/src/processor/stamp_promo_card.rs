@@ -0,0 +1,83 @@
+use crate::utils::{check_mint_limits, create_memo, set_close_authority};
+use crate::{error::ProgramError, StampPromoCard};
+use anchor_lang::prelude::*;
+
+impl<'info> StampPromoCard<'info> {
+    pub fn process(&mut self, memo: Option<String>, authority_seeds: [&[u8]; 2]) -> Result<()> {
+        msg!("Stamp promo card");
+
+        let stamp_threshold = self
+            .promo
+            .stamp_threshold
+            .ok_or(ProgramError::StampCardNotEnabled)?;
+
+        // Stamps earn a mint of the promo, which would bypass approval.
+        if self.promo.requires_approval {
+            return Err(ProgramError::MintApprovalRequired.into());
+        }
+
+        let unix_timestamp = Clock::get()?.unix_timestamp;
+
+        // Set up the card the first time it's stamped.
+        if self.stamp_card.owner == Pubkey::default() {
+            self.stamp_card.mint = self.mint.key();
+            self.stamp_card.owner = self.token_owner.key();
+        }
+        if self.stamp_card.stamp(stamp_threshold, unix_timestamp) {
+            self.mint_reward(unix_timestamp, authority_seeds)?;
+        }
+
+        if let Some(memo) = memo {
+            let account_infos = vec![
+                self.token_owner.to_account_info(),
+                self.device_owner.to_account_info(),
+            ];
+            create_memo(memo, account_infos)?;
+        }
+
+        Ok(())
+    }
+
+    /// Mints a promo token to the token owner, with the same checks as minting.
+    fn mint_reward(&mut self, unix_timestamp: i64, authority_seeds: [&[u8]; 2]) -> Result<()> {
+        check_mint_limits(
+            &self.promo,
+            &self.campaign,
+            &self.campaign_location,
+            &mut self.location_count,
+            &mut self.device_count,
+            unix_timestamp,
+        )?;
+
+        // Set the close authority to the program so it can close the token account when it
+        // burns the last token in it, as when minting.
+        set_close_authority(
+            self.token_program.to_account_info(),
+            &self.token_account,
+            self.token_owner.to_account_info(),
+            &self.authority.key(),
+        )?;
+
+        let mint_to_ctx = anchor_spl::token_interface::MintTo {
+            mint: self.mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                mint_to_ctx,
+                &[&authority_seeds],
+            ),
+            1,
+        )?;
+
+        self.promo.mint_count += 1;
+        self.campaign.mint_count += 1;
+        self.location_count.mint_count += 1;
+        self.device_count.mint_count += 1;
+
+        Ok(())
+    }
+}
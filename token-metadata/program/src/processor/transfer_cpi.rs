@@ -1,14 +1,32 @@
-use crate::{utils::transfer_sol, utils::CAMPAIGN_PREFIX, TransferCpi, TransferSol};
+use crate::{
+    error::ProgramError,
+    utils::{
+        find_campaign_address, find_campaign_address_v1, name_seed, transfer_sol, CAMPAIGN_PREFIX,
+    },
+    TransferCpi, TransferSol,
+};
 use anchor_lang::prelude::*;
 
 impl<'info> TransferCpi<'info> {
-    pub fn process(&mut self, lamports: u64, nonce: u8) -> Result<()> {
+    pub fn process(&mut self, lamports: u64) -> Result<()> {
         msg!("Transfer cpi");
         let merchant = self.merchant.key();
+        let campaign = self.campaign.key();
+        let name = &self.campaign.name;
+
+        // Campaigns created before names were hashed sign with the raw name as the seed.
+        let hashed_name = name_seed(name);
+        let (name_seed, nonce): (&[u8], u8) = match find_campaign_address(&merchant, name) {
+            (address, bump) if address == campaign => (&hashed_name, bump),
+            _ => match find_campaign_address_v1(&merchant, name) {
+                Some((address, bump)) if address == campaign => (name.as_bytes(), bump),
+                _ => return Err(ProgramError::InvalidCampaignAddress.into()),
+            },
+        };
         let seeds = [
             CAMPAIGN_PREFIX.as_bytes(),
             merchant.as_ref(),
-            self.campaign.name.as_bytes(),
+            name_seed,
             &[nonce],
         ];
 
@@ -1,4 +1,8 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::program_pack::Pack};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::ExtensionType,
+    state::{Account as AccountState, Mint as MintState},
+};
 use mpl_token_metadata::state::{
     Collection as CollectionMpl, Creator as CreatorMpl, DataV2 as DataV2Mpl,
     UseMethod as UseMethodMpl, Uses as UsesMpl,
@@ -16,14 +20,20 @@ pub struct AdminSettings {
     pub platform: Pubkey,
     pub create_promo_lamports: u64,
     pub burn_promo_token_lamports: u64,
+    /// Signs claims from customer vaults once the customer has verified their phone or email
+    pub vault_authority: Pubkey,
 }
 
-// Add extra space here when deployed to allow for additional settings
+// Add extra space here when deployed to allow for additional settings. Deployed accounts are
+// 104 bytes and create_admin_settings requires the same space to rewrite them, so new settings
+// have to come out of the reserved bytes.
 impl AdminSettings {
     pub const LEN: usize = 8
-    + 32    // platform,
-    + 32    // create_promo_lamports,
-    + 32; // burn_promo_token_lamports
+    + 32    // platform
+    + 8     // create_promo_lamports
+    + 8     // burn_promo_token_lamports
+    + 32    // vault_authority
+    + 16; // reserved
 }
 
 //==============================
@@ -102,6 +112,8 @@ impl Device {
 // Campaign
 //==============================
 
+// Counts and limits apply across all of the promos in the campaign, in addition to the limits
+// on each promo.
 #[account]
 #[derive(Default, Debug)]
 pub struct Campaign {
@@ -109,25 +121,153 @@ pub struct Campaign {
     pub name: String,
     pub uri: String,
     pub active: bool,
+    pub mint_count: u32,
+    pub burn_count: u32,
+    pub max_mint: Option<u32>,
+    pub max_burn: Option<u32>,
 }
 
 impl Campaign {
-    pub const LEN: usize = 8 + 32 + MAX_NAME_LENGTH + MAX_URI_LENGTH + 1;
+    pub const LEN: usize = 8
+    + 32                // merchant
+    + MAX_NAME_LENGTH   // name
+    + MAX_URI_LENGTH    // uri
+    + 1                 // active
+    + 4                 // mint_count
+    + 4                 // burn_count
+    + 1 + 4             // max_mint
+    + 1 + 4; // max_burn
 }
 
 //==============================
 // CampaignLocation
 //==============================
 
+// Location limits apply across all of the devices at the location and device limits to each
+// device at the location.
 #[account]
 #[derive(Default, Debug)]
 pub struct CampaignLocation {
     pub campaign: Pubkey,
     pub location: Pubkey,
+    pub location_limits: DailyLimits,
+    pub device_limits: DailyLimits,
 }
 
 impl CampaignLocation {
-    pub const LEN: usize = 8 + 32 + 32;
+    pub const LEN: usize = 8
+    + 32                // campaign
+    + 32                // location
+    + DailyLimits::LEN  // location_limits
+    + DailyLimits::LEN; // device_limits
+}
+
+//==============================
+// CampaignMember
+//==============================
+
+// address is pda of the campaign and the participating merchant. Allows a merchant other than
+// the one that created the campaign to link their locations to it, so the campaign's promos can
+// be redeemed across a coalition of merchants, e.g. a business district or mall association.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct CampaignMember {
+    pub campaign: Pubkey,
+    pub merchant: Pubkey,
+}
+
+impl CampaignMember {
+    pub const LEN: usize = 8
+    + 32        // campaign
+    + 32; // merchant
+}
+
+//==============================
+// Sponsorship
+//==============================
+
+pub const MAX_SPONSORED_PROMOS: usize = 8;
+
+// address is pda of the campaign and the sponsor, e.g. a brand funding promos redeemed at
+// independent retailers. The lamports held by the account above its rent exemption pay the
+// platform burn fee and the reimbursement to the owner of the merchant of the redeeming location
+// for each redemption of the sponsored promos, instead of the burn fee being paid from the
+// campaign. Sponsors can add funds at any time with a transfer to the account, and get back what
+// is left by closing it.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct Sponsorship {
+    pub sponsor: Pubkey,
+    pub campaign: Pubkey,
+    /// Mints of the sponsored promos, all of the campaign's promos if empty
+    pub mints: Vec<Pubkey>,
+    /// Paid to the owner of the merchant of the redeeming location for each redemption
+    pub reimbursement_lamports: u64,
+    pub redemptions: u32,
+    /// Burn fees and reimbursements paid from the sponsorship
+    pub spent_lamports: u64,
+}
+
+impl Sponsorship {
+    pub const LEN: usize = 8
+    + 32        // sponsor
+    + 32        // campaign
+    + 4 + MAX_SPONSORED_PROMOS * 32 // mints
+    + 8         // reimbursement_lamports
+    + 4         // redemptions
+    + 8; // spent_lamports
+
+    /// Returns whether the sponsorship pays for redemptions of the promo with the given mint.
+    pub fn covers(&self, mint: &Pubkey) -> bool {
+        self.mints.is_empty() || self.mints.contains(mint)
+    }
+}
+
+//==============================
+// DailyLimits
+//==============================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, PartialEq, Debug, Clone, Copy)]
+pub struct DailyLimits {
+    /// Max number of tokens minted per day
+    pub max_mint: Option<u32>,
+    /// Max number of tokens burned per day
+    pub max_burn: Option<u32>,
+}
+
+impl DailyLimits {
+    pub const LEN: usize = 1 + 4 + 1 + 4;
+}
+
+//==============================
+// DailyCount
+//==============================
+
+// Counts of tokens minted and burned at a campaign location, or by a device at a campaign
+// location, during the current UTC day. Counts are reset on the first mint or burn of each day.
+#[account]
+#[derive(Default, Debug)]
+pub struct DailyCount {
+    pub day: i64,
+    pub mint_count: u32,
+    pub burn_count: u32,
+}
+
+impl DailyCount {
+    pub const LEN: usize = 8
+    + 8         // day
+    + 4         // mint_count
+    + 4; // burn_count
+
+    /// Resets the counts if the timestamp falls on a later day than the current counts.
+    pub fn roll(&mut self, unix_timestamp: i64) {
+        let day = unix_timestamp.div_euclid(86_400);
+        if day != self.day {
+            self.day = day;
+            self.mint_count = 0;
+            self.burn_count = 0;
+        }
+    }
 }
 
 //==============================
@@ -137,7 +277,7 @@ impl CampaignLocation {
 // Keeping track of mints and burns here for the convenience of not having to
 // query and aggregate all transactions.
 #[account]
-#[derive(PartialEq, Debug, Copy)]
+#[derive(Default, PartialEq, Debug)]
 pub struct Promo {
     pub campaign: Pubkey,
     pub mint: Pubkey,
@@ -147,6 +287,14 @@ pub struct Promo {
     pub max_mint: Option<u32>,
     pub max_burn: Option<u32>,
     pub active: bool,
+    pub schedule: Option<PromoSchedule>,
+    pub allowlist: Option<[u8; 32]>,
+    pub referral: Option<ReferralReward>,
+    pub stamp_threshold: Option<u32>,
+    pub terms: Option<PromoTerms>,
+    /// Customers request mints, which a campaign device approves or rejects
+    pub requires_approval: bool,
+    pub gift_policy: GiftPolicy,
 }
 
 impl Promo {
@@ -157,8 +305,486 @@ impl Promo {
     + 4         // mint_count
     + 4         // burn_count
     + 1 + 4     // max_mint
-    + 1 + 4 // max_burn
-    + 1; //active
+    + 1 + 4     // max_burn
+    + 1         // active
+    + 1 + PromoSchedule::LEN // schedule
+    + 1 + 32    // allowlist
+    + 1 + ReferralReward::LEN // referral
+    + 1 + 4     // stamp_threshold
+    + 1 + PromoTerms::LEN // terms
+    + 1         // requires_approval
+    + 1; // gift_policy
+}
+
+//==============================
+// CustomerVault
+//==============================
+
+// address is pda of a salted hash of the customer's phone number or email, so customers without
+// a wallet can receive promo tokens. Tokens are held in token accounts owned by the vault until
+// the customer claims them into a wallet. The salt is kept off chain by the platform.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct CustomerVault {
+    pub identity_hash: [u8; 32],
+    pub created_at: i64,
+    /// Wallet the vault's tokens were last claimed into
+    pub claimed_by: Option<Pubkey>,
+}
+
+impl CustomerVault {
+    pub const LEN: usize = 8
+    + 32        // identity_hash
+    + 8         // created_at
+    + 1 + 32; // claimed_by
+}
+
+//==============================
+// GiftPolicy
+//==============================
+
+// Whether token owners can gift promo tokens to other customers with the `gift_promo_token`
+// instruction. Tokens of non-transferable promos can only change hands through the program,
+// which gifts them by burning the sender's token and minting one to the recipient, so the
+// policy binds. Tokens of other promos can also be moved with a plain token transfer, so the
+// policy only governs the instruction and `AllowedOnce` requires the non-transferable extension.
+#[derive(AnchorSerialize, AnchorDeserialize, Default, PartialEq, Debug, Clone, Copy)]
+pub enum GiftPolicy {
+    #[default]
+    Disallowed,
+    Allowed,
+    /// Each token can be gifted once, so tokens received as a gift, tracked by a [Gift] account,
+    /// can't be gifted again
+    AllowedOnce,
+}
+
+//==============================
+// Gift
+//==============================
+
+// address is pda of the promo mint and the recipient, and records the latest gift they received.
+// Only created for promos with a gift policy of `AllowedOnce`, so its existence means the
+// recipient holds tokens of the promo that were gifted to them and can't gift the promo on.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct Gift {
+    pub mint: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub gifted_at: i64,
+}
+
+impl Gift {
+    pub const LEN: usize = 8
+    + 32        // mint
+    + 32        // sender
+    + 32        // recipient
+    + 8; // gifted_at
+}
+
+//==============================
+// Membership
+//==============================
+
+// address is pda of the mint of the membership's master edition, which is held by the program
+// authority. Each pass issued to a customer is a print of the master edition with its own
+// [MembershipPass].
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct Membership {
+    pub merchant: Pubkey,
+    pub mint: Pubkey,
+    /// Seconds a pass is valid for when issued, and is extended by for each renewal period
+    pub duration: i64,
+    /// Number of passes issued, which is also the edition number of the last pass
+    pub pass_count: u64,
+}
+
+impl Membership {
+    pub const LEN: usize = 8
+    + 32        // merchant
+    + 32        // mint
+    + 8         // duration
+    + 8; // pass_count
+}
+
+//==============================
+// MembershipPass
+//==============================
+
+// address is pda of the mint of the pass. Passes can be transferred, so check ins are made by
+// whoever holds the pass.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct MembershipPass {
+    pub membership: Pubkey,
+    pub mint: Pubkey,
+    pub edition: u64,
+    /// Wallet the pass was issued to
+    pub issued_to: Pubkey,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub renewals: u32,
+    pub check_ins: u32,
+    pub last_check_in_at: i64,
+}
+
+impl MembershipPass {
+    pub const LEN: usize = 8
+    + 32        // membership
+    + 32        // mint
+    + 8         // edition
+    + 32        // issued_to
+    + 8         // issued_at
+    + 8         // expires_at
+    + 4         // renewals
+    + 4         // check_ins
+    + 8; // last_check_in_at
+}
+
+//==============================
+// PromoTerms
+//==============================
+
+pub const MAX_PRODUCT_ID_LENGTH: usize = 32;
+
+// Discount a promo token is worth, so a point of sale can compute it from the promo account
+// rather than the metadata json. Currency amounts are in 100ths of currency.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone)]
+pub enum PromoTerms {
+    /// `get_y_product` free items of a product when buying `buy_x_product` of it
+    BuyXProductGetYFree {
+        product_id: String,
+        buy_x_product: u32,
+        get_y_product: u32,
+    },
+    /// `get_y_percent` percent off an order of at least `buy_x_currency`
+    BuyXCurrencyGetYPercent {
+        buy_x_currency: u64,
+        get_y_percent: u8,
+    },
+    /// `amount_off` off an order of at least `buy_x_currency`
+    AmountOff {
+        buy_x_currency: u64,
+        amount_off: u64,
+    },
+    /// One free item of a product
+    FreeItem { product_id: String },
+}
+
+impl PromoTerms {
+    pub const LEN: usize = 1 // variant
+    + 4 + MAX_PRODUCT_ID_LENGTH // product_id
+    + 4         // buy_x_product
+    + 4; // get_y_product
+
+    pub fn is_valid(&self) -> bool {
+        let valid_product_id =
+            |product_id: &str| !product_id.is_empty() && product_id.len() <= MAX_PRODUCT_ID_LENGTH;
+        match self {
+            Self::BuyXProductGetYFree {
+                product_id,
+                buy_x_product,
+                get_y_product,
+            } => valid_product_id(product_id) && *buy_x_product > 0 && *get_y_product > 0,
+            Self::BuyXCurrencyGetYPercent { get_y_percent, .. } => {
+                (1..=100).contains(get_y_percent)
+            }
+            Self::AmountOff { amount_off, .. } => *amount_off > 0,
+            Self::FreeItem { product_id } => valid_product_id(product_id),
+        }
+    }
+}
+
+//==============================
+// ReferralReward
+//==============================
+
+// Reward promo minted to the customer who referred a token owner when the token owner's promo
+// token is burned. The reward promo must be a different promo in the same campaign.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct ReferralReward {
+    /// Mint of the reward promo
+    pub reward_mint: Pubkey,
+    /// Max number of rewards minted for referrals to the promo
+    pub max_rewards: Option<u32>,
+    /// Number of rewards minted for referrals to the promo
+    pub reward_count: u32,
+}
+
+impl ReferralReward {
+    pub const LEN: usize = 32 // reward_mint
+    + 1 + 4     // max_rewards
+    + 4; // reward_count
+
+    /// Whether a reward can be minted without going over the referral, reward promo or campaign
+    /// caps, and without bypassing the reward promo's approval.
+    pub fn is_available(&self, reward_promo: &Promo, campaign: &Campaign) -> bool {
+        let capped = |count: u32, max: Option<u32>| max.map_or(false, |max| count >= max);
+        !(capped(self.reward_count, self.max_rewards)
+            || capped(reward_promo.mint_count, reward_promo.max_mint)
+            || capped(campaign.mint_count, campaign.max_mint)
+            || !reward_promo.active
+            || reward_promo.requires_approval)
+    }
+}
+
+//==============================
+// Referral
+//==============================
+
+// address is pda of the promo mint and the referred token owner, so a token owner can only be
+// referred to a promo once, by the first referrer recorded when a token is minted to them.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct Referral {
+    pub mint: Pubkey,
+    pub referrer: Pubkey,
+    pub referred: Pubkey,
+    pub rewarded: bool,
+}
+
+impl Referral {
+    pub const LEN: usize = 8
+    + 32        // mint
+    + 32        // referrer
+    + 32        // referred
+    + 1; // rewarded
+}
+
+//==============================
+// StampCard
+//==============================
+
+// address is pda of the promo mint and the customer. A stamp is added by a campaign device at
+// each purchase and a promo token is minted to the customer once `stamp_threshold` stamps have
+// been collected, after which the card starts over.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct StampCard {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    /// Stamps collected towards the next reward
+    pub stamps: u32,
+    /// Number of rewards minted from the card
+    pub rewards: u32,
+    pub last_stamped_at: i64,
+}
+
+impl StampCard {
+    pub const LEN: usize = 8
+    + 32        // mint
+    + 32        // owner
+    + 4         // stamps
+    + 4         // rewards
+    + 8; // last_stamped_at
+
+    /// Adds a stamp, returning whether it completes the card. A completed card counts the reward
+    /// and starts over.
+    pub fn stamp(&mut self, threshold: u32, unix_timestamp: i64) -> bool {
+        self.stamps += 1;
+        self.last_stamped_at = unix_timestamp;
+        if self.stamps < threshold {
+            return false;
+        }
+        self.stamps = 0;
+        self.rewards += 1;
+        true
+    }
+}
+
+//==============================
+// Voucher
+//==============================
+
+// Claim voucher for a promo token signed off chain with the key of a device owner, e.g. printed
+// on a receipt as a QR code, so a customer can claim the token later without the device. The
+// signature is over the serialized voucher and is verified with the Ed25519 program.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Default)]
+pub struct Voucher {
+    /// Promo address
+    pub promo: Pubkey,
+    /// Unique per voucher for the promo, recorded when the voucher is claimed
+    pub nonce: u64,
+    /// Unix timestamp after which the voucher can no longer be claimed
+    pub expiry: i64,
+    /// Only this wallet can claim the voucher, if set
+    pub recipient: Option<Pubkey>,
+}
+
+//==============================
+// VoucherClaim
+//==============================
+
+// address is pda of the promo and the voucher nonce, so each voucher can only be claimed once.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct VoucherClaim {
+    pub promo: Pubkey,
+    pub nonce: u64,
+    pub device: Pubkey,
+    pub claimed_by: Pubkey,
+    pub claimed_at: i64,
+}
+
+impl VoucherClaim {
+    pub const LEN: usize = 8
+    + 32        // promo
+    + 8         // nonce
+    + 32        // device
+    + 32        // claimed_by
+    + 8; // claimed_at
+}
+
+//==============================
+// MintRequest
+//==============================
+
+// address is pda of the promo mint and the customer, so a customer can only have one pending
+// request per promo. Closed when a campaign device approves or rejects the request, returning
+// the rent to the payer of the request.
+#[account]
+#[derive(Default, PartialEq, Debug)]
+pub struct MintRequest {
+    pub mint: Pubkey,
+    pub token_owner: Pubkey,
+    pub campaign: Pubkey,
+    pub payer: Pubkey,
+    pub requested_at: i64,
+}
+
+impl MintRequest {
+    pub const LEN: usize = 8
+    + 32        // mint
+    + 32        // token_owner
+    + 32        // campaign
+    + 32        // payer
+    + 8; // requested_at
+}
+
+//==============================
+// PromoSchedule
+//==============================
+
+pub const MAX_SCHEDULE_WINDOWS: usize = 4;
+
+// Recurring days and times during which a promo can be redeemed, e.g. weekday lunch or
+// Tuesday 3-5pm. Days and windows are evaluated in local time as given by `utc_offset`.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone)]
+pub struct PromoSchedule {
+    /// Days of the week the promo is valid, bit 0 is Sunday through bit 6 Saturday
+    pub days: u8,
+    /// Time of day windows, all day if empty
+    pub windows: Vec<TimeWindow>,
+    /// Offset of local time from UTC in minutes
+    pub utc_offset: i16,
+    /// Whether the schedule also applies to minting
+    pub check_mint: bool,
+}
+
+impl PromoSchedule {
+    pub const LEN: usize = 1   // days
+    + 4 + MAX_SCHEDULE_WINDOWS * TimeWindow::LEN // windows
+    + 2         // utc_offset
+    + 1; // check_mint
+
+    /// Returns whether the schedule includes the given unix timestamp.
+    ///
+    /// Windows that end before they start run past midnight, with the day checked being the
+    /// local day of the timestamp.
+    pub fn includes(&self, unix_timestamp: i64) -> bool {
+        let local = unix_timestamp + self.utc_offset as i64 * 60;
+        // 1970-01-01 was a Thursday
+        let day = (local.div_euclid(86_400) + 4).rem_euclid(7);
+        let minute = (local.rem_euclid(86_400) / 60) as u16;
+
+        if self.days & (1 << day) == 0 {
+            return false;
+        }
+
+        self.windows.is_empty() || self.windows.iter().any(|w| w.includes(minute))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.days & 0b1000_0000 == 0
+            && self.windows.len() <= MAX_SCHEDULE_WINDOWS
+            && self
+                .windows
+                .iter()
+                .all(|w| w.start < 1440 && w.end <= 1440 && w.start != w.end)
+            && (-720..=840).contains(&self.utc_offset)
+    }
+}
+
+// Minutes from local midnight, start inclusive and end exclusive. A window that ends before
+// it starts runs past midnight, and one that starts and ends at the same time is empty, so it
+// isn't valid.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct TimeWindow {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl TimeWindow {
+    pub const LEN: usize = 2 + 2;
+
+    pub fn includes(&self, minute: u16) -> bool {
+        if self.start <= self.end {
+            minute >= self.start && minute < self.end
+        } else {
+            minute >= self.start || minute < self.end
+        }
+    }
+}
+
+//==============================
+// PromoExtensions
+//==============================
+
+// Token-2022 extensions initialized on the promo mint when it is created. Only valid when
+// the promo is created with the Token-2022 program.
+#[derive(AnchorSerialize, AnchorDeserialize, Default, PartialEq, Debug, Clone, Copy)]
+pub struct PromoExtensions {
+    /// Tokens can't be transferred out of the account they were minted into
+    pub non_transferable: bool,
+    /// Program authority is set as permanent delegate so tokens can be clawed back
+    pub permanent_delegate: bool,
+    /// Mint points to its metadata account
+    pub metadata_pointer: bool,
+}
+
+impl PromoExtensions {
+    // The metadata pointer extension was added in spl-token-2022 0.7.0, after the release
+    // compatible with our solana version, so its tlv entry is sized by hand: type (2),
+    // length (2), authority (32) and metadata address (32).
+    pub const METADATA_POINTER_LEN: usize = 2 + 2 + 32 + 32;
+
+    pub fn is_empty(&self) -> bool {
+        !(self.non_transferable || self.permanent_delegate || self.metadata_pointer)
+    }
+
+    /// Size of a Token-2022 mint account with the selected extensions.
+    pub fn mint_len(&self) -> usize {
+        let mut extension_types = vec![];
+        if self.non_transferable {
+            extension_types.push(ExtensionType::NonTransferable);
+        }
+        if self.permanent_delegate {
+            extension_types.push(ExtensionType::PermanentDelegate);
+        }
+
+        if !self.metadata_pointer {
+            return ExtensionType::get_account_len::<MintState>(&extension_types);
+        }
+
+        if extension_types.is_empty() {
+            // base account length plus the account type byte
+            AccountState::LEN + 1 + Self::METADATA_POINTER_LEN
+        } else {
+            ExtensionType::get_account_len::<MintState>(&extension_types)
+                + Self::METADATA_POINTER_LEN
+        }
+    }
 }
 
 //==============================
@@ -265,3 +891,316 @@ impl From<Collection> for CollectionMpl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(windows: Vec<TimeWindow>) -> PromoSchedule {
+        PromoSchedule {
+            days: 0b0111_1111,
+            windows,
+            utc_offset: 0,
+            check_mint: false,
+        }
+    }
+
+    #[test]
+    fn test_schedule_is_valid() {
+        let window = |start, end| TimeWindow { start, end };
+
+        assert!(schedule(vec![]).is_valid());
+        assert!(schedule(vec![window(690, 840), window(1020, 1140)]).is_valid());
+        // runs past midnight
+        assert!(schedule(vec![window(1320, 120)]).is_valid());
+        assert!(schedule(vec![window(0, 1440)]).is_valid());
+
+        // empty windows never match
+        assert!(!schedule(vec![window(600, 600)]).is_valid());
+        assert!(!schedule(vec![window(0, 0)]).is_valid());
+        assert!(!schedule(vec![window(1440, 60)]).is_valid());
+        assert!(!schedule(vec![window(0, 1441)]).is_valid());
+        assert!(!schedule(vec![window(0, 60); MAX_SCHEDULE_WINDOWS + 1]).is_valid());
+    }
+
+    // Seconds since the epoch at the given time of day, `day` days after 1970-01-01, a Thursday.
+    fn timestamp(day: i64, hour: i64, minute: i64) -> i64 {
+        day * 86_400 + hour * 3_600 + minute * 60
+    }
+
+    #[test]
+    fn test_schedule_includes() {
+        const TUESDAY: i64 = 5;
+        const WEDNESDAY: i64 = 6;
+
+        // Tuesday 3-5pm
+        let happy_hour = PromoSchedule {
+            days: 1 << 2,
+            ..schedule(vec![TimeWindow {
+                start: 900,
+                end: 1020,
+            }])
+        };
+        assert!(happy_hour.includes(timestamp(TUESDAY, 15, 0)));
+        assert!(happy_hour.includes(timestamp(TUESDAY, 16, 59)));
+        assert!(!happy_hour.includes(timestamp(TUESDAY, 14, 59)));
+        // end is exclusive
+        assert!(!happy_hour.includes(timestamp(TUESDAY, 17, 0)));
+        assert!(!happy_hour.includes(timestamp(WEDNESDAY, 16, 0)));
+
+        // all day when there are no windows
+        let weekdays = PromoSchedule {
+            days: 0b0011_1110,
+            ..schedule(vec![])
+        };
+        assert!(weekdays.includes(timestamp(0, 0, 0)));
+        assert!(weekdays.includes(timestamp(TUESDAY, 23, 59)));
+        assert!(!weekdays.includes(timestamp(2, 12, 0)));
+        assert!(!weekdays.includes(timestamp(3, 12, 0)));
+    }
+
+    #[test]
+    fn test_schedule_utc_offset() {
+        const TUESDAY: i64 = 5;
+
+        // Tuesday 3-5pm at UTC-5
+        let happy_hour = PromoSchedule {
+            days: 1 << 2,
+            utc_offset: -300,
+            ..schedule(vec![TimeWindow {
+                start: 900,
+                end: 1020,
+            }])
+        };
+        assert!(happy_hour.includes(timestamp(TUESDAY, 20, 0)));
+        assert!(!happy_hour.includes(timestamp(TUESDAY, 15, 0)));
+
+        // the local day decides, 1am UTC on Wednesday is still Tuesday at UTC-5
+        let tuesdays = PromoSchedule {
+            days: 1 << 2,
+            utc_offset: -300,
+            ..schedule(vec![])
+        };
+        assert!(tuesdays.includes(timestamp(TUESDAY + 1, 1, 0)));
+        assert!(!tuesdays.includes(timestamp(TUESDAY, 1, 0)));
+
+        // and 11pm UTC on Monday is already Tuesday at UTC+2
+        let tuesdays = PromoSchedule {
+            utc_offset: 120,
+            ..tuesdays
+        };
+        assert!(tuesdays.includes(timestamp(TUESDAY - 1, 23, 0)));
+        assert!(!tuesdays.includes(timestamp(TUESDAY, 23, 0)));
+    }
+
+    #[test]
+    fn test_schedule_overnight_window() {
+        const FRIDAY: i64 = 1;
+
+        // Friday 10pm-2am
+        let late_night = PromoSchedule {
+            days: 1 << 5,
+            ..schedule(vec![TimeWindow {
+                start: 1320,
+                end: 120,
+            }])
+        };
+        assert!(late_night.includes(timestamp(FRIDAY, 22, 0)));
+        assert!(!late_night.includes(timestamp(FRIDAY, 2, 0)));
+        assert!(!late_night.includes(timestamp(FRIDAY, 12, 0)));
+        // the early morning part counts for the day it falls on
+        assert!(late_night.includes(timestamp(FRIDAY, 1, 59)));
+        assert!(!late_night.includes(timestamp(FRIDAY + 1, 1, 0)));
+    }
+
+    #[test]
+    fn test_daily_count_roll() {
+        let mut count = DailyCount::default();
+
+        count.roll(timestamp(3, 9, 0));
+        assert_eq!(count.day, 3);
+        count.mint_count = 5;
+        count.burn_count = 2;
+
+        // same UTC day
+        count.roll(timestamp(3, 23, 59));
+        assert_eq!((count.day, count.mint_count, count.burn_count), (3, 5, 2));
+
+        count.roll(timestamp(4, 0, 0));
+        assert_eq!((count.day, count.mint_count, count.burn_count), (4, 0, 0));
+
+        // days before the epoch round down
+        count.mint_count = 1;
+        count.roll(-1);
+        assert_eq!((count.day, count.mint_count), (-1, 0));
+    }
+
+    #[test]
+    fn test_stamp_card_stamp() {
+        let mut card = StampCard::default();
+
+        assert!(!card.stamp(3, 100));
+        assert!(!card.stamp(3, 200));
+        assert_eq!(
+            (card.stamps, card.rewards, card.last_stamped_at),
+            (2, 0, 200)
+        );
+
+        assert!(card.stamp(3, 300));
+        assert_eq!(
+            (card.stamps, card.rewards, card.last_stamped_at),
+            (0, 1, 300)
+        );
+
+        // the card starts over after a reward
+        assert!(!card.stamp(3, 400));
+        assert!(!card.stamp(3, 500));
+        assert!(card.stamp(3, 600));
+        assert_eq!((card.stamps, card.rewards), (0, 2));
+
+        // every stamp completes a card with a threshold of one
+        assert!(card.stamp(1, 700));
+        assert_eq!((card.stamps, card.rewards), (0, 3));
+    }
+
+    #[test]
+    fn test_promo_extensions_mint_len() {
+        let extensions = |non_transferable, permanent_delegate, metadata_pointer| PromoExtensions {
+            non_transferable,
+            permanent_delegate,
+            metadata_pointer,
+        };
+
+        // a plain mint, with no account type or padding
+        assert_eq!(extensions(false, false, false).mint_len(), 82);
+        // extensions start after the mint, padded to the length of a token account, and the
+        // account type byte
+        assert_eq!(extensions(true, false, false).mint_len(), 166 + 4);
+        assert_eq!(extensions(false, true, false).mint_len(), 166 + 36);
+        assert_eq!(extensions(true, true, false).mint_len(), 166 + 4 + 36);
+        assert_eq!(extensions(false, false, true).mint_len(), 166 + 68);
+        assert_eq!(extensions(true, false, true).mint_len(), 166 + 4 + 68);
+        assert_eq!(extensions(true, true, true).mint_len(), 166 + 4 + 36 + 68);
+    }
+
+    #[test]
+    fn test_referral_reward_is_available() {
+        let reward = ReferralReward {
+            reward_mint: Pubkey::new_unique(),
+            max_rewards: Some(2),
+            reward_count: 1,
+        };
+        let reward_promo = Promo {
+            active: true,
+            mint_count: 4,
+            max_mint: Some(5),
+            ..Default::default()
+        };
+        let campaign = Campaign {
+            mint_count: 9,
+            max_mint: Some(10),
+            ..Default::default()
+        };
+        assert!(reward.is_available(&reward_promo, &campaign));
+
+        let uncapped = ReferralReward {
+            max_rewards: None,
+            reward_count: u32::MAX,
+            ..reward
+        };
+        assert!(uncapped.is_available(&reward_promo, &campaign));
+
+        let rewarded = ReferralReward {
+            reward_count: 2,
+            ..reward
+        };
+        assert!(!rewarded.is_available(&reward_promo, &campaign));
+
+        let minted_out = Promo {
+            mint_count: 5,
+            ..reward_promo.clone()
+        };
+        assert!(!reward.is_available(&minted_out, &campaign));
+
+        let inactive = Promo {
+            active: false,
+            ..reward_promo.clone()
+        };
+        assert!(!reward.is_available(&inactive, &campaign));
+
+        let requires_approval = Promo {
+            requires_approval: true,
+            ..reward_promo.clone()
+        };
+        assert!(!reward.is_available(&requires_approval, &campaign));
+
+        let campaign_minted_out = Campaign {
+            mint_count: 10,
+            ..campaign
+        };
+        assert!(!reward.is_available(&reward_promo, &campaign_minted_out));
+    }
+
+    #[test]
+    fn test_promo_terms_is_valid() {
+        let product_id = |len: usize| "p".repeat(len);
+        let buy_get = |buy_x_product, get_y_product| PromoTerms::BuyXProductGetYFree {
+            product_id: product_id(8),
+            buy_x_product,
+            get_y_product,
+        };
+        let percent = |get_y_percent| PromoTerms::BuyXCurrencyGetYPercent {
+            buy_x_currency: 0,
+            get_y_percent,
+        };
+
+        assert!(buy_get(2, 1).is_valid());
+        assert!(!buy_get(0, 1).is_valid());
+        assert!(!buy_get(2, 0).is_valid());
+
+        assert!(percent(1).is_valid());
+        assert!(percent(100).is_valid());
+        assert!(!percent(0).is_valid());
+        assert!(!percent(101).is_valid());
+
+        assert!(PromoTerms::AmountOff {
+            buy_x_currency: 0,
+            amount_off: 500,
+        }
+        .is_valid());
+        assert!(!PromoTerms::AmountOff {
+            buy_x_currency: 5000,
+            amount_off: 0,
+        }
+        .is_valid());
+
+        let free_item = |product_id| PromoTerms::FreeItem { product_id };
+        assert!(free_item(product_id(1)).is_valid());
+        assert!(free_item(product_id(MAX_PRODUCT_ID_LENGTH)).is_valid());
+        assert!(!free_item(product_id(0)).is_valid());
+        assert!(!free_item(product_id(MAX_PRODUCT_ID_LENGTH + 1)).is_valid());
+    }
+
+    #[test]
+    fn test_promo_terms_len() {
+        let largest = PromoTerms::BuyXProductGetYFree {
+            product_id: "p".repeat(MAX_PRODUCT_ID_LENGTH),
+            buy_x_product: u32::MAX,
+            get_y_product: u32::MAX,
+        };
+        assert_eq!(largest.try_to_vec().unwrap().len(), PromoTerms::LEN);
+
+        let amount_off = PromoTerms::AmountOff {
+            buy_x_currency: u64::MAX,
+            amount_off: u64::MAX,
+        };
+        assert!(amount_off.try_to_vec().unwrap().len() <= PromoTerms::LEN);
+    }
+
+    #[test]
+    fn test_admin_settings_len() {
+        // Deployed admin settings accounts are 104 bytes.
+        assert_eq!(AdminSettings::LEN, 104);
+        assert!(AdminSettings::default().try_to_vec().unwrap().len() + 8 <= AdminSettings::LEN);
+    }
+}
@@ -1,9 +1,36 @@
 use std::str::FromStr;
 
-use crate::{CreateMetaData, CreateNonFungible, TransferSol};
-use anchor_lang::prelude::*;
-use anchor_spl::associated_token::get_associated_token_address;
-use mpl_token_metadata::{pda::find_metadata_account, state::DataV2};
+use crate::{
+    error::ProgramError as BplError,
+    state::{Campaign, CampaignLocation, DailyCount, Location, Merchant, Promo, Sponsorship},
+    CreateMetaData, CreateNonFungible, IssueMembershipPass, TransferSol,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        hash::{hash, hashv},
+        instruction::Instruction,
+    },
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_2022::{
+        self,
+        spl_token_2022::{
+            self,
+            extension::{
+                non_transferable::NonTransferable, BaseStateWithExtensions, StateWithExtensions,
+            },
+            instruction::AuthorityType,
+        },
+    },
+    token_interface::TokenAccount,
+};
+use mpl_token_metadata::{
+    pda::{find_master_edition_account, find_metadata_account},
+    state::{DataV2, EDITION_MARKER_BIT_SIZE},
+};
 
 pub const UPGRADE_AUTHORITY: &str = "61mVTaw6hBtwWnSaGXRSJePFWEQqipeCka3evytEVNUp";
 pub const ADMIN_PREFIX: &str = "admin";
@@ -13,12 +40,23 @@ pub const LOCATION_PREFIX: &str = "location";
 pub const DEVICE_PREFIX: &str = "device";
 pub const CAMPAIGN_PREFIX: &str = "campaign";
 pub const CAMPAIGN_LOCATION_PREFIX: &str = "campaign_location";
+pub const CAMPAIGN_MEMBER_PREFIX: &str = "campaign_member";
 pub const PROMO_PREFIX: &str = "promo";
+pub const DAILY_COUNT_PREFIX: &str = "daily_count";
+pub const REFERRAL_PREFIX: &str = "referral";
+pub const STAMP_CARD_PREFIX: &str = "stamp_card";
+pub const SPONSORSHIP_PREFIX: &str = "sponsorship";
+pub const VOUCHER_PREFIX: &str = "voucher";
+pub const MINT_REQUEST_PREFIX: &str = "mint_request";
+pub const GIFT_PREFIX: &str = "gift";
+pub const CUSTOMER_VAULT_PREFIX: &str = "customer_vault";
+pub const MEMBERSHIP_PREFIX: &str = "membership";
+pub const MEMBERSHIP_PASS_PREFIX: &str = "membership_pass";
 pub const MAX_NAME_LENGTH: usize = 64;
 pub const MAX_URI_LENGTH: usize = 200;
 
-pub fn transfer_sol<'a, 'b, 'c, 'info>(
-    ctx: CpiContext<'a, 'b, 'c, 'info, TransferSol<'info>>,
+pub fn transfer_sol<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferSol<'info>>,
     lamports: u64,
 ) -> Result<()> {
     let ix = anchor_lang::solana_program::system_instruction::transfer(
@@ -37,14 +75,215 @@ pub fn transfer_sol<'a, 'b, 'c, 'info>(
     .map_err(Into::into)
 }
 
-pub fn create_metadata_accounts_v2<'a, 'b, 'c, 'info>(
-    ctx: CpiContext<'a, 'b, 'c, 'info, CreateMetaData<'info>>,
+/// Pays the platform burn fee for a promo token. If a sponsorship covering the promo is included
+/// and has the funds, the fee and the sponsorship reimbursement to the payee are paid from the
+/// sponsorship, otherwise the fee is paid from the campaign. The payee has to be the owner of the
+/// merchant of the location the token is redeemed at, which for a coalition campaign may not be
+/// the merchant that created the campaign.
+pub fn pay_burn_fee<'info>(
+    burn_fee: u64,
+    mint: &Pubkey,
+    location: &Pubkey,
+    campaign: AccountInfo<'info>,
+    platform: AccountInfo<'info>,
+    sponsorship: Option<&mut Account<'info, Sponsorship>>,
+    payee: Option<SponsorshipPayee<'_, 'info>>,
+) -> Result<()> {
+    match (sponsorship, payee) {
+        (Some(sponsorship), Some(payee)) => {
+            if payee.location.key() != *location
+                || payee.location.merchant != payee.merchant.key()
+                || payee.owner.key() != payee.merchant.owner
+            {
+                return Err(BplError::InvalidSponsorshipAccounts.into());
+            }
+            if !sponsorship.covers(mint) {
+                return Err(BplError::PromoNotSponsored.into());
+            }
+
+            let reimbursement = sponsorship.reimbursement_lamports;
+            let amount = burn_fee.checked_add(reimbursement).unwrap();
+            let sponsorship_info = sponsorship.to_account_info();
+            let rent = Rent::get()?.minimum_balance(sponsorship_info.data_len());
+            if sponsorship_info.lamports().saturating_sub(rent) >= amount {
+                move_lamports(&sponsorship_info, &platform, burn_fee)?;
+                move_lamports(
+                    &sponsorship_info,
+                    &payee.owner.to_account_info(),
+                    reimbursement,
+                )?;
+                sponsorship.redemptions += 1;
+                sponsorship.spent_lamports += amount;
+                return Ok(());
+            }
+            msg!("Sponsorship funds exhausted");
+        }
+        (None, None) => (),
+        _ => return Err(BplError::InvalidSponsorshipAccounts.into()),
+    }
+
+    move_lamports(&campaign, &platform, burn_fee)
+}
+
+/// Accounts identifying who is reimbursed for a sponsored redemption.
+pub struct SponsorshipPayee<'a, 'info> {
+    pub location: &'a Account<'info, Location>,
+    pub merchant: &'a Account<'info, Merchant>,
+    pub owner: &'a UncheckedAccount<'info>,
+}
+
+/// Moves lamports out of an account owned by the program.
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount > 0 {
+        **from.try_borrow_mut_lamports()? = from
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(BplError::InsufficientFunds)?;
+        **to.try_borrow_mut_lamports()? = to.lamports().checked_add(amount).unwrap();
+    }
+    Ok(())
+}
+
+/// Checks that a token of the promo can be minted: the promo and campaign max mints, the daily
+/// max mints of the campaign location and device, after rolling their counts over to the current
+/// day, and the promo schedule if it applies to minting.
+pub fn check_mint_limits(
+    promo: &Promo,
+    campaign: &Campaign,
+    campaign_location: &CampaignLocation,
+    location_count: &mut DailyCount,
+    device_count: &mut DailyCount,
+    unix_timestamp: i64,
+) -> Result<()> {
+    // Check to see if mint_count is still below max_mint.
+    if let Some(max_mint) = promo.max_mint {
+        if promo.mint_count >= max_mint {
+            return Err(BplError::MaxMintExceeded.into());
+        }
+    }
+
+    // Check to see if campaign mint_count is still below campaign max_mint.
+    if let Some(max_mint) = campaign.max_mint {
+        if campaign.mint_count >= max_mint {
+            return Err(BplError::CampaignMaxMintExceeded.into());
+        }
+    }
+
+    // Check daily limits for the campaign location and device.
+    location_count.roll(unix_timestamp);
+    device_count.roll(unix_timestamp);
+    if let Some(max_mint) = campaign_location.location_limits.max_mint {
+        if location_count.mint_count >= max_mint {
+            return Err(BplError::LocationMaxMintExceeded.into());
+        }
+    }
+    if let Some(max_mint) = campaign_location.device_limits.max_mint {
+        if device_count.mint_count >= max_mint {
+            return Err(BplError::DeviceMaxMintExceeded.into());
+        }
+    }
+
+    // Check the schedule if the promo applies it to minting.
+    if let Some(schedule) = &promo.schedule {
+        if schedule.check_mint && !schedule.includes(unix_timestamp) {
+            return Err(BplError::OutsideSchedule.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that a token of the promo can be burned, the same as [check_mint_limits] for the
+/// burn counts, except that the schedule always applies to burning.
+pub fn check_burn_limits(
+    promo: &Promo,
+    campaign: &Campaign,
+    campaign_location: &CampaignLocation,
+    location_count: &mut DailyCount,
+    device_count: &mut DailyCount,
+    unix_timestamp: i64,
+) -> Result<()> {
+    // Check to see if burn_count is still below max_burn.
+    if let Some(max_burn) = promo.max_burn {
+        if promo.burn_count >= max_burn {
+            return Err(BplError::MaxBurnExceeded.into());
+        }
+    }
+
+    // Check to see if campaign burn_count is still below campaign max_burn.
+    if let Some(max_burn) = campaign.max_burn {
+        if campaign.burn_count >= max_burn {
+            return Err(BplError::CampaignMaxBurnExceeded.into());
+        }
+    }
+
+    // Check daily limits for the campaign location and device.
+    location_count.roll(unix_timestamp);
+    device_count.roll(unix_timestamp);
+    if let Some(max_burn) = campaign_location.location_limits.max_burn {
+        if location_count.burn_count >= max_burn {
+            return Err(BplError::LocationMaxBurnExceeded.into());
+        }
+    }
+    if let Some(max_burn) = campaign_location.device_limits.max_burn {
+        if device_count.burn_count >= max_burn {
+            return Err(BplError::DeviceMaxBurnExceeded.into());
+        }
+    }
+
+    // Check that the promo is within its schedule, if it has one.
+    if let Some(schedule) = &promo.schedule {
+        if !schedule.includes(unix_timestamp) {
+            return Err(BplError::OutsideSchedule.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the wallet is on the allowlist, if the promo has one.
+pub fn check_allowlist(promo: &Promo, proof: &[[u8; 32]], wallet: &Pubkey) -> Result<()> {
+    if let Some(root) = &promo.allowlist {
+        if !verify_allowlist_proof(proof, root, allowlist_leaf(wallet)) {
+            return Err(BplError::NotAllowlisted.into());
+        }
+    }
+    Ok(())
+}
+
+/// Sets the close authority of a token account to the program authority so the program can
+/// close the account when it burns the last token in it. Only needed the first time a token is
+/// added to the account, with the token owner signing.
+pub fn set_close_authority<'info>(
+    token_program: AccountInfo<'info>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_owner: AccountInfo<'info>,
+    authority: &Pubkey,
+) -> Result<()> {
+    if token_account.close_authority.is_some() {
+        return Ok(());
+    }
+
+    let set_authority_ctx = anchor_spl::token_interface::SetAuthority {
+        current_authority: token_owner,
+        account_or_mint: token_account.to_account_info(),
+    };
+
+    anchor_spl::token_interface::set_authority(
+        CpiContext::new(token_program, set_authority_ctx),
+        AuthorityType::CloseAccount,
+        Some(*authority),
+    )
+}
+
+pub fn create_metadata_accounts_v2<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, CreateMetaData<'info>>,
     update_authority_is_signer: bool,
     is_mutable: bool,
     data: DataV2,
 ) -> Result<()> {
     let ix = mpl_token_metadata::instruction::create_metadata_accounts_v3(
-        mpl_token_metadata::ID.clone(),
+        mpl_token_metadata::ID,
         ctx.accounts.metadata_account.to_account_info().key(),
         ctx.accounts.mint.to_account_info().key(),
         ctx.accounts.mint_authority.key(),
@@ -77,18 +316,18 @@ pub fn create_metadata_accounts_v2<'a, 'b, 'c, 'info>(
     .map_err(Into::into)
 }
 
-pub fn create_master_edition_v3<'a, 'b, 'c, 'info>(
-    ctx: CpiContext<'a, 'b, 'c, 'info, CreateNonFungible<'info>>,
+pub fn create_master_edition_v3<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, CreateNonFungible<'info>>,
     max_supply: Option<u64>,
 ) -> Result<()> {
     let ix = mpl_token_metadata::instruction::create_master_edition_v3(
-        mpl_token_metadata::ID.clone(),
-        ctx.accounts.edition_account.key.clone(),
+        mpl_token_metadata::ID,
+        *ctx.accounts.edition_account.key,
         ctx.accounts.mint.to_account_info().key(),
-        ctx.accounts.authority.key.clone(),
-        ctx.accounts.authority.key.clone(),
+        *ctx.accounts.authority.key,
+        *ctx.accounts.authority.key,
         ctx.accounts.metadata_account.key(),
-        ctx.accounts.payer.key().clone(),
+        ctx.accounts.payer.key(),
         max_supply,
     );
     anchor_lang::solana_program::program::invoke_signed(
@@ -108,6 +347,95 @@ pub fn create_master_edition_v3<'a, 'b, 'c, 'info>(
     .map_err(Into::into)
 }
 
+/// Prints a new edition of a membership's master edition to the pass mint. The master edition
+/// token is held by the program authority, which also becomes the update authority of the print.
+pub fn mint_new_edition_from_master_edition_via_token<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, IssueMembershipPass<'info>>,
+    edition: u64,
+) -> Result<()> {
+    let ix = mpl_token_metadata::instruction::mint_new_edition_from_master_edition_via_token(
+        mpl_token_metadata::ID,
+        ctx.accounts.pass_metadata.key(),
+        ctx.accounts.pass_edition.key(),
+        ctx.accounts.master_edition.key(),
+        ctx.accounts.pass_mint.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.payer.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.master_token_account.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.master_metadata.key(),
+        ctx.accounts.master_mint.key(),
+        edition,
+    );
+    anchor_lang::solana_program::program::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.pass_metadata.to_account_info(),
+            ctx.accounts.pass_edition.to_account_info(),
+            ctx.accounts.master_edition.to_account_info(),
+            ctx.accounts.pass_mint.to_account_info(),
+            ctx.accounts.edition_marker.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.master_token_account.to_account_info(),
+            ctx.accounts.master_metadata.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+        ],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// `TokenInstruction::MetadataPointerExtension` as of spl-token-2022 0.7.0, the release that
+/// added the metadata pointer extension.
+pub const METADATA_POINTER_EXTENSION_INSTRUCTION: u8 = 39;
+
+/// `MetadataPointerInstruction::Initialize` as of spl-token-2022 0.7.0.
+pub const METADATA_POINTER_INITIALIZE_INSTRUCTION: u8 = 0;
+
+/// Initializes the metadata pointer extension on a Token-2022 mint. Must be called before the
+/// mint is initialized.
+///
+/// The spl-token-2022 release compatible with our solana version (0.6) doesn't include the
+/// metadata pointer extension so the instruction is serialized by hand:
+/// [METADATA_POINTER_EXTENSION_INSTRUCTION], [METADATA_POINTER_INITIALIZE_INSTRUCTION],
+/// authority and metadata address, with zeroed pubkeys for `None`.
+pub fn initialize_metadata_pointer<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    authority: Option<Pubkey>,
+    metadata_address: Option<Pubkey>,
+) -> Result<()> {
+    let mut data = vec![
+        METADATA_POINTER_EXTENSION_INSTRUCTION,
+        METADATA_POINTER_INITIALIZE_INSTRUCTION,
+    ];
+    data.extend_from_slice(authority.unwrap_or_default().as_ref());
+    data.extend_from_slice(metadata_address.unwrap_or_default().as_ref());
+
+    let ix = anchor_lang::solana_program::instruction::Instruction {
+        program_id: token_program.key(),
+        accounts: vec![AccountMeta::new(mint.key(), false)],
+        data,
+    };
+    anchor_lang::solana_program::program::invoke(&ix, &[mint]).map_err(Into::into)
+}
+
+/// Checks whether a mint has the Token-2022 non-transferable extension. Tokens of these mints
+/// can be minted and burned but not transferred between accounts.
+pub fn is_non_transferable(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != token_2022::ID {
+        return Ok(false);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(state.get_extension::<NonTransferable>().is_ok())
+}
+
 pub fn create_memo(memo: String, account_infos: Vec<AccountInfo>) -> Result<()> {
     let signer_pubkeys: Vec<&Pubkey> = account_infos
         .iter()
@@ -117,8 +445,81 @@ pub fn create_memo(memo: String, account_infos: Vec<AccountInfo>) -> Result<()>
     anchor_lang::solana_program::program::invoke(&ix, &account_infos).map_err(Into::into)
 }
 
-pub fn find_associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
-    get_associated_token_address(wallet, mint)
+pub fn find_associated_token_address(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    get_associated_token_address_with_program_id(wallet, mint, token_program)
+}
+
+/// Returns the allowlist merkle tree leaf for a wallet. Leaves and nodes are prefixed with
+/// different bytes so that a node can't be passed off as a leaf.
+pub fn allowlist_leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[&[0u8], wallet.as_ref()]).to_bytes()
+}
+
+/// Returns the parent of two allowlist merkle tree nodes. Nodes are sorted before hashing so
+/// proofs don't need to include the position of each sibling.
+pub fn allowlist_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[&[1u8], left, right]).to_bytes()
+}
+
+pub fn verify_allowlist_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| allowlist_node(&node, sibling))
+        == *root
+}
+
+// Layout of Ed25519 program instruction data with a single signature: the number of signatures
+// and a padding byte, seven u16 offsets, then the public key, signature and message.
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_PUBKEY_START: usize = ED25519_OFFSETS_START + 14;
+const ED25519_SIGNATURE_START: usize = ED25519_PUBKEY_START + 32;
+const ED25519_MESSAGE_START: usize = ED25519_SIGNATURE_START + 64;
+
+/// Returns Ed25519 program instruction data verifying a single signature, with the public key,
+/// signature and message all contained in the instruction itself.
+pub fn ed25519_instruction_data(pubkey: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(ED25519_MESSAGE_START + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for offset in [
+        ED25519_SIGNATURE_START,
+        u16::MAX as usize,
+        ED25519_PUBKEY_START,
+        u16::MAX as usize,
+        ED25519_MESSAGE_START,
+        message.len(),
+        u16::MAX as usize,
+    ] {
+        data.extend_from_slice(&(offset as u16).to_le_bytes());
+    }
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    data
+}
+
+/// Checks that an Ed25519 program instruction verified a single signature by `signer` over
+/// `message`. The instruction indexes have to point at the Ed25519 instruction itself, otherwise
+/// the signature could have been verified over data elsewhere in the transaction.
+pub fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> bool {
+    let data = &ix.data;
+    if ix.program_id != ed25519_program::ID || data.len() < ED25519_MESSAGE_START || data[0] != 1 {
+        return false;
+    }
+    let offset = |i: usize| {
+        let start = ED25519_OFFSETS_START + i * 2;
+        u16::from_le_bytes([data[start], data[start + 1]]) as usize
+    };
+    let (pubkey_offset, message_offset, message_size) = (offset(2), offset(4), offset(5));
+    [offset(1), offset(3), offset(6)]
+        .iter()
+        .all(|index| *index == u16::MAX as usize)
+        && data.get(pubkey_offset..pubkey_offset + 32) == Some(signer.as_ref())
+        && data.get(message_offset..message_offset + message_size) == Some(message)
 }
 
 pub fn find_admin_address() -> (Pubkey, u8) {
@@ -133,13 +534,27 @@ pub fn find_merchant_address(owner: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[MERCHANT_PREFIX.as_bytes(), owner.as_ref()], &crate::id())
 }
 
+/// Seed for the address of an account identified by name, i.e. a [Location], [Device] or
+/// [Campaign].
+///
+/// Version 2 of the derivation. Names are hashed since seeds are limited to 32 bytes, so that any
+/// utf-8 name up to `MAX_NAME_LENGTH` bytes can be used. Accounts created with version 1, which
+/// used the raw name, can still be found with the `_v1` functions.
+///
+/// [Location]: crate::state::Location
+/// [Device]: crate::state::Device
+/// [Campaign]: crate::state::Campaign
+pub fn name_seed(name: &str) -> [u8; 32] {
+    hash(name.as_bytes()).to_bytes()
+}
+
 pub fn find_location_address(merchant_owner: &Pubkey, name: &str) -> (Pubkey, u8) {
-    let merchant = find_merchant_address(&merchant_owner).0;
+    let merchant = find_merchant_address(merchant_owner).0;
     Pubkey::find_program_address(
         &[
             LOCATION_PREFIX.as_bytes(),
             merchant.as_ref(),
-            name.as_bytes(),
+            &name_seed(name),
         ],
         &crate::id(),
     )
@@ -147,13 +562,53 @@ pub fn find_location_address(merchant_owner: &Pubkey, name: &str) -> (Pubkey, u8
 
 pub fn find_device_address(location: &Pubkey, name: &str) -> (Pubkey, u8) {
     Pubkey::find_program_address(
-        &[DEVICE_PREFIX.as_bytes(), location.as_ref(), name.as_bytes()],
+        &[
+            DEVICE_PREFIX.as_bytes(),
+            location.as_ref(),
+            &name_seed(name),
+        ],
         &crate::id(),
     )
 }
 
 pub fn find_campaign_address(merchant: &Pubkey, name: &str) -> (Pubkey, u8) {
     Pubkey::find_program_address(
+        &[
+            CAMPAIGN_PREFIX.as_bytes(),
+            merchant.as_ref(),
+            &name_seed(name),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Address of a location created with version 1 of the derivation, or `None` if the name is
+/// longer than a seed can be.
+pub fn find_location_address_v1(merchant_owner: &Pubkey, name: &str) -> Option<(Pubkey, u8)> {
+    let merchant = find_merchant_address(merchant_owner).0;
+    Pubkey::try_find_program_address(
+        &[
+            LOCATION_PREFIX.as_bytes(),
+            merchant.as_ref(),
+            name.as_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Address of a device created with version 1 of the derivation, or `None` if the name is
+/// longer than a seed can be.
+pub fn find_device_address_v1(location: &Pubkey, name: &str) -> Option<(Pubkey, u8)> {
+    Pubkey::try_find_program_address(
+        &[DEVICE_PREFIX.as_bytes(), location.as_ref(), name.as_bytes()],
+        &crate::id(),
+    )
+}
+
+/// Address of a campaign created with version 1 of the derivation, or `None` if the name is
+/// longer than a seed can be.
+pub fn find_campaign_address_v1(merchant: &Pubkey, name: &str) -> Option<(Pubkey, u8)> {
+    Pubkey::try_find_program_address(
         &[
             CAMPAIGN_PREFIX.as_bytes(),
             merchant.as_ref(),
@@ -178,13 +633,136 @@ pub fn find_promo_address(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[PROMO_PREFIX.as_bytes(), mint.as_ref()], &crate::id())
 }
 
+pub fn find_location_daily_count_address(campaign_location: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DAILY_COUNT_PREFIX.as_bytes(), campaign_location.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_device_daily_count_address(
+    campaign_location: &Pubkey,
+    device: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DAILY_COUNT_PREFIX.as_bytes(),
+            campaign_location.as_ref(),
+            device.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_campaign_member_address(campaign: &Pubkey, merchant: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CAMPAIGN_MEMBER_PREFIX.as_bytes(),
+            campaign.as_ref(),
+            merchant.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_sponsorship_address(campaign: &Pubkey, sponsor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SPONSORSHIP_PREFIX.as_bytes(),
+            campaign.as_ref(),
+            sponsor.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_referral_address(mint: &Pubkey, referred: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFERRAL_PREFIX.as_bytes(), mint.as_ref(), referred.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_stamp_card_address(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[STAMP_CARD_PREFIX.as_bytes(), mint.as_ref(), owner.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_mint_request_address(mint: &Pubkey, token_owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            MINT_REQUEST_PREFIX.as_bytes(),
+            mint.as_ref(),
+            token_owner.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_gift_address(mint: &Pubkey, recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[GIFT_PREFIX.as_bytes(), mint.as_ref(), recipient.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_customer_vault_address(identity_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CUSTOMER_VAULT_PREFIX.as_bytes(), identity_hash.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_voucher_claim_address(promo: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VOUCHER_PREFIX.as_bytes(),
+            promo.as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn find_membership_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MEMBERSHIP_PREFIX.as_bytes(), mint.as_ref()], &crate::id())
+}
+
+pub fn find_membership_pass_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MEMBERSHIP_PASS_PREFIX.as_bytes(), mint.as_ref()],
+        &crate::id(),
+    )
+}
+
 pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
     find_metadata_account(mint)
 }
 
+pub fn find_edition_address(mint: &Pubkey) -> (Pubkey, u8) {
+    find_master_edition_account(mint)
+}
+
+/// Returns the token metadata edition marker of a master edition, which records which edition
+/// numbers have been printed in groups of [EDITION_MARKER_BIT_SIZE].
+pub fn find_edition_marker_address(master_mint: &Pubkey, edition: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::ID.as_ref(),
+            master_mint.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            (edition / EDITION_MARKER_BIT_SIZE).to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::ID,
+    )
+}
+
 pub fn find_program_data_address() -> Pubkey {
     Pubkey::find_program_address(
-        &[&crate::id().as_ref()],
+        &[crate::id().as_ref()],
         &Pubkey::from_str("BPFLoaderUpgradeab1e11111111111111111111111").unwrap(),
     )
     .0
@@ -200,3 +778,72 @@ pub fn puffed_out_string(s: &str, size: usize) -> String {
     }
     s.to_owned() + std::str::from_utf8(&array_of_zeroes).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowlist_node_is_sorted() {
+        let a = allowlist_leaf(&Pubkey::new_unique());
+        let b = allowlist_leaf(&Pubkey::new_unique());
+
+        assert_eq!(allowlist_node(&a, &b), allowlist_node(&b, &a));
+        assert_ne!(allowlist_node(&a, &b), allowlist_node(&a, &a));
+    }
+
+    #[test]
+    fn test_allowlist_leaf_and_node_prefixes() {
+        let wallet = Pubkey::new_unique();
+        let leaf = allowlist_leaf(&wallet);
+
+        assert_eq!(leaf, hashv(&[&[0u8], wallet.as_ref()]).to_bytes());
+        assert_ne!(leaf, hashv(&[wallet.as_ref()]).to_bytes());
+
+        let (a, b) = (leaf, allowlist_leaf(&Pubkey::new_unique()));
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        assert_eq!(
+            allowlist_node(&a, &b),
+            hashv(&[&[1u8], &left, &right]).to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_verify_allowlist_proof() {
+        let wallets: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = wallets.iter().map(allowlist_leaf).collect();
+        let left = allowlist_node(&leaves[0], &leaves[1]);
+        let right = allowlist_node(&leaves[2], &leaves[3]);
+        let root = allowlist_node(&left, &right);
+
+        assert!(verify_allowlist_proof(
+            &[leaves[1], right],
+            &root,
+            leaves[0]
+        ));
+        assert!(verify_allowlist_proof(&[leaves[2], left], &root, leaves[3]));
+        // a single wallet list is its own root
+        assert!(verify_allowlist_proof(&[], &leaves[0], leaves[0]));
+
+        assert!(!verify_allowlist_proof(
+            &[leaves[1], right],
+            &root,
+            leaves[2]
+        ));
+        assert!(!verify_allowlist_proof(&[leaves[1]], &root, leaves[0]));
+        let outsider = allowlist_leaf(&Pubkey::new_unique());
+        assert!(!verify_allowlist_proof(
+            &[leaves[1], right],
+            &root,
+            outsider
+        ));
+
+        // a wallet whose key is an inner node isn't on the list, since leaves are prefixed
+        let node_wallet = Pubkey::new_from_array(left);
+        assert!(!verify_allowlist_proof(
+            &[right],
+            &root,
+            allowlist_leaf(&node_wallet)
+        ));
+    }
+}
@@ -86,6 +86,7 @@ echo '{
     "addresses": [
         "HB53jiCac5VtNdokJeibrfd1QJsyWWFe56M1TQUSKQfY",
         "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
         "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
         "hausS13jsjafwWwGqZTUQRmWyvyxn9EQpqMwV1PBBmk"
     ]